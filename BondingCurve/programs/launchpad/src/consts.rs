@@ -4,9 +4,33 @@ pub const ANCHOR_DISCRIMINATOR: usize = 8;
 pub const MEME_TOKEN_DECIMALS: u64 = 1_000_000;
 pub const WSOL_DECIMALS: u64 = 1_000_000_000;
 
-pub const DEFAULT_PRICE_FACTOR_NUMERATOR: u64 = 1;
+// omega_m * price factor must land between gamma_m and 2 * gamma_m for a
+// positive slope and intercept, which 1/1 misses with a 310M/690M split
+pub const DEFAULT_PRICE_FACTOR_NUMERATOR: u64 = 3;
 pub const DEFAULT_PRICE_FACTOR_DENOMINATOR: u64 = 1;
 
+pub const MIN_PRICE_FACTOR: u64 = 1;
+pub const MAX_PRICE_FACTOR: u64 = 100;
+
+pub const DEFAULT_MIN_SLOT_BETWEEN_TRADES: u64 = 0; // disabled
+
+pub const DEFAULT_CREATOR_FEE_BPS: u16 = 2_000; // 20% of quote fees go to the creator
+
+pub const DEFAULT_MAX_PRICE_IMPACT_BPS: u16 = 0; // disabled
+
+pub const DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS: u16 = 0; // disabled
+
+pub const DEFAULT_MIN_MEME_RESERVE: u64 = 0; // disabled
+
+pub const DEFAULT_MIN_UNIQUE_BUYERS: u32 = 0; // disabled
+
+pub const DEFAULT_MIN_QUOTE_IN: u64 = 0; // disabled
+pub const DEFAULT_MIN_MEME_IN: u64 = 0; // disabled
+
+pub const DEFAULT_WALLET_COOLDOWN_SECS: u64 = 0; // disabled
+
+pub const CREATOR_COOLDOWN_SECS: i64 = 3_600; // one launch per hour per creator
+
 pub const DEFAULT_MAX_M_LP: u128 = 310_000_000_000_000; // 310B tokens for LP
 pub const DEFAULT_MAX_M: u128 = 690_000_000_000_000; // 690B tokens for trading
 
@@ -14,8 +38,72 @@ pub const MAX_MEME_TOKENS: u128 = DEFAULT_MAX_M_LP + DEFAULT_MAX_M;
 
 pub const DECIMALS_S: u128 = 1_000_000_000;
 
+// How far the curve's supply at `gamma_s` may drift from `gamma_m`
+pub const CURVE_TOLERANCE_BPS: u64 = 10; // 0.1%
+
+// Largest quote mint precision the curve math supports, 10^12
+pub const MAX_QUOTE_DECIMALS: u8 = 12;
+// Largest alpha/beta precision `compute_decimals` produces
+pub const MAX_CURVE_DECIMALS: u128 = 100_000_000;
+
 pub const MAX_AIRDROPPED_TOKENS: u64 = 100_000_000_000_000;
 
+pub const MAX_CREATOR_LOCKED_TOKENS: u64 = 50_000_000_000_000; // 50M tokens
+
+pub const MAX_FIRST_BUYER_BONUS_BPS: u16 = 1_000; // 10% of the first buy
+
+pub const MAX_BURN_UNSOLD_BPS: u16 = 5_000; // Half the unsold curve meme
+
+pub const MAX_BATCH_BUYS: usize = 16;
+
+pub const MAX_AIRDROP_RECIPIENTS: usize = 10;
+
+// Compute budgets `estimate_compute` recommends, with headroom over the units
+// the instructions consume on a local validator. tests/launchpad.ts measures a
+// swap against its budget. The runtime's default is 200k units per instruction.
+pub const SWAP_COMPUTE_UNITS: u32 = 150_000;
+pub const BATCH_BUY_COMPUTE_UNITS: u32 = 20_000; // Per buy after the first
+pub const MIGRATION_COMPUTE_UNITS: u32 = 400_000; // AMM pool creation included
+pub const AIRDROP_BASE_COMPUTE_UNITS: u32 = 40_000;
+pub const AIRDROP_RECIPIENT_COMPUTE_UNITS: u32 = 20_000;
+
+// Swaps kept by a pool's `PriceSnapshots` ring buffer
+pub const PRICE_SNAPSHOT_CAPACITY: usize = 32;
+
+// Pools listed by one `PoolRegistry` page before the next page starts
+pub const POOL_REGISTRY_PAGE_CAPACITY: usize = 64;
+
+// Metaplex metadata field limits, in bytes
+pub const MAX_NAME_LENGTH: usize = 32;
+pub const MAX_SYMBOL_LENGTH: usize = 10;
+pub const MAX_URI_LENGTH: usize = 200;
+
+pub const BPS_DENOMINATOR: u64 = 10_000;
+
+// Scale of `StakingPool::acc_reward_per_share`
+pub const REWARD_PER_SHARE_PRECISION: u128 = 1_000_000_000_000;
+
+// Sells returning at most this share of the quote reserve skip the exact quadratic
+pub const LINEAR_FAST_PATH_BPS: u64 = 100; // 1%
+
+// Share of the curve reserves seeding the AMM on migration, the rest stays on the curve
+pub const MIGRATION_LP_BPS: u64 = 9_500; // 95%
+
+// Share of the migrated quote skimmed to the protocol fee vault
+pub const MIGRATION_FEE_BPS: u64 = 100; // 1%
+
+// Quote reserve, as a share of the target, graduating a `QuotePercent` pool
+pub const MIGRATION_QUOTE_BPS: u64 = 8_000; // 80%
+
+// Meme sold, as a share of the trading supply, graduating a `TokenTarget` pool
+pub const MIGRATION_MEME_BPS: u64 = 8_000; // 80%
+
+// Smallest quote reserve worth seeding an AMM pool with, in quote base units
+pub const MIN_MIGRATION_QUOTE: u64 = 5_000_000_000; // 5 SOL
+
+// Raydium CPMM derives its AMM configs from this seed and a big-endian u16 index
+pub const RAYDIUM_AMM_CONFIG_SEED: &[u8] = b"amm_config";
+
 #[cfg(feature = "localnet-testing")]
 pub const LOCK_TIME: i64 = 4; // 4 seconds
 #[cfg(feature = "mainnet-testing")]
@@ -38,8 +126,10 @@ pub const MIN_LINEAR: i64 = 600; // 10 minutes
 pub const MIN_LINEAR: i64 = 86_400; // 1 day
 pub const MIN_LINEAR: i64 = 86_400; // 1 day
 pub const MAX_LINEAR: i64 = 1_123_200; // 13 days
+pub const MAX_CREATOR_VESTING_PERIOD: i64 = 31_536_000; // 365 days
 
 pub const POINTS_PDA: &'static [u8; 10] = b"points_pda";
+pub const POINTS_EPOCH_SEED: &[u8; 12] = b"points_epoch";
 
 #[cfg(feature = "testing")]
 pub const ADMIN_KEY: Pubkey =
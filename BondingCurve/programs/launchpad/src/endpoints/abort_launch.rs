@@ -0,0 +1,238 @@
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::creator_lock::CreatorLock;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
+
+impl<'info> AbortLaunch<'info> {
+    /// Creates a CPI context for handing the airdrop allocation back to the
+    /// creator.
+    fn return_to_creator(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.meme_vault.to_account_info(),
+            to: self.creator_meme.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for handing the unclaimed creator lock back to
+    /// the creator.
+    fn release_lock(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.creator_lock_vault.to_account_info(),
+            to: self.creator_meme.to_account_info(),
+            authority: self.creator_lock.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for closing the emptied lock vault, with the
+    /// rent going to the creator.
+    fn close_lock_vault(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.creator_lock_vault.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.creator_lock.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for sweeping quote sent to the untraded pool back
+    /// to the creator, so its vault can be closed.
+    fn sweep_quote(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: self.creator_quote.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for burning the unsold supply left in the vault.
+    fn burn_unsold(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: self.meme_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for closing one of the pool's vaults, with the
+    /// rent going to the creator.
+    fn close_vault(
+        &self,
+        vault: &Account<'info, TokenAccount>,
+    ) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: vault.to_account_info(),
+            destination: self.creator.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Unwinds a launch nobody traded on yet. The creator gets the creator and
+/// airdrop allocations back, along with any quote sent to the pool, the rest
+/// of the supply is burned and the pool, its vaults and the creator lock are
+/// closed.
+///
+/// # Errors
+/// * `AmmError::LaunchAlreadyTraded` - If the pool saw any trade
+/// * `AmmError::AlreadyMigrated` - If the pool already migrated
+pub fn handle(ctx: Context<AbortLaunch>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.pool.check_abortable()?;
+
+    let creator_tokens = accs.creator_lock.unclaimed_amount();
+    let airdrop_tokens = accs.pool.airdropped_tokens;
+    let burned = accs
+        .meme_vault
+        .amount
+        .checked_sub(airdrop_tokens)
+        .ok_or(error!(AmmError::ExceedsPoolReserve))?;
+    let returned = creator_tokens
+        .checked_add(airdrop_tokens)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    let lock_seeds = &[
+        CreatorLock::CREATOR_LOCK_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.creator_lock],
+    ];
+
+    let lock_signer_seeds = &[&lock_seeds[..]];
+
+    if airdrop_tokens > 0 {
+        token::transfer(
+            accs.return_to_creator().with_signer(signer_seeds),
+            airdrop_tokens,
+        )?;
+    }
+
+    // The lock lives in its own vault, owned by the lock account
+    if creator_tokens > 0 {
+        token::transfer(
+            accs.release_lock().with_signer(lock_signer_seeds),
+            creator_tokens,
+        )?;
+    }
+    token::close_account(accs.close_lock_vault().with_signer(lock_signer_seeds))?;
+
+    if burned > 0 {
+        token::burn(accs.burn_unsold().with_signer(signer_seeds), burned)?;
+    }
+
+    token::close_account(accs.close_vault(&accs.meme_vault).with_signer(signer_seeds))?;
+
+    // Nothing was bought, but anyone can send quote to the vault, and a token
+    // account only closes empty. Refund what it actually holds
+    accs.quote_vault.reload()?;
+    let swept_quote = accs.quote_vault.amount;
+    if swept_quote > 0 {
+        token::transfer(accs.sweep_quote().with_signer(signer_seeds), swept_quote)?;
+    }
+    token::close_account(
+        accs.close_vault(&accs.quote_vault)
+            .with_signer(signer_seeds),
+    )?;
+
+    msg!(
+        "launch_aborted returned: {} burned: {} swept_quote: {}",
+        returned,
+        burned,
+        swept_quote
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AbortLaunch<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        constraint = pool.is_creator(&creator.key()) @ AmmError::InvalidAccountInput
+    )]
+    pub pool: Box<Account<'info, BoundPool>>,
+
+    #[account(
+        mut,
+        close = creator,
+        seeds = [CreatorLock::CREATOR_LOCK_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    pub creator_lock: Box<Account<'info, CreatorLock>>,
+
+    #[account(
+        mut,
+        seeds = [CreatorLock::VAULT_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub creator_lock_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.mint == meme_mint.key() @ AmmError::InvalidTokenMints
+    )]
+    pub meme_mint: Account<'info, Mint>,
+
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = creator_meme.owner == creator.key()
+    )]
+    pub creator_meme: Account<'info, TokenAccount>,
+
+    /// Creator's quote account, refunded any quote sent to the pool
+    #[account(
+        mut,
+        constraint = creator_quote.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = creator_quote.owner == creator.key()
+    )]
+    pub creator_quote: Account<'info, TokenAccount>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,22 @@
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Completes an admin handoff, signed by the wallet `transfer_admin`
+/// proposed.
+pub fn handle(ctx: Context<AcceptAdmin>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.global_config.accept_admin(&accs.pending_admin.key())?;
+
+    msg!("admin_accepted: {}", accs.pending_admin.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptAdmin<'info> {
+    pub pending_admin: Signer<'info>,
+
+    #[account(mut, seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
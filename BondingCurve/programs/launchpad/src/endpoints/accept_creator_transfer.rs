@@ -0,0 +1,76 @@
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::creator_lock::CreatorLock;
+use anchor_lang::prelude::*;
+
+/// Completes a creator handoff, signed by the wallet
+/// `propose_creator_transfer` proposed. An unclaimed creator allocation
+/// moves along with the pool, so the new creator can claim it.
+pub fn handle(ctx: Context<AcceptCreatorTransfer>) -> Result<()> {
+    let accs = ctx.accounts;
+    let new_creator = accs.new_creator.key();
+
+    accs.pool.accept_creator(&new_creator)?;
+    accs.creator_lock.hand_over(new_creator);
+
+    msg!("creator_transfer_accepted: {}", new_creator);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct AcceptCreatorTransfer<'info> {
+    pub new_creator: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+
+    /// Pool's creator allocation lock, handed over with the pool
+    #[account(
+        mut,
+        seeds = [CreatorLock::CREATOR_LOCK_PREFIX, pool.key().as_ref()],
+        bump,
+        constraint = creator_lock.pool == pool.key() @ AmmError::InvalidAccountInput
+    )]
+    pub creator_lock: Account<'info, CreatorLock>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bound::test_pool;
+
+    #[test]
+    fn test_new_creator_claims_after_handoff() {
+        // ARRANGE: A pool whose creator locked 10M tokens
+        println!("🧪 Testing a claim by the creator who took the pool over");
+        let (creator, next_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut pool = test_pool();
+        pool.creator_addr = creator;
+        let mut lock = CreatorLock {
+            pool: Pubkey::new_unique(),
+            creator,
+            amount: 10_000_000_000_000,
+            unlock_ts: 1_000,
+            vesting_period: 0,
+            claimed_amount: 0,
+            claimed: false,
+        };
+
+        // ACT: Hand the pool over the way the endpoint does
+        pool.propose_creator(next_creator);
+        pool.accept_creator(&next_creator).unwrap();
+        lock.hand_over(next_creator);
+
+        // ASSERT: The claim gates now point at the new creator only
+        assert!(pool.is_creator(&next_creator) && lock.creator == next_creator);
+        assert!(!pool.is_creator(&creator) && lock.creator != creator);
+        assert_eq!(lock.claim(1_000).unwrap(), 10_000_000_000_000);
+
+        // A fully claimed lock keeps the record of who claimed it
+        lock.hand_over(creator);
+        assert_eq!(lock.creator, next_creator);
+
+        println!("✅ Claim after handoff test passed!");
+    }
+}
@@ -0,0 +1,93 @@
+use crate::err::AmmError;
+use crate::models::limit_order::LimitOrder;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Transfer};
+
+/// Cancels a limit order: the unfilled quote goes back to the owner, and the
+/// escrow and order accounts are closed with their rent.
+pub fn handle(ctx: Context<CancelLimitOrder>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let refund = accs.limit_order.cancel();
+
+    let order = &accs.limit_order;
+    let order_id = order.order_id.to_le_bytes();
+    let order_seeds = &[
+        LimitOrder::LIMIT_ORDER_PREFIX.as_ref(),
+        order.pool.as_ref(),
+        order.owner.as_ref(),
+        &order_id,
+        &[ctx.bumps.limit_order],
+    ];
+
+    if refund > 0 {
+        token::transfer(
+            CpiContext::new(
+                accs.token_program.to_account_info(),
+                Transfer {
+                    from: accs.escrow.to_account_info(),
+                    to: accs.user_sol.to_account_info(),
+                    authority: accs.limit_order.to_account_info(),
+                },
+            )
+            .with_signer(&[&order_seeds[..]]),
+            refund,
+        )?;
+    }
+
+    token::close_account(
+        CpiContext::new(
+            accs.token_program.to_account_info(),
+            CloseAccount {
+                account: accs.escrow.to_account_info(),
+                destination: accs.owner.to_account_info(),
+                authority: accs.limit_order.to_account_info(),
+            },
+        )
+        .with_signer(&[&order_seeds[..]]),
+    )?;
+
+    msg!(
+        "limit_order_cancelled: {} refund: {}",
+        accs.limit_order.order_id,
+        refund
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct CancelLimitOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        has_one = owner,
+        close = owner,
+        seeds = [
+            LimitOrder::LIMIT_ORDER_PREFIX.as_ref(),
+            limit_order.pool.as_ref(),
+            owner.key().as_ref(),
+            &limit_order.order_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    #[account(
+        mut,
+        seeds = [LimitOrder::ESCROW_PREFIX.as_ref(), limit_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    /// The owner's quote token account receiving the refund
+    #[account(
+        mut,
+        constraint = user_sol.mint == escrow.mint @ AmmError::InvalidTokenMints
+    )]
+    pub user_sol: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
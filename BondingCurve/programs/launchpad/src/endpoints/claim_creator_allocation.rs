@@ -0,0 +1,82 @@
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::creator_lock::CreatorLock;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> ClaimCreatorAllocation<'info> {
+    /// Creates a CPI context for releasing the locked allocation from the
+    /// lock vault to the creator.
+    fn send_allocation_to_creator(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.creator_lock_vault.to_account_info(),
+            to: self.creator_meme.to_account_info(),
+            authority: self.creator_lock.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Releases the part of the creator's allocation vested since the last claim,
+/// all of it once `unlock_ts` has passed without a vesting period.
+///
+/// # Errors
+/// * `AmmError::CreatorAllocationLocked` - If the lock hasn't expired yet
+/// * `AmmError::NoTokensToWithdraw` - If nothing vested since the last claim
+pub fn handle(ctx: Context<ClaimCreatorAllocation>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let now = Clock::get()?.unix_timestamp;
+    let amount = accs.creator_lock.claim(now)?;
+
+    let seeds = &[
+        CreatorLock::CREATOR_LOCK_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.creator_lock],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.send_allocation_to_creator().with_signer(signer_seeds),
+        amount,
+    )?;
+
+    msg!("creator_allocation_claimed: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimCreatorAllocation<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(constraint = pool.is_creator(&creator.key()) @ AmmError::InvalidAccountInput)]
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        seeds = [CreatorLock::CREATOR_LOCK_PREFIX, pool.key().as_ref()],
+        bump,
+        constraint = creator_lock.creator == creator.key() @ AmmError::InvalidAccountInput
+    )]
+    pub creator_lock: Account<'info, CreatorLock>,
+
+    #[account(
+        mut,
+        seeds = [CreatorLock::VAULT_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub creator_lock_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = creator_meme.owner == creator.key()
+    )]
+    pub creator_meme: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,60 @@
+use crate::consts::POINTS_PDA;
+use crate::models::points_escrow::PointsEscrow;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Releases the signer's escrowed referral points once their vesting delay
+/// has passed.
+pub fn handle(ctx: Context<ClaimPoints>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let amount = accs.points_escrow.claim(Clock::get()?.unix_timestamp)?;
+
+    let point_pda: &[&[u8]] = &[POINTS_PDA, &[ctx.bumps.points_pda]];
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            accs.token_program.to_account_info(),
+            Transfer {
+                from: accs.points_escrow_vault.to_account_info(),
+                to: accs.owner_points.to_account_info(),
+                authority: accs.points_pda.to_account_info(),
+            },
+            &[point_pda],
+        ),
+        amount,
+    )?;
+
+    msg!("points_claimed: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimPoints<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [PointsEscrow::POINTS_ESCROW_PREFIX, owner.key().as_ref()],
+        bump
+    )]
+    pub points_escrow: Account<'info, PointsEscrow>,
+
+    #[account(mut, seeds = [PointsEscrow::VAULT_PREFIX], bump)]
+    pub points_escrow_vault: Account<'info, TokenAccount>,
+
+    /// Owner's points token account receiving the claim
+    #[account(
+        mut,
+        token::mint = points_escrow_vault.mint,
+        token::authority = owner,
+    )]
+    pub owner_points: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA owning the escrow vault
+    #[account(seeds = [POINTS_PDA], bump)]
+    pub points_pda: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,89 @@
+use crate::err::AmmError;
+use crate::models::stake_account::StakeAccount;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> ClaimStakingRewards<'info> {
+    /// Creates a CPI context for paying quote rewards from the staking pool's
+    /// quote vault to the staker.
+    fn send_rewards_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.staking_quote_vault.to_account_info(),
+            to: self.user_quote.to_account_info(),
+            authority: self.staking_pool_signer_pda.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Pays out the quote rewards earned by the caller's stake, i.e.
+/// `amount * acc_reward_per_share - reward_debt` plus anything carried over
+/// from earlier stake changes.
+///
+/// # Errors
+/// * `AmmError::NoTokensToWithdraw` - If there is nothing to claim
+pub fn handle(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let rewards = accs.staking.claim_rewards(&mut accs.stake_account)?;
+
+    if rewards == 0 {
+        return Err(error!(AmmError::NoTokensToWithdraw));
+    }
+
+    let staking_seeds = &[
+        StakingPool::SIGNER_PDA_PREFIX,
+        &accs.staking.key().to_bytes()[..],
+        &[ctx.bumps.staking_pool_signer_pda],
+    ];
+
+    let staking_signer_seeds = &[&staking_seeds[..]];
+
+    token::transfer(
+        accs.send_rewards_to_user()
+            .with_signer(staking_signer_seeds),
+        rewards,
+    )?;
+
+    msg!("staking_rewards_claimed: {}", rewards);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    pub owner: Signer<'info>,
+
+    pub staking: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [StakeAccount::STAKE_ACCOUNT_PREFIX, staking.key().as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = stake_account.owner == owner.key() @ AmmError::InvalidAccountInput
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = staking_quote_vault.owner == staking_pool_signer_pda.key()
+            @ AmmError::InvalidAccountInput
+    )]
+    pub staking_quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = user_quote.mint == staking_quote_vault.mint @ AmmError::InvalidTokenMints,
+        constraint = user_quote.owner == owner.key()
+    )]
+    pub user_quote: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
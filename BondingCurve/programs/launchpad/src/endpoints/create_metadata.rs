@@ -1,4 +1,6 @@
+use crate::consts::{MAX_NAME_LENGTH, MAX_SYMBOL_LENGTH, MAX_URI_LENGTH};
 use crate::err;
+use crate::err::AmmError;
 use crate::models::bound::BoundPool;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::program_option::COption;
@@ -8,7 +10,7 @@ use anchor_spl::metadata::mpl_token_metadata::types::DataV2;
 use anchor_spl::metadata::CreateMetadataAccountsV3;
 use anchor_spl::metadata::Metadata;
 use anchor_spl::token;
-use anchor_spl::token::spl_token::instruction::AuthorityType::MintTokens;
+use anchor_spl::token::spl_token::instruction::AuthorityType::{FreezeAccount, MintTokens};
 use anchor_spl::token::SetAuthority;
 use anchor_spl::token::{Mint, Token};
 
@@ -18,6 +20,7 @@ impl<'info> CreateMetadata<'info> {
         name: String,
         symbol: String,
         uri: String,
+        renounce_freeze: bool,
         bump: &[u8],
     ) -> Result<()> {
         let pool_key = self.pool.key();
@@ -38,7 +41,7 @@ impl<'info> CreateMetadata<'info> {
                 collection: None,
                 uses: None,
             },
-            false, // is_mutable
+            true,  // is_mutable, so the URI can be fixed via update_metadata before migration
             true,  // update_authority_is_signer
             None,  // collection_details
         )?;
@@ -51,6 +54,16 @@ impl<'info> CreateMetadata<'info> {
         )
         .unwrap();
 
+        // Setting the authority to None can't be undone by anyone, the pool signer included
+        if renounce_freeze && self.meme_mint.freeze_authority.is_some() {
+            token::set_authority(
+                self.set_mint_authority(&self.meme_mint)
+                    .with_signer(&[seeds]),
+                FreezeAccount,
+                None,
+            )?;
+        }
+
         Ok(())
     }
 
@@ -84,16 +97,51 @@ impl<'info> CreateMetadata<'info> {
     }
 }
 
+/// A meme mint may only be frozen by the pool signer, which exposes no freeze
+/// instruction and can renounce it for good in `create_metadata`.
+pub fn freeze_authority_allowed(freeze_authority: &COption<Pubkey>, pool_signer: &Pubkey) -> bool {
+    match freeze_authority {
+        COption::None => true,
+        COption::Some(authority) => authority == pool_signer,
+    }
+}
+
+/// Rejects a URI longer than Metaplex accepts.
+pub fn validate_uri(uri: &str) -> Result<()> {
+    if uri.len() > MAX_URI_LENGTH {
+        return Err(error!(AmmError::MetadataFieldTooLong));
+    }
+
+    Ok(())
+}
+
+/// Rejects metadata fields which would be refused or mangled by Metaplex.
+/// Lengths are counted in bytes, as Metaplex does.
+pub fn validate_metadata_fields(name: &str, symbol: &str, uri: &str) -> Result<()> {
+    if name.is_empty() || symbol.is_empty() {
+        return Err(error!(AmmError::MetadataFieldEmpty));
+    }
+
+    if name.len() > MAX_NAME_LENGTH || symbol.len() > MAX_SYMBOL_LENGTH {
+        return Err(error!(AmmError::MetadataFieldTooLong));
+    }
+
+    validate_uri(uri)
+}
+
 pub fn handle(
     ctx: Context<CreateMetadata>,
     name: String,
     symbol: String,
     uri: String,
+    renounce_freeze: bool,
 ) -> Result<()> {
+    validate_metadata_fields(&name, &symbol, &uri)?;
+
     let accs = ctx.accounts;
 
     let signer_bump_seed = ctx.bumps.pool_signer;
-    accs.create_nft_with_metadata(name, symbol, uri, &[signer_bump_seed])?;
+    accs.create_nft_with_metadata(name, symbol, uri, renounce_freeze, &[signer_bump_seed])?;
 
     Ok(())
 }
@@ -102,14 +150,14 @@ pub fn handle(
 pub struct CreateMetadata<'info> {
     #[account(mut)]
     pub sender: Signer<'info>,
-    #[account(constraint = sender.key() == pool.creator_addr)]
+    #[account(constraint = pool.is_creator(&sender.key()))]
     pub pool: Account<'info, BoundPool>,
     #[account(
         mut,
         constraint = meme_mint.mint_authority == COption::Some(pool_signer.key())
             @ err::acc("meme mint authority must be the pool signer"),
-        constraint = meme_mint.freeze_authority == COption::None
-            @ err::acc("meme mint mustn't have a freeze authority"),
+        constraint = freeze_authority_allowed(&meme_mint.freeze_authority, &pool_signer.key())
+            @ err::acc("meme mint freeze authority must be the pool signer or none"),
     )]
     pub meme_mint: Account<'info, Mint>,
 
@@ -127,3 +175,71 @@ pub struct CreateMetadata<'info> {
     pub metadata_program: Program<'info, Metadata>,
     pub rent: Sysvar<'info, Rent>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_metadata_length_boundaries() {
+        let uri = "u".repeat(MAX_URI_LENGTH);
+
+        // Exactly at every limit is accepted
+        validate_metadata_fields(&"n".repeat(MAX_NAME_LENGTH), "DOGE", "").unwrap();
+        validate_metadata_fields("Doge", &"S".repeat(MAX_SYMBOL_LENGTH), "").unwrap();
+        validate_metadata_fields("Doge", "DOGE", &uri).unwrap();
+
+        // One byte over any limit is rejected
+        let too_long = error!(AmmError::MetadataFieldTooLong);
+        assert_eq!(
+            validate_metadata_fields(&"n".repeat(MAX_NAME_LENGTH + 1), "DOGE", "").unwrap_err(),
+            too_long
+        );
+        assert_eq!(
+            validate_metadata_fields("Doge", &"S".repeat(MAX_SYMBOL_LENGTH + 1), "")
+                .unwrap_err(),
+            too_long
+        );
+        assert_eq!(
+            validate_metadata_fields("Doge", "DOGE", &format!("{}u", uri)).unwrap_err(),
+            too_long
+        );
+
+        // Multi-byte characters count by bytes: 11 x 3 bytes > 32
+        assert_eq!(
+            validate_metadata_fields(&"€".repeat(11), "DOGE", "").unwrap_err(),
+            too_long
+        );
+
+        println!("✅ Metadata length boundary test passed!");
+    }
+
+    #[test]
+    fn test_metadata_empty_fields() {
+        let empty = error!(AmmError::MetadataFieldEmpty);
+
+        assert_eq!(validate_metadata_fields("", "DOGE", "uri").unwrap_err(), empty);
+        assert_eq!(validate_metadata_fields("Doge", "", "uri").unwrap_err(), empty);
+
+        println!("✅ Empty metadata field test passed!");
+    }
+
+    #[test]
+    fn test_freeze_authority_allowed() {
+        let pool_signer = Pubkey::new_unique();
+
+        // No freeze authority, or one already renounced, is always fine
+        assert!(freeze_authority_allowed(&COption::None, &pool_signer));
+
+        // The pool signer may hold it until it is renounced
+        assert!(freeze_authority_allowed(&COption::Some(pool_signer), &pool_signer));
+
+        // Anyone else could freeze holders, so the mint is refused
+        assert!(!freeze_authority_allowed(
+            &COption::Some(Pubkey::new_unique()),
+            &pool_signer
+        ));
+
+        println!("✅ Freeze authority validation test passed!");
+    }
+}
@@ -0,0 +1,156 @@
+use crate::consts::{
+    AIRDROP_BASE_COMPUTE_UNITS, AIRDROP_RECIPIENT_COMPUTE_UNITS, BATCH_BUY_COMPUTE_UNITS,
+    MAX_AIRDROP_RECIPIENTS, MAX_BATCH_BUYS, MIGRATION_COMPUTE_UNITS, SWAP_COMPUTE_UNITS,
+};
+use crate::err;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Instruction a compute budget is estimated for
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ComputeOperation {
+    /// Any single buy or sell
+    Swap,
+    /// `swap_y_batch`, sized by its number of buys
+    BatchSwap,
+    /// `migrate_to_raydium` or `migrate`
+    Migration,
+    /// `send_airdrop_funds_batch`, sized by its number of recipients
+    AirdropBatch,
+}
+
+/// Result of `estimate_compute`, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct ComputeEstimate {
+    /// Units to request with `ComputeBudgetInstruction::set_compute_unit_limit`
+    pub units: u32,
+}
+
+/// Recommends the compute budget of `operation`, so clients can add a
+/// `ComputeBudget` instruction to the ones which outgrow the default limit.
+/// Touches no accounts.
+///
+/// # Arguments
+/// * `operation` - Instruction to budget
+/// * `size` - Buys or recipients of a batch, ignored otherwise
+pub fn handle(_ctx: Context<EstimateCompute>, operation: ComputeOperation, size: u8) -> Result<()> {
+    let estimate = ComputeEstimate {
+        units: estimate_units(operation, size)?,
+    };
+
+    msg!("compute_units: {}", estimate.units);
+
+    set_return_data(&estimate.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Compute units `operation` needs, batches growing with their `size`.
+pub fn estimate_units(operation: ComputeOperation, size: u8) -> Result<u32> {
+    let size = size as u32;
+
+    match operation {
+        ComputeOperation::Swap => Ok(SWAP_COMPUTE_UNITS),
+        ComputeOperation::BatchSwap => {
+            if size == 0 || size > MAX_BATCH_BUYS as u32 {
+                return Err(err::arg(format!(
+                    "Batch size must be between 1 and {} buys",
+                    MAX_BATCH_BUYS
+                ))
+                .into());
+            }
+
+            Ok(SWAP_COMPUTE_UNITS + (size - 1) * BATCH_BUY_COMPUTE_UNITS)
+        }
+        ComputeOperation::Migration => Ok(MIGRATION_COMPUTE_UNITS),
+        ComputeOperation::AirdropBatch => {
+            if size == 0 || size > MAX_AIRDROP_RECIPIENTS as u32 {
+                return Err(err::arg(format!(
+                    "Airdrop batch must have between 1 and {} recipients",
+                    MAX_AIRDROP_RECIPIENTS
+                ))
+                .into());
+            }
+
+            Ok(AIRDROP_BASE_COMPUTE_UNITS + size * AIRDROP_RECIPIENT_COMPUTE_UNITS)
+        }
+    }
+}
+
+#[derive(Accounts)]
+pub struct EstimateCompute {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::err::AmmError;
+
+    // Budget of an instruction without a ComputeBudget request
+    const DEFAULT_COMPUTE_UNITS: u32 = 200_000;
+
+    #[test]
+    fn test_migration_needs_more_than_swap() {
+        println!("🧪 Testing compute estimates for migration vs swap");
+
+        // ACT
+        let swap = estimate_units(ComputeOperation::Swap, 0).unwrap();
+        let migration = estimate_units(ComputeOperation::Migration, 0).unwrap();
+
+        // ASSERT: A swap fits the default budget, a migration doesn't
+        assert!(swap <= DEFAULT_COMPUTE_UNITS);
+        assert!(migration > DEFAULT_COMPUTE_UNITS);
+
+        // The size only matters to batches
+        assert_eq!(estimate_units(ComputeOperation::Swap, 7).unwrap(), swap);
+        assert_eq!(estimate_units(ComputeOperation::Migration, 7).unwrap(), migration);
+
+        println!("✅ Compute estimate test passed!");
+    }
+
+    #[test]
+    fn test_batch_estimates_grow_with_size() {
+        println!("🧪 Testing compute estimates for batches");
+
+        // ACT & ASSERT: A one-buy batch costs a swap, each further buy adds its share
+        for size in 1..=MAX_BATCH_BUYS as u8 {
+            assert_eq!(
+                estimate_units(ComputeOperation::BatchSwap, size).unwrap(),
+                SWAP_COMPUTE_UNITS + (size as u32 - 1) * BATCH_BUY_COMPUTE_UNITS
+            );
+        }
+        for size in 1..=MAX_AIRDROP_RECIPIENTS as u8 {
+            assert_eq!(
+                estimate_units(ComputeOperation::AirdropBatch, size).unwrap(),
+                AIRDROP_BASE_COMPUTE_UNITS + size as u32 * AIRDROP_RECIPIENT_COMPUTE_UNITS
+            );
+        }
+
+        // A full batch of buys outgrows the default budget
+        assert!(
+            estimate_units(ComputeOperation::BatchSwap, MAX_BATCH_BUYS as u8).unwrap()
+                > DEFAULT_COMPUTE_UNITS
+        );
+
+        println!("✅ Compute estimate batch test passed!");
+    }
+
+    #[test]
+    fn test_batch_sizes_bounded() {
+        for (operation, max) in [
+            (ComputeOperation::BatchSwap, MAX_BATCH_BUYS as u8),
+            (ComputeOperation::AirdropBatch, MAX_AIRDROP_RECIPIENTS as u8),
+        ] {
+            estimate_units(operation, max).unwrap();
+
+            // Sizes the batch instructions refuse aren't estimated either
+            for size in [0, max + 1] {
+                assert_eq!(
+                    estimate_units(operation, size).unwrap_err(),
+                    error!(AmmError::InvalidArg)
+                );
+            }
+        }
+
+        println!("✅ Compute estimate batch bounds test passed!");
+    }
+}
@@ -0,0 +1,360 @@
+use crate::endpoints::swap_y::{LockReason, PoolLocked, SwapEvent};
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::buyer_record::BuyerRecord;
+use crate::models::limit_order::LimitOrder;
+use crate::models::SwapAmount;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Crank filling a triggered limit order. Anyone may call it; the escrowed
+/// quote tokens buy meme for the order's owner at the current curve price.
+/// No referral points are paid on crank fills.
+pub fn handle(ctx: Context<ExecuteLimitOrder>) -> Result<()> {
+    let accs = &mut *ctx.accounts;
+
+    let slot = Clock::get()?.slot;
+    let swap_amount = fill_limit_order(&mut accs.pool, &mut accs.limit_order, slot)?;
+
+    // An order can be the launch's first buy too
+    let first_buyer_bonus = accs.pool.claim_first_buyer_bonus(swap_amount.amount_out)?;
+
+    // Fail fast on a lookalike pool signer
+    BoundPool::assert_signer_pda(&crate::ID, &accs.pool.key(), &accs.pool_signer_pda.key())?;
+
+    let order = &accs.limit_order;
+    let order_id = order.order_id.to_le_bytes();
+    let order_seeds = &[
+        LimitOrder::LIMIT_ORDER_PREFIX.as_ref(),
+        order.pool.as_ref(),
+        order.owner.as_ref(),
+        &order_id,
+        &[ctx.bumps.limit_order],
+    ];
+
+    // Pay the curve out of the escrow
+    token::transfer(
+        CpiContext::new(
+            accs.token_program.to_account_info(),
+            Transfer {
+                from: accs.escrow.to_account_info(),
+                to: accs.quote_vault.to_account_info(),
+                authority: accs.limit_order.to_account_info(),
+            },
+        )
+        .with_signer(&[&order_seeds[..]]),
+        swap_amount.amount_in + swap_amount.admin_fee_in,
+    )?;
+
+    let pool_signer_seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer_pda],
+    ];
+
+    token::transfer(
+        CpiContext::new(
+            accs.token_program.to_account_info(),
+            Transfer {
+                from: accs.meme_vault.to_account_info(),
+                to: accs.owner_meme.to_account_info(),
+                authority: accs.pool_signer_pda.to_account_info(),
+            },
+        )
+        .with_signer(&[&pool_signer_seeds[..]]),
+        swap_amount.amount_out + first_buyer_bonus,
+    )?;
+
+    let pool = &mut accs.pool;
+    let pool_key = pool.key();
+    pool.record_buyer(
+        &mut accs.buyer_record,
+        pool_key,
+        accs.limit_order.owner,
+        slot,
+    )?;
+
+    let pool_locked = pool.lock_if_sold_out().then(|| PoolLocked {
+        pool: pool_key,
+        meme_reserve: pool.meme_reserve.tokens,
+        quote_reserve: pool.quote_reserve.tokens,
+        reason: LockReason::SoldOut,
+    });
+
+    msg!(
+        "limit_order_filled: {} remaining: {}",
+        accs.limit_order.order_id,
+        accs.limit_order.quote_amount
+    );
+
+    let swap_event = SwapEvent {
+        pool: pool_key,
+        owner: accs.limit_order.owner,
+        buy_meme: true,
+        amount_in: swap_amount.amount_in,
+        amount_out: swap_amount.amount_out,
+        admin_fee_in: swap_amount.admin_fee_in,
+        admin_fee_out: swap_amount.admin_fee_out,
+        points_emitted: 0,
+        spot_price: accs.pool.spot_price()?,
+        price_decimals: accs.pool.price_decimals(),
+    };
+
+    if let Some(pool_locked) = pool_locked {
+        emit_cpi!(pool_locked);
+    }
+    emit_cpi!(swap_event);
+
+    Ok(())
+}
+
+/// Checks `order` is triggered at the pool's spot price and books the buy
+/// of its escrowed quote on `pool`, leaving the token transfers to the
+/// caller. The buy must average no worse than the trigger price. A buy
+/// capped at the end of the curve leaves the unspent quote on the order.
+///
+/// # Arguments
+/// * `pool` - The pool the order buys from
+/// * `order` - The order being filled
+/// * `slot` - Current slot, for the trade spacing and the TWAP
+pub fn fill_limit_order(
+    pool: &mut BoundPool,
+    order: &mut LimitOrder,
+    slot: u64,
+) -> Result<SwapAmount> {
+    if order.quote_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Orders fill on the curve, not the reserves a migrated pool left behind
+    pool.check_tradeable()?;
+    pool.check_not_migrated()?;
+    order.check_triggered(pool.spot_price()?)?;
+    pool.check_trade_slot(slot)?;
+    pool.check_min_trade(order.quote_amount, true)?;
+
+    // Price the floor on what the fill spends, as the curve's last buy may
+    // take less than escrowed
+    let quoted = pool.swap_amounts(order.quote_amount, 0, true)?;
+    let min_out = order.min_meme_out(quoted.amount_in + quoted.admin_fee_in, &pool.fees)?;
+    let swap_amount = pool.swap_amounts(order.quote_amount, min_out, true)?;
+    pool.check_price_impact(&swap_amount, true)?;
+
+    order.fill(swap_amount.amount_in + swap_amount.admin_fee_in)?;
+
+    // Close the TWAP interval at the pre-trade price
+    pool.update_twap(slot)?;
+
+    pool.accrue_swap_fees(swap_amount.admin_fee_in, swap_amount.admin_fee_out)?;
+
+    pool.quote_reserve.tokens += swap_amount.amount_in;
+    pool.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
+
+    pool.record_volume(&swap_amount, true)?;
+
+    Ok(swap_amount)
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct ExecuteLimitOrder<'info> {
+    /// Whoever cranks the order, paying for the owner's buyer record
+    #[account(mut)]
+    pub cranker: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        has_one = pool,
+        seeds = [
+            LimitOrder::LIMIT_ORDER_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            limit_order.owner.as_ref(),
+            &limit_order.order_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    #[account(
+        mut,
+        seeds = [LimitOrder::ESCROW_PREFIX.as_ref(), limit_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    /// The order owner's meme token account receiving the buy
+    #[account(
+        mut,
+        constraint = owner_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = owner_meme.owner == limit_order.owner
+    )]
+    pub owner_meme: Account<'info, TokenAccount>,
+
+    /// Record of the owner's buys from this pool, created on the first buy
+    #[account(
+        init_if_needed,
+        payer = cranker,
+        space = 8 + BuyerRecord::INIT_SPACE,
+        seeds = [BuyerRecord::BUYER_RECORD_PREFIX, pool.key().as_ref(), limit_order.owner.as_ref()],
+        bump
+    )]
+    pub buyer_record: Box<Account<'info, BuyerRecord>>,
+
+    /// CHECK: PDA signer for the pool - seeds validation ensures this is the correct pool authority
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer_pda: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bound::test_pool;
+    use crate::models::limit_order::LimitDirection;
+
+    // Default launch curve with `raised` SOL in it
+    fn create_order_pool(raised: u64) -> BoundPool {
+        let mut pool = test_pool();
+
+        if raised > 0 {
+            pool.meme_reserve.tokens -= pool.compute_delta_m(0, raised).unwrap();
+            pool.quote_reserve.tokens = raised;
+        }
+
+        pool
+    }
+
+    fn create_order(trigger_price: u64, quote_amount: u64) -> LimitOrder {
+        LimitOrder {
+            pool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            order_id: 0,
+            trigger_price,
+            quote_amount,
+            direction: LimitDirection::Below,
+        }
+    }
+
+    #[test]
+    fn test_limit_order_trigger_not_met() {
+        // ARRANGE: An order waiting for the price 20 SOL further along the curve
+        let mut pool = create_order_pool(20_000_000_000);
+        let trigger = create_order_pool(40_000_000_000).spot_price().unwrap();
+        let mut order = create_order(trigger, 1_000_000_000);
+        let before = pool.clone();
+
+        println!("🧪 Testing a limit order whose trigger isn't met");
+
+        // ACT
+        let err = fill_limit_order(&mut pool, &mut order, 1).unwrap_err();
+
+        // ASSERT: Nothing moves
+        assert_eq!(err, error!(AmmError::LimitOrderNotTriggered));
+        assert_eq!(order.quote_amount, 1_000_000_000);
+        assert_eq!(pool.quote_reserve.tokens, before.quote_reserve.tokens);
+        assert_eq!(pool.meme_reserve.tokens, before.meme_reserve.tokens);
+
+        println!("✅ Untriggered limit order test passed!");
+    }
+
+    #[test]
+    fn test_limit_order_trigger_met() {
+        // ARRANGE: The price reached the same order's trigger
+        let mut pool = create_order_pool(40_000_000_000);
+        let trigger = pool.spot_price().unwrap();
+        let mut order = create_order(trigger, 1_000_000_000);
+        let expected = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        let quote_before = pool.quote_reserve.tokens;
+
+        println!("🧪 Testing a triggered limit order fill");
+
+        // ACT
+        let swap = fill_limit_order(&mut pool, &mut order, 1).unwrap();
+
+        // ASSERT: Booked exactly like a regular buy of the escrowed amount
+        assert_eq!(swap.amount_out, expected.amount_out);
+        assert_eq!(swap.admin_fee_in, expected.admin_fee_in);
+        assert_eq!(order.quote_amount, 0);
+        assert_eq!(pool.quote_reserve.tokens, quote_before + swap.amount_in);
+        assert_eq!(pool.admin_fees_quote, swap.admin_fee_in);
+
+        // An emptied order can't be cranked again
+        assert_eq!(
+            fill_limit_order(&mut pool, &mut order, 2).unwrap_err(),
+            error!(AmmError::NoZeroTokens)
+        );
+
+        println!("✅ Triggered limit order test passed!");
+    }
+
+    #[test]
+    fn test_limit_order_fill_held_to_trigger_price() {
+        // ARRANGE: An order buying once the price rises to its 40 SOL level,
+        // with the price since pushed well above it
+        let mut pool = create_order_pool(20_000_000_000);
+        let trigger = create_order_pool(40_000_000_000).spot_price().unwrap();
+        let mut order = create_order(trigger, 1_000_000_000);
+        order.direction = LimitDirection::Above;
+        let before = pool.clone();
+
+        println!("🧪 Testing a limit order filled past its trigger");
+
+        // ACT
+        let err = fill_limit_order(&mut pool, &mut order, 1).unwrap_err();
+
+        // ASSERT: Triggered, but the buy would cost more than the trigger price
+        assert!(order.is_triggered(pool.spot_price().unwrap()));
+        assert_eq!(err, error!(AmmError::SlippageExceeded));
+        assert_eq!(order.quote_amount, 1_000_000_000);
+        assert_eq!(pool.meme_reserve.tokens, before.meme_reserve.tokens);
+
+        // A dip past a buy-the-dip trigger fills, with at least the floor
+        let mut pool = create_order_pool(40_000_000_000);
+        let trigger = create_order_pool(20_000_000_000).spot_price().unwrap();
+        let mut order = create_order(trigger, 1_000_000_000);
+        let swap = fill_limit_order(&mut pool, &mut order, 1).unwrap();
+        let floor = order
+            .min_meme_out(swap.amount_in + swap.admin_fee_in, &pool.fees)
+            .unwrap();
+        assert!(swap.amount_out >= floor);
+
+        println!("✅ Limit order trigger price floor test passed!");
+    }
+
+    #[test]
+    fn test_limit_order_capped_fill_keeps_remainder() {
+        // ARRANGE: 1 SOL short of the target, ordering 10 SOL
+        let mut pool = create_order_pool(84_000_000_000);
+        let trigger = pool.spot_price().unwrap();
+        let mut order = create_order(trigger, 10_000_000_000);
+
+        // ACT
+        let swap = fill_limit_order(&mut pool, &mut order, 1).unwrap();
+
+        // ASSERT: Only what the curve took left the escrow
+        assert!(order.quote_amount > 0);
+        assert_eq!(
+            order.quote_amount + swap.amount_in + swap.admin_fee_in,
+            10_000_000_000
+        );
+
+        println!("✅ Capped limit order fill test passed!");
+    }
+}
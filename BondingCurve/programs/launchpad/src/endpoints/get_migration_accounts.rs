@@ -0,0 +1,150 @@
+use crate::consts::RAYDIUM_AMM_CONFIG_SEED;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+use anchor_spl::token::Mint;
+use raydium_cpmm_cpi::states::{OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED};
+
+/// Raydium accounts `migrate_to_raydium` expects, returned by `get_migration_accounts`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MigrationAccounts {
+    /// AMM config the pool is created under
+    pub amm_config: Pubkey,
+    /// Vault and LP mint authority
+    pub raydium_authority: Pubkey,
+    /// Pool state account to be created
+    pub raydium_pool_state: Pubkey,
+    /// LP mint to be created
+    pub raydium_lp_mint: Pubkey,
+    /// Meme (token_0) vault to be created
+    pub token_0_vault: Pubkey,
+    /// Quote (token_1) vault to be created
+    pub token_1_vault: Pubkey,
+    /// Oracle observation account to be created
+    pub observation_state: Pubkey,
+}
+
+/// Derives the Raydium CPMM accounts for migrating the `meme_mint`/`quote_mint`
+/// pair under the AMM config at `amm_config_index`, with the same seeds the
+/// `MigrateToRaydium` constraints check.
+pub fn derive_migration_accounts(
+    meme_mint: &Pubkey,
+    quote_mint: &Pubkey,
+    amm_config_index: u16,
+) -> MigrationAccounts {
+    let program_id = raydium_cpmm_cpi::ID;
+    let pda = |seeds: &[&[u8]]| Pubkey::find_program_address(seeds, &program_id).0;
+
+    let amm_config = pda(&[RAYDIUM_AMM_CONFIG_SEED, &amm_config_index.to_be_bytes()]);
+    let raydium_pool_state = pda(&[
+        POOL_SEED.as_bytes(),
+        amm_config.as_ref(),
+        meme_mint.as_ref(),
+        quote_mint.as_ref(),
+    ]);
+
+    MigrationAccounts {
+        amm_config,
+        raydium_authority: pda(&[raydium_cpmm_cpi::AUTH_SEED.as_bytes()]),
+        raydium_pool_state,
+        raydium_lp_mint: pda(&[POOL_LP_MINT_SEED.as_bytes(), raydium_pool_state.as_ref()]),
+        token_0_vault: pda(&[
+            POOL_VAULT_SEED.as_bytes(),
+            raydium_pool_state.as_ref(),
+            meme_mint.as_ref(),
+        ]),
+        token_1_vault: pda(&[
+            POOL_VAULT_SEED.as_bytes(),
+            raydium_pool_state.as_ref(),
+            quote_mint.as_ref(),
+        ]),
+        observation_state: pda(&[OBSERVATION_SEED.as_bytes(), raydium_pool_state.as_ref()]),
+    }
+}
+
+pub fn handle(ctx: Context<GetMigrationAccounts>, amm_config_index: u16) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let accounts = derive_migration_accounts(
+        &accs.meme_mint.key(),
+        &accs.quote_mint.key(),
+        amm_config_index,
+    );
+
+    msg!(
+        "raydium_pool_state: {}\n raydium_lp_mint: {}",
+        accounts.raydium_pool_state,
+        accounts.raydium_lp_mint
+    );
+
+    set_return_data(&accounts.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetMigrationAccounts<'info> {
+    #[account(
+        seeds = [
+            b"bound_pool",
+            meme_mint.key().as_ref(),
+            quote_mint.key().as_ref()
+        ],
+        bump,
+    )]
+    pub pool: Account<'info, BoundPool>,
+
+    /// Meme token mint, token_0 of the Raydium pool
+    #[account(
+        constraint = meme_mint.key() < quote_mint.key() @ AmmError::InvalidMintOrdering
+    )]
+    pub meme_mint: Account<'info, Mint>,
+
+    /// Quote token mint, token_1 of the Raydium pool
+    pub quote_mint: Account<'info, Mint>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_accounts_decode() {
+        // ARRANGE: An ordered pair as migration requires
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (meme_mint, quote_mint) = if a < b { (a, b) } else { (b, a) };
+
+        println!("🧪 Testing the derived Raydium migration accounts");
+
+        // ACT: Derive and round-trip through the return data encoding
+        let accounts = derive_migration_accounts(&meme_mint, &quote_mint, 0);
+        let data = accounts.try_to_vec().unwrap();
+        let decoded = MigrationAccounts::try_from_slice(&data).unwrap();
+
+        // ASSERT: The decoded addresses are the PDAs Raydium derives
+        assert_eq!(decoded, accounts);
+        let program_id = raydium_cpmm_cpi::ID;
+        let (amm_config, _) =
+            Pubkey::find_program_address(&[b"amm_config", &0u16.to_be_bytes()], &program_id);
+        let (pool_state, _) = Pubkey::find_program_address(
+            &[
+                b"pool",
+                amm_config.as_ref(),
+                meme_mint.as_ref(),
+                quote_mint.as_ref(),
+            ],
+            &program_id,
+        );
+        assert_eq!(decoded.amm_config, amm_config);
+        assert_eq!(decoded.raydium_pool_state, pool_state);
+        assert_ne!(decoded.token_0_vault, decoded.token_1_vault);
+
+        // Another config index is another pool
+        let other = derive_migration_accounts(&meme_mint, &quote_mint, 1);
+        assert_ne!(other.raydium_pool_state, decoded.raydium_pool_state);
+        assert_eq!(other.raydium_authority, decoded.raydium_authority);
+
+        println!("✅ Migration accounts decode test passed!");
+    }
+}
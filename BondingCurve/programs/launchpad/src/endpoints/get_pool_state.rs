@@ -0,0 +1,62 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Snapshot of a pool returned by `get_pool_state`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct PoolState {
+    pub meme_reserve: u64,
+    pub quote_reserve: u64,
+    pub admin_fees_meme: u64,
+    pub admin_fees_quote: u64,
+    pub creator_fees_quote: u64,
+    pub locked: bool,
+    pub pool_migration: bool,
+    pub total_quote_volume: u128,
+    pub total_meme_volume: u128,
+    pub unique_buyers: u32,
+    /// Current spot price, see `BoundPool::spot_price`
+    pub spot_price: u64,
+    /// Decimal places of `spot_price`, from the pool's quote decimals
+    pub price_decimals: u8,
+}
+
+impl TryFrom<&BoundPool> for PoolState {
+    type Error = Error;
+
+    fn try_from(pool: &BoundPool) -> Result<Self> {
+        Ok(PoolState {
+            meme_reserve: pool.meme_reserve.tokens,
+            quote_reserve: pool.quote_reserve.tokens,
+            admin_fees_meme: pool.admin_fees_meme,
+            admin_fees_quote: pool.admin_fees_quote,
+            creator_fees_quote: pool.creator_fees_quote,
+            locked: pool.locked,
+            pool_migration: pool.pool_migration,
+            total_quote_volume: pool.total_quote_volume,
+            total_meme_volume: pool.total_meme_volume,
+            unique_buyers: pool.unique_buyers,
+            spot_price: pool.spot_price()?,
+            price_decimals: pool.price_decimals(),
+        })
+    }
+}
+
+pub fn handle(ctx: Context<GetPoolState>) -> Result<()> {
+    let state = PoolState::try_from(&*ctx.accounts.pool)?;
+
+    msg!(
+        "total_quote_volume: {}\n total_meme_volume: {}",
+        state.total_quote_volume,
+        state.total_meme_volume
+    );
+
+    set_return_data(&state.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GetPoolState<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,185 @@
+use crate::consts::POINTS_EPOCH_SEED;
+use crate::endpoints::swap_y::get_swap_points;
+use crate::models::bound::BoundPool;
+use crate::models::points_epoch::PointsEpoch;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Result of `get_quote`, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct SwapQuote {
+    /// Tokens the swap pays out, after fees
+    pub amount_out: u64,
+    /// Admin fee charged in the quote token
+    pub admin_fee: u64,
+    /// Admin fee charged in the meme token
+    pub admin_fee_meme: u64,
+    /// Referral points the buy emits under the current epoch, 0 for sells
+    pub points_earned: u64,
+    /// How far the swap moves the spot price, in basis points
+    pub price_impact_bps: u64,
+    /// Whether the pool currently accepts swaps. The other fields are zero
+    /// when it doesn't.
+    pub is_tradeable: bool,
+}
+
+/// Everything a wallet needs to preview a swap, in one call. Quotes without
+/// slippage limits or fee exemptions and never mutates the pool.
+///
+/// # Arguments
+/// * `ctx` - The pool and the current points epoch
+/// * `buy_meme` - `true` to quote a buy (quote in), `false` for a sell (meme in)
+/// * `amount_in` - Tokens the swap pays in, fees included
+pub fn handle(ctx: Context<GetQuote>, buy_meme: bool, amount_in: u64) -> Result<()> {
+    let quote = quote_swap(
+        &ctx.accounts.pool,
+        &ctx.accounts.points_epoch,
+        buy_meme,
+        amount_in,
+    )?;
+
+    msg!(
+        "quoted_out: {}\n price_impact_bps: {}",
+        quote.amount_out,
+        quote.price_impact_bps
+    );
+
+    set_return_data(&quote.try_to_vec()?);
+
+    Ok(())
+}
+
+/// Composes the quote `get_quote` returns.
+pub fn quote_swap(
+    pool: &BoundPool,
+    points_epoch: &PointsEpoch,
+    buy_meme: bool,
+    amount_in: u64,
+) -> Result<SwapQuote> {
+    if !pool.is_tradeable() {
+        return Ok(SwapQuote::default());
+    }
+
+    let swap_amount = pool.swap_amounts(amount_in, 0, buy_meme)?;
+
+    let (admin_fee, admin_fee_meme, points_earned) = if buy_meme {
+        let points = get_swap_points(
+            swap_amount.amount_in + swap_amount.admin_fee_in,
+            points_epoch,
+            pool.percent_sold()?,
+        )?;
+
+        (
+            swap_amount.admin_fee_in,
+            swap_amount.admin_fee_out,
+            points_epoch.grantable_points(points),
+        )
+    } else {
+        (swap_amount.admin_fee_out, swap_amount.admin_fee_in, 0)
+    };
+
+    Ok(SwapQuote {
+        amount_out: swap_amount.amount_out,
+        admin_fee,
+        admin_fee_meme,
+        points_earned,
+        price_impact_bps: pool.price_impact_bps(&swap_amount, buy_meme)?,
+        is_tradeable: true,
+    })
+}
+
+#[derive(Accounts)]
+pub struct GetQuote<'info> {
+    pub pool: Account<'info, BoundPool>,
+    /// The current points epoch, for the points a buy would emit
+    #[account(seeds = [POINTS_EPOCH_SEED], bump)]
+    pub points_epoch: Account<'info, PointsEpoch>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bound::test_pool;
+    use crate::models::points_epoch::PointsEmission;
+
+    // Default launch curve, 40 SOL raised
+    fn create_quote_pool() -> BoundPool {
+        let mut pool = test_pool();
+
+        let raised = 40_000_000_000;
+        pool.meme_reserve.tokens -= pool.compute_delta_m(0, raised).unwrap();
+        pool.quote_reserve.tokens = raised;
+
+        pool
+    }
+
+    fn create_quote_epoch() -> PointsEpoch {
+        PointsEpoch {
+            epoch_number: 1,
+            points_per_sol_num: 1000,
+            points_per_sol_denom: 1_000_000_000,
+            max_points_per_epoch: 0,
+            points_emitted: 0,
+            burn_on_sell: false,
+            emission_mode: PointsEmission::Flat,
+            points_vesting_secs: 0,
+            padding: [0; 2],
+        }
+    }
+
+    #[test]
+    fn test_quote_decode_both_directions() {
+        // ARRANGE
+        let pool = create_quote_pool();
+        let epoch = create_quote_epoch();
+
+        println!("🧪 Testing get_quote for buys and sells");
+
+        for (buy_meme, amount_in) in [(true, 2_000_000_000), (false, 50_000_000_000_000)] {
+            // ACT: Quote and round-trip through the return data encoding
+            let quote = quote_swap(&pool, &epoch, buy_meme, amount_in).unwrap();
+            let data = quote.try_to_vec().unwrap();
+            let decoded = SwapQuote::try_from_slice(&data).unwrap();
+
+            // ASSERT: The quote matches what the swap itself would do
+            let swap = pool.swap_amounts(amount_in, 0, buy_meme).unwrap();
+            assert_eq!(decoded, quote);
+            assert!(decoded.is_tradeable);
+            assert_eq!(decoded.amount_out, swap.amount_out);
+            assert_eq!(
+                decoded.price_impact_bps,
+                pool.price_impact_bps(&swap, buy_meme).unwrap()
+            );
+            assert!(decoded.price_impact_bps > 0);
+
+            if buy_meme {
+                assert_eq!(decoded.admin_fee, swap.admin_fee_in);
+                assert_eq!(decoded.points_earned, 2_000);
+            } else {
+                assert_eq!(decoded.admin_fee, swap.admin_fee_out);
+                assert_eq!(decoded.points_earned, 0);
+            }
+            assert!(decoded.admin_fee > 0);
+        }
+
+        println!("✅ Quote decode test passed!");
+    }
+
+    #[test]
+    fn test_quote_untradeable_pool() {
+        let mut pool = create_quote_pool();
+        let mut epoch = create_quote_epoch();
+        pool.locked = true;
+
+        // A locked pool quotes nothing instead of failing
+        let quote = quote_swap(&pool, &epoch, true, 1_000_000_000).unwrap();
+        assert_eq!(quote, SwapQuote::default());
+        assert!(!quote.is_tradeable);
+
+        // The epoch cap limits the points a buy can promise
+        pool.locked = false;
+        epoch.max_points_per_epoch = 500;
+        let quote = quote_swap(&pool, &epoch, true, 1_000_000_000).unwrap();
+        assert_eq!(quote.points_earned, 500);
+    }
+}
@@ -6,6 +6,7 @@
 // to the project's memecoin token via the bonding curve.
 
 use crate::models::bound::BoundPool;
+use crate::models::SwapAmount;
 use anchor_lang::prelude::*;
 use anchor_spl::token::TokenAccount;
 
@@ -17,12 +18,10 @@ use anchor_spl::token::TokenAccount;
 /// * `coin_y_min_value` - The minimum amount of output tokens expected
 ///
 /// # Returns
-/// * `Result<()>` - Returns Ok if calculation succeeds
+/// * `Result<()>` - Returns Ok if calculation succeeds, `PoolIsLocked` for a
+///   pool locked awaiting migration
 pub fn handle(ctx: Context<GetSwapXAmt>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
-    let swap_amount = ctx
-        .accounts
-        .pool
-        .swap_amounts(coin_in_amount, coin_y_min_value, false);
+    let swap_amount = preview_sell(&ctx.accounts.pool, coin_in_amount, coin_y_min_value)?;
 
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -32,6 +31,19 @@ pub fn handle(ctx: Context<GetSwapXAmt>, coin_in_amount: u64, coin_y_min_value:
 
     Ok(())
 }
+
+/// Sell `get_swap_x_amt` previews, as `swap_x` would quote it.
+pub fn preview_sell(
+    pool: &BoundPool,
+    coin_in_amount: u64,
+    coin_y_min_value: u64,
+) -> Result<SwapAmount> {
+    // Don't quote a sell swap_x would refuse
+    pool.check_tradeable()?;
+
+    pool.swap_amounts(coin_in_amount, coin_y_min_value, false)
+}
+
 /// Account validation struct for getting swap amounts
 #[derive(Accounts)]
 pub struct GetSwapXAmt<'info> {
@@ -42,3 +54,126 @@ pub struct GetSwapXAmt<'info> {
     #[account(constraint = pool.quote_reserve.vault == quote_vault.key())]
     pub quote_vault: Account<'info, TokenAccount>,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::get_swap_y_amt::{preview_buy, BuyPreview};
+    use crate::models::bound::{migrated_test_pool, test_pool, ResidualFeeMode};
+
+    // Books a previewed buy on the pool the way swap_y does
+    fn apply_buy(pool: &mut BoundPool, buy: &SwapAmount) {
+        pool.admin_fees_quote += buy.admin_fee_in;
+        pool.admin_fees_meme += buy.admin_fee_out;
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+    }
+
+    #[test]
+    fn test_preview_round_trip_loses_to_fees() {
+        println!("🧪 Testing buy then sell-back previews round trip at a loss");
+
+        // Small, mid-curve and curve-filling buys, the last one capped
+        for sol_in in [1_000_000, 1_000_000_000, 40_000_000_000, 100_000_000_000] {
+            // ARRANGE
+            let mut pool = test_pool();
+
+            // ACT: Preview the buy, book it, then preview selling it all back
+            let buy = preview_buy(&pool, sol_in, 0).unwrap();
+            apply_buy(&mut pool, &buy);
+            let sell = preview_sell(&pool, buy.amount_out, 0).unwrap();
+
+            // ASSERT: The round trip pays back strictly less than was spent
+            let spent = buy.amount_in + buy.admin_fee_in;
+            assert!(
+                sell.amount_out < spent,
+                "{} SOL in, {} SOL back",
+                spent,
+                sell.amount_out
+            );
+            assert!(sell.admin_fee_out > 0);
+        }
+
+        println!("✅ Preview round trip test passed!");
+    }
+
+    #[test]
+    fn test_previews_refuse_locked_pool() {
+        let mut pool = test_pool();
+        let buy = preview_buy(&pool, 1_000_000_000, 0).unwrap();
+        apply_buy(&mut pool, &buy);
+        pool.locked = true;
+
+        // Neither preview quotes a swap the pool would refuse
+        assert_eq!(
+            preview_buy(&pool, 1_000_000_000, 0).unwrap_err(),
+            error!(crate::err::AmmError::PoolIsLocked)
+        );
+        assert_eq!(
+            preview_sell(&pool, buy.amount_out, 0).unwrap_err(),
+            error!(crate::err::AmmError::PoolIsLocked)
+        );
+
+        println!("✅ Locked pool preview test passed!");
+    }
+
+    #[test]
+    fn test_previews_quote_migrated_pool() {
+        // ARRANGE: A migrated pool, still locked, with reserves left on the curve
+        let mut pool = migrated_test_pool(ResidualFeeMode::Treasury);
+        let meme_left = pool.meme_reserve.tokens;
+        assert!(pool.locked);
+
+        println!("🧪 Testing previews against a migrated pool's leftover reserves");
+
+        // ACT: Buy off the leftover reserves, book it, then sell it back
+        let buy = preview_buy(&pool, 1_000_000_000, 0).unwrap();
+        apply_buy(&mut pool, &buy);
+        let sell = preview_sell(&pool, buy.amount_out, 0).unwrap();
+
+        // ASSERT: Both sides quote within what the curve has left
+        assert!(buy.amount_out > 0 && buy.amount_out < meme_left);
+        assert!(sell.amount_out > 0 && sell.amount_out < 1_000_000_000);
+
+        println!("✅ Migrated pool preview test passed!");
+    }
+
+    #[test]
+    fn test_buy_preview_slippage() {
+        println!("🧪 Testing the buy preview's execution price and slippage");
+
+        let mut last_slippage_bps = 0;
+        for sol_in in [1_000_000, 1_000_000_000, 40_000_000_000] {
+            // ARRANGE
+            let pool = test_pool();
+            let buy = preview_buy(&pool, sol_in, 0).unwrap();
+
+            // ACT: Encode the preview as return data and decode it back
+            let data = BuyPreview::new(&pool, &buy).unwrap().try_to_vec().unwrap();
+            let preview = BuyPreview::try_from_slice(&data).unwrap();
+
+            // ASSERT: Both figures match a calculation by hand
+            let spot_price = pool.spot_price().unwrap() as u128;
+            let execution_price = buy.amount_in as u128 * crate::consts::DECIMALS_S
+                / (buy.amount_out + buy.admin_fee_out) as u128;
+            let slippage_bps = (execution_price.abs_diff(spot_price) * 10_000)
+                .div_ceil(spot_price);
+
+            assert_eq!(preview.amount_out, buy.amount_out);
+            assert_eq!(preview.execution_price as u128, execution_price);
+            assert_eq!(preview.slippage_bps as u128, slippage_bps);
+
+            // A dust buy barely moves off the spot price, bigger ones walk
+            // further along the curve
+            if sol_in == 1_000_000 {
+                assert!(preview.slippage_bps <= 1);
+            }
+            assert!(preview.slippage_bps > last_slippage_bps);
+            last_slippage_bps = preview.slippage_bps;
+        }
+
+        assert!(last_slippage_bps > 1_000);
+
+        println!("✅ Buy preview slippage test passed!");
+    }
+}
@@ -1,21 +1,88 @@
+use crate::consts::{BPS_DENOMINATOR, DECIMALS_S};
+use crate::err::AmmError;
+use crate::libraries::MulDiv;
 use crate::models::bound::BoundPool;
+use crate::models::SwapAmount;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 use anchor_spl::token::TokenAccount;
 
+/// Result of `get_swap_y_amt`, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default, PartialEq, Eq)]
+pub struct BuyPreview {
+    /// Quote tokens reaching the curve, after fees
+    pub amount_in: u64,
+    /// Meme tokens paid out, after fees
+    pub amount_out: u64,
+    /// Admin fee charged in the quote token
+    pub admin_fee_in: u64,
+    /// Admin fee charged in the meme token
+    pub admin_fee_out: u64,
+    /// Quote paid into the curve per meme it gives out, in `spot_price` units
+    pub execution_price: u64,
+    /// How far `execution_price` strays from the spot price before the buy,
+    /// in basis points rounded up
+    pub slippage_bps: u64,
+}
+
+impl BuyPreview {
+    /// Prices `swap_amount`, a buy quoted on `pool` before it's booked.
+    pub fn new(pool: &BoundPool, swap_amount: &SwapAmount) -> Result<Self> {
+        // Fees aside, so the figure only reflects the move along the curve
+        let execution_price = swap_amount
+            .amount_in
+            .mul_div_floor(
+                DECIMALS_S as u64,
+                swap_amount.amount_out + swap_amount.admin_fee_out,
+            )
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        let spot_price = pool.spot_price()?;
+        let slippage_bps = execution_price
+            .abs_diff(spot_price)
+            .mul_div_ceil(BPS_DENOMINATOR, spot_price)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(BuyPreview {
+            amount_in: swap_amount.amount_in,
+            amount_out: swap_amount.amount_out,
+            admin_fee_in: swap_amount.admin_fee_in,
+            admin_fee_out: swap_amount.admin_fee_out,
+            execution_price,
+            slippage_bps,
+        })
+    }
+}
+
 pub fn handle(ctx: Context<GetSwapYAmt>, coin_in_amount: u64, coin_x_min_value: u64) -> Result<()> {
-    let swap_amount = ctx
-        .accounts
-        .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+    let pool = &ctx.accounts.pool;
+    let swap_amount = preview_buy(pool, coin_in_amount, coin_x_min_value)?;
+    let preview = BuyPreview::new(pool, &swap_amount)?;
 
     msg!(
-        "swapped_in: {}\n swapped_out: {}",
-        swap_amount.amount_in,
-        swap_amount.amount_out
+        "swapped_in: {}\n swapped_out: {}\n slippage_bps: {}",
+        preview.amount_in,
+        preview.amount_out,
+        preview.slippage_bps
     );
 
+    set_return_data(&preview.try_to_vec()?);
+
     Ok(())
 }
+
+/// Buy `get_swap_y_amt` previews, as `swap_y` would quote it.
+pub fn preview_buy(
+    pool: &BoundPool,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+) -> Result<SwapAmount> {
+    // Don't quote a buy swap_y would refuse
+    pool.check_tradeable()?;
+
+    pool.swap_amounts(coin_in_amount, coin_x_min_value, true)
+}
+
 #[derive(Accounts)]
 pub struct GetSwapYAmt<'info> {
     pub pool: Account<'info, BoundPool>,
@@ -0,0 +1,48 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::fee_exemption::FeeExemption;
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Exempts `wallet` from the admin fees on `pool`. Swaps passing the
+/// exemption account in are quoted as if the pool charged no fees.
+pub fn handle(ctx: Context<GrantFeeExemption>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.fee_exemption.pool = accs.pool.key();
+    accs.fee_exemption.wallet = accs.wallet.key();
+
+    msg!("fee_exemption_granted: {}", accs.wallet.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct GrantFeeExemption<'info> {
+    #[account(
+        mut,
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can grant fee exemptions")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub pool: Account<'info, BoundPool>,
+
+    /// CHECK: Any wallet can be exempted, only its key is stored
+    pub wallet: AccountInfo<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + FeeExemption::INIT_SPACE,
+        seeds = [FeeExemption::FEE_EXEMPTION_PREFIX, pool.key().as_ref(), wallet.key().as_ref()],
+        bump
+    )]
+    pub fee_exemption: Account<'info, FeeExemption>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,39 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, SWAP_AUTH_KEY};
+use crate::err;
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Creates the global config with the swap authority as its first admin.
+/// The PDA can only be initialized once; later admins come in through
+/// `transfer_admin` and `accept_admin`.
+pub fn handle(ctx: Context<InitGlobalConfig>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.global_config.admin = accs.sender.key();
+    accs.global_config.pending_admin = Pubkey::default();
+
+    msg!("global_config_admin: {}", accs.sender.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitGlobalConfig<'info> {
+    #[account(
+        mut,
+        constraint = sender.key() == SWAP_AUTH_KEY
+            @ err::acc("Only the swap authority can initialize the global config")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + GlobalConfig::INIT_SPACE,
+        seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX],
+        bump
+    )]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,55 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, POINTS_EPOCH_SEED};
+use crate::err;
+use crate::models::global_config::GlobalConfig;
+use crate::models::points_epoch::PointsEpoch;
+use anchor_lang::prelude::*;
+
+/// Creates the points epoch swaps read their points rate from, starting at
+/// epoch 1 with every optional points rule off. The PDA can only be
+/// initialized once.
+///
+/// # Arguments
+/// * `ctx` - The context containing the new points epoch
+/// * `points_per_sol_num` - Numerator of the points earned per quote token
+/// * `points_per_sol_denom` - Denominator of the points earned per quote token
+///
+/// # Errors
+/// * `AmmError::InvalidArg` - If the denominator is 0
+pub fn handle(
+    ctx: Context<InitPointsEpoch>,
+    points_per_sol_num: u64,
+    points_per_sol_denom: u64,
+) -> Result<()> {
+    let points_epoch = &mut ctx.accounts.points_epoch;
+
+    points_epoch.epoch_number = 1;
+    points_epoch.set_rate(points_per_sol_num, points_per_sol_denom)?;
+
+    msg!("points_epoch: {}", points_epoch.epoch_number);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitPointsEpoch<'info> {
+    #[account(
+        mut,
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can initialize the points epoch")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PointsEpoch::INIT_SPACE,
+        seeds = [POINTS_EPOCH_SEED],
+        bump
+    )]
+    pub points_epoch: Account<'info, PointsEpoch>,
+
+    pub system_program: Program<'info, System>,
+}
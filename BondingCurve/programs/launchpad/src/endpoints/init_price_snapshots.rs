@@ -0,0 +1,31 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::models::bound::BoundPool;
+use crate::models::price_snapshot::PriceSnapshots;
+use anchor_lang::prelude::*;
+
+/// Creates the pool's price snapshot ring buffer. Anyone may pay for it;
+/// swaps passing it in record into it from then on.
+pub fn handle(ctx: Context<InitPriceSnapshots>) -> Result<()> {
+    ctx.accounts.price_snapshots.pool = ctx.accounts.pool.key();
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct InitPriceSnapshots<'info> {
+    #[account(mut)]
+    pub sender: Signer<'info>,
+
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PriceSnapshots::INIT_SPACE,
+        seeds = [PriceSnapshots::PRICE_SNAPSHOTS_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub price_snapshots: Box<Account<'info, PriceSnapshots>>,
+
+    pub system_program: Program<'info, System>,
+}
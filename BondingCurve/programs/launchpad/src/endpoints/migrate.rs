@@ -0,0 +1,118 @@
+use crate::err::AmmError;
+use crate::migration::{self, Graduated, Graduation};
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Graduates the pool into the AMM of its `migration_target`. Runs the same
+/// checks, burn and liquidity split as `migrate_to_raydium`, then hands the seeding
+/// to the target's `MigrationBackend`. Accounts only the target needs are
+/// passed as remaining accounts, in the order its backend documents.
+///
+/// # Arguments
+/// * `ctx` - The context containing the pool and the target's accounts
+/// * `keep_mint_authority` - Keep the meme mint authority instead of revoking it
+/// * `dry_run` - Only validate and emit `MigrationSimulated`, skipping the CPIs and state changes
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>,
+    keep_mint_authority: bool,
+    dry_run: bool,
+) -> Result<()> {
+    let accs = &mut *ctx.accounts;
+
+    let graduated = migration::graduate(
+        Graduation {
+            program_id: ctx.program_id,
+            pool: &mut accs.pool,
+            meme_mint: &mut accs.meme_mint,
+            quote_mint: &accs.quote_mint.to_account_info(),
+            meme_vault: &accs.meme_vault.to_account_info(),
+            quote_vault: &accs.quote_vault.to_account_info(),
+            pool_signer: &accs.pool_signer.to_account_info(),
+            pool_signer_bump: ctx.bumps.pool_signer,
+            fee_quote_vault: &accs.fee_quote_vault.to_account_info(),
+            creator: &accs.signer.to_account_info(),
+            creator_meme_account: &accs.creator_meme_account.to_account_info(),
+            creator_quote_account: &accs.creator_quote_account.to_account_info(),
+            token_program: &accs.token_program.to_account_info(),
+            associated_token_program: &accs.associated_token_program.to_account_info(),
+            system_program: &accs.system_program.to_account_info(),
+            rent: &accs.rent.to_account_info(),
+            target_accounts: ctx.remaining_accounts,
+        },
+        keep_mint_authority,
+        dry_run,
+    )?;
+
+    match graduated {
+        Graduated::Simulated(migration_simulated) => emit_cpi!(migration_simulated),
+        Graduated::Migrated(pool_locked, migration_event) => {
+            emit_cpi!(pool_locked);
+            emit_cpi!(migration_event);
+        }
+    }
+
+    Ok(())
+}
+
+#[event_cpi]
+#[derive(Accounts)]
+pub struct Migrate<'info> {
+    #[account(mut)]
+    pub signer: Signer<'info>,
+
+    #[account(
+        mut,
+        seeds = [
+            b"bound_pool",
+            meme_mint.key().as_ref(),
+            quote_mint.key().as_ref()
+        ],
+        bump,
+        constraint = !pool.locked @ AmmError::PoolIsLocked,
+    )]
+    pub pool: Box<Account<'info, BoundPool>>,
+
+    #[account(mut)]
+    pub meme_mint: Box<Account<'info, Mint>>,
+
+    #[account(mut)]
+    pub quote_mint: Box<Account<'info, Mint>>,
+
+    /// Pool's meme token vault
+    #[account(mut, seeds = [b"meme_vault", pool.key().as_ref()], bump)]
+    pub meme_vault: Box<Account<'info, TokenAccount>>,
+
+    /// Pool's quote token vault
+    #[account(mut, seeds = [b"quote_vault", pool.key().as_ref()], bump)]
+    pub quote_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Legacy authority PDA, kept for account order; the vaults are owned by `pool_signer`
+    #[account(seeds = [b"bound_pool_auth", pool.key().as_ref()], bump)]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Pool signer PDA, owning the vaults and the meme mint until the authority is renounced
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: UncheckedAccount<'info>,
+
+    /// Creator's meme token account the AMM is seeded from
+    #[account(mut, token::mint = meme_mint, token::authority = signer)]
+    pub creator_meme_account: Box<Account<'info, TokenAccount>>,
+
+    /// Creator's quote token account the AMM is seeded from
+    #[account(mut, token::mint = quote_mint, token::authority = signer)]
+    pub creator_quote_account: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol fee vault receiving the migration fee
+    #[account(
+        mut,
+        constraint = pool.fee_vault_quote == fee_quote_vault.key()
+    )]
+    pub fee_quote_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
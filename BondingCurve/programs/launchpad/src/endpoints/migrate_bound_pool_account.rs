@@ -0,0 +1,58 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, BP_FEE_KEY};
+use crate::err;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Upgrades a pool written with an older account layout to the current one
+/// in place, growing the account and topping up its rent from the admin.
+///
+/// # Errors
+/// * `AmmError::UnsupportedPoolVersion` - If the account isn't an old layout
+pub fn handle(ctx: Context<MigrateBoundPoolAccount>) -> Result<()> {
+    let accs = ctx.accounts;
+    let pool_info = accs.pool.to_account_info();
+
+    let pool = BoundPool::upgrade_v1(&pool_info.try_borrow_data()?)?;
+
+    let new_len = ANCHOR_DISCRIMINATOR + BoundPool::INIT_SPACE;
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(pool_info.lamports());
+
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accs.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accs.admin.to_account_info(),
+                    to: pool_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    pool_info.realloc(new_len, false)?;
+    pool.try_serialize(&mut &mut pool_info.try_borrow_mut_data()?[..])?;
+
+    msg!("pool_account_version: {}", pool.version);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateBoundPoolAccount<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == BP_FEE_KEY @ err::acc("Only the fee key can migrate pool accounts")
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: An old layout doesn't load as `BoundPool`; the owner is checked
+    /// here and the layout by `BoundPool::upgrade_v1`
+    #[account(mut, owner = crate::ID)]
+    pub pool: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,59 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, BP_FEE_KEY};
+use crate::err;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+/// Upgrades a staking pool written with an older account layout to the
+/// current one in place, growing the account and topping up its rent from
+/// the admin.
+///
+/// # Errors
+/// * `AmmError::UnsupportedPoolVersion` - If the account isn't an old layout
+pub fn handle(ctx: Context<MigrateStakingPoolAccount>) -> Result<()> {
+    let accs = ctx.accounts;
+    let staking_info = accs.staking.to_account_info();
+
+    let staking = StakingPool::upgrade_v1(&staking_info.try_borrow_data()?)?;
+
+    let new_len = ANCHOR_DISCRIMINATOR + StakingPool::INIT_SPACE;
+    let top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(staking_info.lamports());
+
+    if top_up > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                accs.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: accs.admin.to_account_info(),
+                    to: staking_info.clone(),
+                },
+            ),
+            top_up,
+        )?;
+    }
+
+    staking_info.realloc(new_len, false)?;
+    staking.try_serialize(&mut &mut staking_info.try_borrow_mut_data()?[..])?;
+
+    msg!("staking_account_version: {}", staking.version);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct MigrateStakingPoolAccount<'info> {
+    #[account(
+        mut,
+        constraint = admin.key() == BP_FEE_KEY @ err::acc("Only the fee key can migrate staking accounts")
+    )]
+    pub admin: Signer<'info>,
+
+    /// CHECK: An old layout doesn't load as `StakingPool`; the owner is
+    /// checked here and the layout by `StakingPool::upgrade_v1`
+    #[account(mut, owner = crate::ID)]
+    pub staking: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
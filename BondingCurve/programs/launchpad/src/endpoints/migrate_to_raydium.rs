@@ -1,15 +1,18 @@
 use crate::consts::*;
 use crate::err::AmmError;
-use crate::models::bound::BoundPool;
+use crate::libraries::MulDiv;
+use crate::migration::{self, validate_mint_ordering, Graduated, Graduation};
+use crate::models::bound::{BoundPool, GraduationMode, MigrationTarget};
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program_option::COption;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{Mint, Token, TokenAccount};
 use raydium_cpmm_cpi::{
-    cpi,
     program::RaydiumCpmm,
     states::{AmmConfig, OBSERVATION_SEED, POOL_LP_MINT_SEED, POOL_SEED, POOL_VAULT_SEED},
 };
 
+#[event_cpi]
 #[derive(Accounts)]
 pub struct MigrateToRaydium<'info> {
     #[account(mut)]
@@ -27,14 +30,13 @@ pub struct MigrateToRaydium<'info> {
         ],
         bump,
         constraint = !pool.locked @ AmmError::PoolIsLocked,
+        constraint = pool.config.migration_target == MigrationTarget::Raydium
+            @ AmmError::MigrationTargetMismatch,
     )]
     pub pool: Account<'info, BoundPool>,
 
     /// Meme token mint (must be smaller key than quote_mint for CPMM)
-    #[account(
-        mut,
-        constraint = meme_mint.key() < quote_mint.key() @ AmmError::InvalidTokenOrder
-    )]
+    #[account(mut)]
     pub meme_mint: Account<'info, Mint>,
 
     /// Quote token mint (WSOL - must be larger key than meme_mint)
@@ -63,7 +65,7 @@ pub struct MigrateToRaydium<'info> {
     )]
     pub quote_vault: Account<'info, TokenAccount>,
 
-    /// CHECK: Authority PDA that controls the bonding curve vaults, seeds are verified for security
+    /// CHECK: Legacy authority PDA, kept for account order; the vaults are owned by `pool_signer`
     #[account(
         seeds = [
             b"bound_pool_auth",
@@ -73,6 +75,13 @@ pub struct MigrateToRaydium<'info> {
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
+    /// CHECK: Pool signer PDA, owning the vaults and the meme mint until the authority is renounced
+    #[account(
+        seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()],
+        bump,
+    )]
+    pub pool_signer: UncheckedAccount<'info>,
+
     // === RAYDIUM CPMM ACCOUNTS ===
     /// Raydium AMM config account
     pub amm_config: Box<Account<'info, AmmConfig>>,
@@ -129,6 +138,13 @@ pub struct MigrateToRaydium<'info> {
     )]
     pub creator_quote_account: Account<'info, TokenAccount>,
 
+    /// Protocol fee vault receiving the migration fee
+    #[account(
+        mut,
+        constraint = pool.fee_vault_quote == fee_quote_vault.key()
+    )]
+    pub fee_quote_vault: Account<'info, TokenAccount>,
+
     /// CHECK: Creator's LP token account to receive LP tokens, will be created by Raydium
     #[account(mut)]
     pub creator_lp_token: UncheckedAccount<'info>,
@@ -184,129 +200,718 @@ pub struct MigrateToRaydium<'info> {
     pub rent: Sysvar<'info, Rent>,
 }
 
-pub fn handle(ctx: Context<MigrateToRaydium>) -> Result<()> {
-    let pool = &mut ctx.accounts.pool;
+pub fn handle(
+    ctx: Context<MigrateToRaydium>,
+    keep_mint_authority: bool,
+    dry_run: bool,
+) -> Result<()> {
+    // CPMM can't take the pair the other way round
+    validate_mint_ordering(&ctx.accounts.meme_mint.key(), &ctx.accounts.quote_mint.key())?;
+
+    let accs = &mut *ctx.accounts;
+
+    // Raydium's accounts in the backend's order
+    let target_accounts = [
+        accs.cp_swap_program.to_account_info(),
+        accs.amm_config.to_account_info(),
+        accs.raydium_authority.to_account_info(),
+        accs.raydium_pool_state.to_account_info(),
+        accs.raydium_lp_mint.to_account_info(),
+        accs.creator_lp_token.to_account_info(),
+        accs.token_0_vault.to_account_info(),
+        accs.token_1_vault.to_account_info(),
+        accs.create_pool_fee.to_account_info(),
+        accs.observation_state.to_account_info(),
+    ];
 
-    // 1. Check migration threshold
-    let migration_threshold = calculate_migration_threshold(pool)?;
-    let current_meme_supply = pool.meme_reserve.tokens - ctx.accounts.meme_vault.amount;
+    let graduated = migration::graduate(
+        Graduation {
+            program_id: ctx.program_id,
+            pool: &mut accs.pool,
+            meme_mint: &mut accs.meme_mint,
+            quote_mint: &accs.quote_mint.to_account_info(),
+            meme_vault: &accs.meme_vault.to_account_info(),
+            quote_vault: &accs.quote_vault.to_account_info(),
+            pool_signer: &accs.pool_signer.to_account_info(),
+            pool_signer_bump: ctx.bumps.pool_signer,
+            fee_quote_vault: &accs.fee_quote_vault.to_account_info(),
+            creator: &accs.signer.to_account_info(),
+            creator_meme_account: &accs.creator_meme_account.to_account_info(),
+            creator_quote_account: &accs.creator_quote_account.to_account_info(),
+            token_program: &accs.token_program.to_account_info(),
+            associated_token_program: &accs.associated_token_program.to_account_info(),
+            system_program: &accs.system_program.to_account_info(),
+            rent: &accs.rent.to_account_info(),
+            target_accounts: &target_accounts,
+        },
+        keep_mint_authority,
+        dry_run,
+    )?;
+
+    match graduated {
+        Graduated::Simulated(migration_simulated) => emit_cpi!(migration_simulated),
+        Graduated::Migrated(pool_locked, migration_event) => {
+            emit_cpi!(pool_locked);
+            emit_cpi!(migration_event);
+        }
+    }
 
+    Ok(())
+}
+
+/// Runs every migration check and returns the `(meme, quote)` amounts to seed
+/// the AMM with, plus the migration fee. Leaves the pool untouched, so dry runs
+/// can share it.
+pub(crate) fn plan_migration(pool: &BoundPool) -> Result<(u64, u64, u64)> {
+    // Refuse to migrate twice
+    pool.check_not_migrated()?;
+
+    // Check migration threshold
     require!(
-        current_meme_supply >= migration_threshold,
-        AmmError::MigrationThresholdNotReached
+        migration_threshold_reached(pool)?,
+        AmmError::MigrationThresholdNotMet
     );
 
-    // 2. Lock the pool to prevent further trading
-    pool.locked = true;
+    // Don't let a single whale graduate the pool
+    check_migration_holders(pool)?;
+
+    // Don't seed an AMM pool with dust
+    check_migration_liquidity(pool.quote_reserve.tokens)?;
 
-    // 3. Calculate liquidity amounts for Raydium pool
     let (meme_amount, quote_amount) = calculate_migration_amounts(pool)?;
+    let (meme_amount, quote_amount, migration_fee) =
+        apply_migration_fee(meme_amount, quote_amount)?;
 
-    // 4. Prepare authority seeds for token transfers
-    let pool_key = pool.key();
-    let auth_seeds = &[
-        b"bound_pool_auth",
-        pool_key.as_ref(),
-        &[ctx.bumps.pool_authority],
-    ];
-    let signer_seeds = &[&auth_seeds[..]];
-
-    // 5. Transfer tokens from bonding curve to creator accounts
-    // Transfer meme tokens
-    let transfer_meme_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.meme_vault.to_account_info(),
-            to: ctx.accounts.creator_meme_account.to_account_info(),
-            authority: ctx.accounts.pool_authority.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(transfer_meme_ctx, meme_amount)?;
-
-    // Transfer quote tokens
-    let transfer_quote_ctx = CpiContext::new_with_signer(
-        ctx.accounts.token_program.to_account_info(),
-        Transfer {
-            from: ctx.accounts.quote_vault.to_account_info(),
-            to: ctx.accounts.creator_quote_account.to_account_info(),
-            authority: ctx.accounts.pool_authority.to_account_info(),
-        },
-        signer_seeds,
-    );
-    token::transfer(transfer_quote_ctx, quote_amount)?;
-
-    // 6. Calculate open time (can trade immediately)
-    let clock = Clock::get()?;
-    let open_time = clock.unix_timestamp as u64;
-
-    // 7. Initialize Raydium CPMM pool via CPI
-    let cpi_accounts = cpi::accounts::Initialize {
-        creator: ctx.accounts.signer.to_account_info(),
-        amm_config: ctx.accounts.amm_config.to_account_info(),
-        authority: ctx.accounts.raydium_authority.to_account_info(),
-        pool_state: ctx.accounts.raydium_pool_state.to_account_info(),
-        token_0_mint: ctx.accounts.meme_mint.to_account_info(), // smaller key
-        token_1_mint: ctx.accounts.quote_mint.to_account_info(), // larger key
-        lp_mint: ctx.accounts.raydium_lp_mint.to_account_info(),
-        creator_token_0: ctx.accounts.creator_meme_account.to_account_info(),
-        creator_token_1: ctx.accounts.creator_quote_account.to_account_info(),
-        creator_lp_token: ctx.accounts.creator_lp_token.to_account_info(),
-        token_0_vault: ctx.accounts.token_0_vault.to_account_info(),
-        token_1_vault: ctx.accounts.token_1_vault.to_account_info(),
-        create_pool_fee: ctx.accounts.create_pool_fee.to_account_info(),
-        observation_state: ctx.accounts.observation_state.to_account_info(),
-        token_program: ctx.accounts.token_program.to_account_info(),
-        token_0_program: ctx.accounts.token_program.to_account_info(),
-        token_1_program: ctx.accounts.token_program.to_account_info(),
-        associated_token_program: ctx.accounts.associated_token_program.to_account_info(),
-        system_program: ctx.accounts.system_program.to_account_info(),
-        rent: ctx.accounts.rent.to_account_info(),
-    };
-
-    let cpi_context = CpiContext::new(ctx.accounts.cp_swap_program.to_account_info(), cpi_accounts);
-
-    // Call Raydium's initialize function
-    cpi::initialize(cpi_context, meme_amount, quote_amount, open_time)?;
-
-    // 8. Update pool state
-    pool.meme_reserve.tokens = ctx.accounts.meme_vault.amount - meme_amount;
-    pool.quote_reserve.tokens = ctx.accounts.quote_vault.amount - quote_amount;
-    pool.pool_migration = true;
-    pool.pool_key = ctx.accounts.raydium_pool_state.key();
-
-    // 9. Emit migration event
-    emit!(MigrationEvent {
-        pool: pool.key(),
-        raydium_pool: ctx.accounts.raydium_pool_state.key(),
-        meme_amount_migrated: meme_amount,
-        quote_amount_migrated: quote_amount,
-        timestamp: clock.unix_timestamp,
-    });
+    // Don't open the AMM at a price arbitrageurs would close at the pool's expense
+    check_migration_price(pool, meme_amount, quote_amount)?;
+
+    Ok((meme_amount, quote_amount, migration_fee))
+}
+
+/// Meme a `TokenTarget` pool must have sold off its curve to migrate, 80% of
+/// its trading supply.
+pub(crate) fn calculate_migration_threshold(pool: &BoundPool) -> Result<u64> {
+    pool.config
+        .gamma_m
+        .mul_div_ceil(MIGRATION_MEME_BPS, BPS_DENOMINATOR)
+        .ok_or(error!(AmmError::MathOverflow))
+}
+
+/// Whether the pool has met the threshold of its `graduation_mode`.
+pub(crate) fn migration_threshold_reached(pool: &BoundPool) -> Result<bool> {
+    match pool.config.graduation_mode {
+        GraduationMode::QuotePercent => {
+            let quote_target = pool
+                .config
+                .gamma_s
+                .mul_div_ceil(MIGRATION_QUOTE_BPS, BPS_DENOMINATOR)
+                .ok_or(error!(AmmError::MathOverflow))?;
+
+            // Virtual SOL counts towards the target like it does on the curve
+            let (_, quote_supply) = pool.balances();
+
+            Ok(quote_supply >= quote_target)
+        }
+        GraduationMode::TokenTarget => {
+            let migration_threshold = calculate_migration_threshold(pool)?;
+
+            Ok(pool.meme_sold()? >= migration_threshold)
+        }
+    }
+}
+
+/// Rejects migrating a pool bought by fewer distinct wallets than its
+/// `min_unique_buyers`. A zero minimum disables the check.
+pub(crate) fn check_migration_holders(pool: &BoundPool) -> Result<()> {
+    if pool.unique_buyers < pool.config.min_unique_buyers {
+        return Err(error!(AmmError::InsufficientHolders));
+    }
+
+    Ok(())
+}
+
+/// Rejects migrating a curve whose quote reserve is too thin for a usable AMM pool.
+pub(crate) fn check_migration_liquidity(quote_reserve: u64) -> Result<()> {
+    if quote_reserve < MIN_MIGRATION_QUOTE {
+        return Err(error!(AmmError::InsufficientMigrationLiquidity));
+    }
 
     Ok(())
 }
 
-fn calculate_migration_threshold(pool: &BoundPool) -> Result<u64> {
-    // 80% of max supply sold = ready for migration
-    let threshold = (DEFAULT_MAX_M as u64 * 80) / 100;
-    Ok(threshold)
+/// Rejects seeding the AMM at a price further than `migration_price_tolerance_bps`
+/// from the curve's spot price. A zero tolerance disables the check.
+pub(crate) fn check_migration_price(
+    pool: &BoundPool,
+    meme_amount: u64,
+    quote_amount: u64,
+) -> Result<()> {
+    let tolerance_bps = pool.config.migration_price_tolerance_bps as u64;
+
+    if tolerance_bps == 0 {
+        return Ok(());
+    }
+
+    let curve_price = pool.spot_price()?;
+    let amm_price = quote_amount
+        .mul_div_floor(DECIMALS_S as u64, meme_amount)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    let deviation_bps = amm_price
+        .abs_diff(curve_price)
+        .mul_div_ceil(BPS_DENOMINATOR, curve_price)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    if deviation_bps > tolerance_bps {
+        return Err(error!(AmmError::MigrationPriceDeviation));
+    }
+
+    Ok(())
+}
+
+/// Whether migration should revoke the meme mint authority. Nothing to do when
+/// it's already gone, e.g. renounced in `create_metadata`.
+pub(crate) fn should_renounce_mint_authority(
+    mint_authority: &COption<Pubkey>,
+    keep_mint_authority: bool,
+) -> bool {
+    !keep_mint_authority && mint_authority.is_some()
+}
+
+/// Meme burned at migration: `burn_unsold_bps` of what's still unsold on the
+/// curve. The AMM is seeded from what's left.
+pub(crate) fn calculate_burn_amount(pool: &BoundPool) -> Result<u64> {
+    pool.meme_reserve
+        .tokens
+        .mul_div_floor(pool.config.burn_unsold_bps as u64, BPS_DENOMINATOR)
+        .ok_or(error!(AmmError::MathOverflow))
 }
 
-fn calculate_migration_amounts(pool: &BoundPool) -> Result<(u64, u64)> {
-    // Use most of the remaining liquidity for the Raydium pool
-    // Reserve 5% for potential continued bonding curve trading
-    let meme_amount = (pool.meme_reserve.tokens * 95) / 100;
-    let quote_amount = (pool.quote_reserve.tokens * 95) / 100;
+pub(crate) fn calculate_migration_amounts(pool: &BoundPool) -> Result<(u64, u64)> {
+    // Use most of the remaining liquidity for the Raydium pool, after the burn
+    // The rest is kept for potential continued bonding curve trading
+    let meme_amount = (pool.meme_reserve.tokens - calculate_burn_amount(pool)?)
+        .mul_div_floor(MIGRATION_LP_BPS, BPS_DENOMINATOR)
+        .ok_or(error!(AmmError::MathOverflow))?;
+    let quote_amount = pool
+        .quote_reserve
+        .tokens
+        .mul_div_floor(MIGRATION_LP_BPS, BPS_DENOMINATOR)
+        .ok_or(error!(AmmError::MathOverflow))?;
 
     Ok((meme_amount, quote_amount))
 }
 
+/// Takes `MIGRATION_FEE_BPS` of the migrated quote for the protocol and trims
+/// the meme side by the same share, so the AMM opens at the curve's ratio.
+/// Returns `(meme, quote, fee)`; the trimmed meme stays on the curve.
+pub(crate) fn apply_migration_fee(
+    meme_amount: u64,
+    quote_amount: u64,
+) -> Result<(u64, u64, u64)> {
+    let fee = quote_amount
+        .mul_div_floor(MIGRATION_FEE_BPS, BPS_DENOMINATOR)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    if fee == 0 {
+        return Ok((meme_amount, quote_amount, 0));
+    }
+
+    let net_quote = quote_amount - fee;
+    let net_meme = meme_amount
+        .mul_div_floor(net_quote, quote_amount)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    Ok((net_meme, net_quote, fee))
+}
+
+/// Takes the meme and quote that left the curve at migration off its reserves.
+/// The vaults also hold fees and supply off the curve, so their balances
+/// can't stand in for what's left.
+///
+/// # Arguments
+/// * `pool` - The migrating pool
+/// * `meme_out` - Meme seeded into the AMM plus the burned meme
+/// * `quote_out` - Quote seeded into the AMM plus the migration fee
+pub(crate) fn debit_migrated_reserves(
+    pool: &mut BoundPool,
+    meme_out: u64,
+    quote_out: u64,
+) -> Result<()> {
+    pool.meme_reserve.tokens = pool
+        .meme_reserve
+        .tokens
+        .checked_sub(meme_out)
+        .ok_or(error!(AmmError::MathOverflow))?;
+    pool.quote_reserve.tokens = pool
+        .quote_reserve
+        .tokens
+        .checked_sub(quote_out)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    Ok(())
+}
+
 #[event]
 pub struct MigrationEvent {
     pub pool: Pubkey,
     pub raydium_pool: Pubkey,
     pub meme_amount_migrated: u64,
     pub quote_amount_migrated: u64,
+    /// Quote skimmed to the protocol fee vault
+    pub migration_fee: u64,
+    /// Unsold meme burned instead of seeding the AMM
+    pub meme_burned: u64,
     pub timestamp: i64,
+    /// Whether the meme mint is left without a mint authority
+    pub mint_authority_renounced: bool,
+}
+
+/// Emitted by a dry-run migration with what a real one would do
+#[event]
+pub struct MigrationSimulated {
+    pub pool: Pubkey,
+    pub raydium_pool: Pubkey,
+    pub meme_amount: u64,
+    pub quote_amount: u64,
+    pub migration_fee: u64,
+    pub meme_burned: u64,
+    pub renounces_mint_authority: bool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::migration::RaydiumBackend;
+    use crate::models::bound::{test_pool, Config};
+
+    #[test]
+    fn test_migration_liquidity_threshold() {
+        check_migration_liquidity(MIN_MIGRATION_QUOTE).unwrap();
+        check_migration_liquidity(MIN_MIGRATION_QUOTE + 1).unwrap();
+
+        assert_eq!(
+            check_migration_liquidity(MIN_MIGRATION_QUOTE - 1).unwrap_err(),
+            error!(AmmError::InsufficientMigrationLiquidity)
+        );
+        assert_eq!(
+            check_migration_liquidity(0).unwrap_err(),
+            error!(AmmError::InsufficientMigrationLiquidity)
+        );
+
+        println!("✅ Migration liquidity threshold test passed!");
+    }
+
+    #[test]
+    fn test_mint_authority_renounce_flag() {
+        let pool_signer = COption::Some(Pubkey::new_unique());
+
+        // Renounced by default, kept when the project opts out
+        assert!(should_renounce_mint_authority(&pool_signer, false));
+        assert!(!should_renounce_mint_authority(&pool_signer, true));
+
+        // Already cleared authority needs no CPI either way
+        assert!(!should_renounce_mint_authority(&COption::None, false));
+        assert!(!should_renounce_mint_authority(&COption::None, true));
+
+        println!("✅ Mint authority renounce flag test passed!");
+    }
+
+    fn create_test_pool(graduation_mode: GraduationMode) -> BoundPool {
+        let pool = test_pool();
+
+        BoundPool {
+            config: Config {
+                graduation_mode,
+                ..pool.config
+            },
+            ..pool
+        }
+    }
+
+    #[test]
+    fn test_migration_precondition_errors() {
+        // ARRANGE: A pool one lamport short of graduating
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 68_000_000_000 - 1;
+
+        println!("🧪 Testing each migration precondition fails on its own error");
+
+        // ACT & ASSERT: Below the threshold
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::MigrationThresholdNotMet)
+        );
+
+        // Past the threshold the plan goes through
+        pool.quote_reserve.tokens = 68_000_000_000;
+        plan_migration(&pool).unwrap();
+
+        // A graduated pool is refused before the threshold is looked at
+        pool.pool_migration = true;
+        pool.quote_reserve.tokens = 0;
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        // Mint ordering and the Raydium accounts are covered by the backend
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+        assert_eq!(
+            RaydiumBackend::check_target_accounts(&high, &low, &[Pubkey::default(); 10])
+                .unwrap_err(),
+            error!(AmmError::InvalidMintOrdering)
+        );
+        assert_eq!(
+            RaydiumBackend::check_target_accounts(&low, &high, &[Pubkey::default(); 10])
+                .unwrap_err(),
+            error!(AmmError::InvalidRaydiumAccounts)
+        );
+
+        println!("✅ Migration precondition errors test passed!");
+    }
+
+    #[test]
+    fn test_quote_percent_graduation() {
+        // ARRANGE: 80% of an 85 SOL target is 68 SOL
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+
+        println!("🧪 Testing QuotePercent graduation");
+
+        // ACT & ASSERT: Triggers on the quote reserve alone
+        pool.quote_reserve.tokens = 68_000_000_000 - 1;
+        assert!(!migration_threshold_reached(&pool).unwrap());
+
+        pool.quote_reserve.tokens = 68_000_000_000;
+        assert!(migration_threshold_reached(&pool).unwrap());
+
+        // Tokens sold don't matter in this mode
+        pool.meme_reserve.tokens -= calculate_migration_threshold(&pool).unwrap();
+        pool.quote_reserve.tokens = 0;
+        assert!(!migration_threshold_reached(&pool).unwrap());
+
+        println!("✅ QuotePercent graduation test passed!");
+    }
+
+    #[test]
+    fn test_token_target_graduation() {
+        // ARRANGE: A fresh pool, 80% of its 690M trading supply to sell
+        let mut pool = create_test_pool(GraduationMode::TokenTarget);
+        let threshold = calculate_migration_threshold(&pool).unwrap();
+        assert_eq!(threshold, 552_000_000_000_000);
+
+        println!("🧪 Testing TokenTarget graduation");
+
+        // ACT & ASSERT: Buy through the curve in 5 SOL steps, refused until
+        // the threshold is sold
+        while pool.meme_sold().unwrap() < threshold {
+            assert!(!migration_threshold_reached(&pool).unwrap());
+            assert_eq!(
+                plan_migration(&pool).unwrap_err(),
+                error!(AmmError::MigrationThresholdNotMet)
+            );
+
+            let buy = pool.swap_amounts(5_000_000_000, 0, true).unwrap();
+            pool.admin_fees_quote += buy.admin_fee_in;
+            pool.quote_reserve.tokens += buy.amount_in;
+            pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        }
+
+        // Graduates before the curve sells out
+        assert!(migration_threshold_reached(&pool).unwrap());
+        assert!(pool.meme_reserve.tokens > 0);
+        plan_migration(&pool).unwrap();
+
+        println!("✅ TokenTarget graduation test passed!");
+    }
+
+    #[test]
+    fn test_dry_run_leaves_pool_untouched() {
+        // ARRANGE: A QuotePercent pool past its threshold
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = pool.config.gamma_s;
+        let before = pool.try_to_vec().unwrap();
+
+        println!("🧪 Testing a dry-run migration changes no state");
+
+        // ACT
+        let plan = plan_migration(&pool).unwrap();
+
+        // ASSERT: Same split as a real migration, pool bytes unchanged
+        let (meme_amount, quote_amount) = calculate_migration_amounts(&pool).unwrap();
+        assert_eq!(plan, apply_migration_fee(meme_amount, quote_amount).unwrap());
+        assert_eq!(pool.try_to_vec().unwrap(), before);
+        assert!(!pool.locked);
+        assert!(!pool.pool_migration);
+
+        // The dry run still enforces every check
+        pool.pool_migration = true;
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Dry-run migration test passed!");
+    }
+
+    #[test]
+    fn test_migration_price_within_tolerance() {
+        // ARRANGE: Reserves whose ratio matches the curve's spot price at 80 SOL
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 80_000_000_000;
+        let curve_price = pool.spot_price().unwrap();
+        pool.meme_reserve.tokens = (pool.quote_reserve.tokens as u128 * DECIMALS_S
+            / curve_price as u128) as u64;
+        pool.config.migration_price_tolerance_bps = 100;
+
+        println!("🧪 Testing the seeded AMM price against the curve");
+
+        // ACT: Split the reserves as a migration would
+        let (meme_amount, quote_amount, _) =
+            plan_migration(&pool).unwrap();
+
+        // ASSERT: The seeded ratio lands within 1% of the curve's price
+        let amm_price = quote_amount as u128 * DECIMALS_S / meme_amount as u128;
+        let deviation_bps =
+            amm_price.abs_diff(curve_price as u128) * 10_000 / curve_price as u128;
+        assert!(deviation_bps <= 100, "seeded {} vs curve {}", amm_price, curve_price);
+
+        // Seeding 2% less meme opens the AMM 2% above the curve
+        let short = meme_amount - meme_amount / 50;
+        assert_eq!(
+            check_migration_price(&pool, short, quote_amount).unwrap_err(),
+            error!(AmmError::MigrationPriceDeviation)
+        );
+
+        // The migration itself refuses such a split
+        pool.meme_reserve.tokens -= pool.meme_reserve.tokens / 50;
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::MigrationPriceDeviation)
+        );
+
+        // A zero tolerance leaves the price unchecked
+        pool.config.migration_price_tolerance_bps = 0;
+        check_migration_price(&pool, short, quote_amount).unwrap();
+
+        println!("✅ Migration price tolerance test passed!");
+    }
+
+    #[test]
+    fn test_migration_price_tolerance_set_by_update() {
+        // ARRANGE: Reserves 2% short on meme, seeding the AMM above the curve
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 80_000_000_000;
+        let curve_price = pool.spot_price().unwrap();
+        pool.meme_reserve.tokens = (pool.quote_reserve.tokens as u128 * DECIMALS_S
+            / curve_price as u128) as u64;
+        pool.meme_reserve.tokens -= pool.meme_reserve.tokens / 50;
+
+        println!("🧪 Testing the migration price guard switched on after launch");
+
+        // The guard launches disabled, so the skewed split migrates
+        plan_migration(&pool).unwrap();
+
+        // ACT: The admin caps the AMM's opening price at 1% off the curve
+        let mut guards = pool.guards();
+        guards.migration_price_tolerance_bps = 100;
+        pool.update_guards(guards).unwrap();
+
+        // ASSERT: The same split is now refused
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::MigrationPriceDeviation)
+        );
+
+        // A tolerance above 100% is refused
+        guards.migration_price_tolerance_bps = 10_001;
+        assert_eq!(
+            pool.update_guards(guards).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+
+        println!("✅ Updated migration price tolerance test passed!");
+    }
+
+    #[test]
+    fn test_migration_fee_skim() {
+        // ARRANGE: 95% of an 80 SOL reserve migrates
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 80_000_000_000;
+        pool.meme_reserve.tokens = 138_000_000_000_000;
+        let (meme_amount, quote_amount) = calculate_migration_amounts(&pool).unwrap();
+
+        println!("🧪 Testing the migration fee skim");
+
+        // ACT
+        let (net_meme, net_quote, fee) = apply_migration_fee(meme_amount, quote_amount).unwrap();
+
+        // ASSERT: The treasury gets 1% of the migrated quote
+        assert_eq!(quote_amount, 76_000_000_000);
+        assert_eq!(fee, 760_000_000);
+        assert_eq!(net_quote + fee, quote_amount);
+
+        // The AMM opens at the same meme/quote ratio as before the skim
+        assert_eq!(net_meme, meme_amount / 100 * 99);
+        assert_eq!(
+            net_quote as u128 * meme_amount as u128,
+            quote_amount as u128 * net_meme as u128
+        );
+
+        // A migration too small for a fee isn't trimmed
+        assert_eq!(apply_migration_fee(50, 99).unwrap(), (50, 99, 0));
+
+        println!("✅ Migration fee skim test passed!");
+    }
+
+    #[test]
+    fn test_burn_unsold_before_seeding() {
+        // ARRANGE: The fee skim pool, burning 20% of its unsold meme
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 80_000_000_000;
+        pool.meme_reserve.tokens = 138_000_000_000_000;
+        let unburned = calculate_migration_amounts(&pool).unwrap();
+        pool.config.burn_unsold_bps = 2_000;
+
+        println!("🧪 Testing the unsold meme burn at migration");
+
+        // ACT
+        let meme_burned = calculate_burn_amount(&pool).unwrap();
+        let (meme_amount, quote_amount) = calculate_migration_amounts(&pool).unwrap();
+
+        // ASSERT: 20% burned, 95% of the rest seeds the AMM
+        assert_eq!(meme_burned, 27_600_000_000_000);
+        assert_eq!(meme_amount, 104_880_000_000_000);
+        assert!(meme_burned + meme_amount <= pool.meme_reserve.tokens);
+
+        // The quote side is untouched, so the AMM opens at a higher price
+        assert_eq!(quote_amount, unburned.1);
+        assert!(meme_amount < unburned.0);
+
+        // Migration plans the same burned split
+        let (planned_meme, planned_quote, fee) =
+            plan_migration(&pool).unwrap();
+        assert_eq!(
+            (planned_meme, planned_quote, fee),
+            apply_migration_fee(meme_amount, quote_amount).unwrap()
+        );
+
+        // Burning nothing is the default
+        pool.config.burn_unsold_bps = 0;
+        assert_eq!(calculate_burn_amount(&pool).unwrap(), 0);
+        assert_eq!(calculate_migration_amounts(&pool).unwrap(), unburned);
+
+        println!("✅ Unsold meme burn test passed!");
+    }
+
+    #[test]
+    fn test_reserves_after_migration() {
+        // ARRANGE: The fee skim pool burning 20%, its vaults also holding the
+        // LP supply and accrued fees
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = 80_000_000_000;
+        pool.meme_reserve.tokens = 138_000_000_000_000;
+        pool.admin_fees_quote = 500_000_000;
+        pool.config.burn_unsold_bps = 2_000;
+        let meme_vault = pool.meme_reserve.tokens + DEFAULT_MAX_M_LP as u64;
+        let quote_vault = pool.quote_reserve.tokens + pool.admin_fees_quote;
+        let (meme_amount, quote_amount, migration_fee) =
+            plan_migration(&pool).unwrap();
+        let meme_burned = calculate_burn_amount(&pool).unwrap();
+
+        println!("🧪 Testing the reserves left on the curve after migration");
+
+        // ACT
+        debit_migrated_reserves(
+            &mut pool,
+            meme_amount + meme_burned,
+            quote_amount + migration_fee,
+        )
+        .unwrap();
+
+        // ASSERT: Only the trimmed meme and the last 5% of quote stay behind
+        assert_eq!(
+            pool.meme_reserve.tokens,
+            138_000_000_000_000 - meme_amount - meme_burned
+        );
+        assert_eq!(pool.quote_reserve.tokens, 4_000_000_000);
+
+        // The vaults after the transfers still back what the pool owes,
+        // without the LP supply or the fees counted as reserve
+        let meme_left = meme_vault - meme_amount - meme_burned;
+        let quote_left = quote_vault - quote_amount - migration_fee;
+        pool.check_reserve_invariant(meme_left, quote_left).unwrap();
+        assert!(pool.meme_reserve.tokens < meme_left);
+        assert_eq!(pool.quote_reserve.tokens + pool.admin_fees_quote, quote_left);
+
+        // More than the reserve can't leave it
+        assert_eq!(
+            debit_migrated_reserves(&mut pool, 0, quote_left).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        println!("✅ Post-migration reserves test passed!");
+    }
+
+    #[test]
+    fn test_min_unique_buyers_boundary() {
+        // ARRANGE: A pool past its threshold, bought by one wallet short
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = pool.config.gamma_s;
+        pool.config.min_unique_buyers = 25;
+        pool.unique_buyers = 24;
+
+        println!("🧪 Testing the distinct buyer requirement at its boundary");
+
+        // ACT & ASSERT: One buyer short is refused
+        assert_eq!(
+            check_migration_holders(&pool).unwrap_err(),
+            error!(AmmError::InsufficientHolders)
+        );
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::InsufficientHolders)
+        );
+
+        // Exactly the minimum migrates
+        pool.unique_buyers = 25;
+        check_migration_holders(&pool).unwrap();
+        plan_migration(&pool).unwrap();
+
+        // A zero minimum lets even a single-buyer pool graduate
+        pool.config.min_unique_buyers = 0;
+        pool.unique_buyers = 1;
+        plan_migration(&pool).unwrap();
+
+        println!("✅ Distinct buyer requirement test passed!");
+    }
+
+    #[test]
+    fn test_min_unique_buyers_set_by_update() {
+        // ARRANGE: A pool past its threshold, bought by a single wallet
+        let mut pool = create_test_pool(GraduationMode::QuotePercent);
+        pool.quote_reserve.tokens = pool.config.gamma_s;
+        pool.unique_buyers = 1;
+
+        println!("🧪 Testing the distinct buyer requirement switched on after launch");
+
+        // The requirement launches disabled, so the whale alone graduates it
+        plan_migration(&pool).unwrap();
+
+        // ACT: The admin asks for 25 distinct buyers before migrating
+        let mut guards = pool.guards();
+        guards.min_unique_buyers = 25;
+        pool.update_guards(guards).unwrap();
+
+        // ASSERT: The single-buyer pool can't migrate anymore, 25 buyers can
+        assert_eq!(
+            plan_migration(&pool).unwrap_err(),
+            error!(AmmError::InsufficientHolders)
+        );
+        pool.unique_buyers = 25;
+        plan_migration(&pool).unwrap();
+
+        println!("✅ Updated distinct buyer requirement test passed!");
+    }
 }
@@ -1,18 +1,108 @@
+pub use abort_launch::*;
+pub use accept_admin::*;
+pub use accept_creator_transfer::*;
+pub use cancel_limit_order::*;
+pub use claim_creator_allocation::*;
+pub use claim_points::*;
+pub use claim_staking_rewards::*;
 pub use create_metadata::*;
+pub use estimate_compute::*;
+pub use execute_limit_order::*;
+pub use get_migration_accounts::*;
+pub use get_pool_state::*;
+pub use get_quote::*;
 pub use get_swap_x_amt::*;
 pub use get_swap_y_amt::*;
+pub use grant_fee_exemption::*;
+pub use init_global_config::*;
+pub use init_points_epoch::*;
+pub use init_price_snapshots::*;
+pub use migrate::*;
+pub use migrate_bound_pool_account::*;
+pub use migrate_staking_pool_account::*;
 pub use migrate_to_raydium::*;
 pub use new_pool::*;
+pub use open_points_escrow::*;
+pub use open_referrer_stats::*;
+pub use place_limit_order::*;
+pub use propose_creator_transfer::*;
+pub use read_twap::*;
+pub use read_pool_registry::*;
+pub use read_referrer_stats::*;
+pub use read_snapshots::*;
+pub use reclaim_expired_airdrop::*;
+pub use recover_stuck_tokens::*;
+pub use register_referral_code::*;
+pub use route_fees_to_stakers::*;
 pub use send_airdrop_funds::*;
+pub use send_airdrop_funds_batch::*;
+pub use set_creator_exemption::*;
+pub use simulate_migration::*;
+pub use stake::*;
 
 pub use swap_x::*;
 pub use swap_y::*;
+pub use transfer_admin::*;
+pub use unstake::*;
+pub use update_fees::*;
+pub use update_metadata::*;
+pub use update_points_epoch::*;
+pub use update_pool_guards::*;
+pub use withdraw_admin_fees::*;
+pub use withdraw_creator_fees::*;
 
+pub mod abort_launch;
+pub mod accept_admin;
+pub mod accept_creator_transfer;
+pub mod cancel_limit_order;
+pub mod claim_creator_allocation;
+pub mod claim_points;
+pub mod claim_staking_rewards;
 pub mod create_metadata;
+pub mod estimate_compute;
+pub mod execute_limit_order;
+pub mod get_migration_accounts;
+pub mod get_pool_state;
+pub mod get_quote;
 pub mod get_swap_x_amt;
 pub mod get_swap_y_amt;
+pub mod grant_fee_exemption;
+pub mod init_global_config;
+pub mod init_points_epoch;
+pub mod init_price_snapshots;
+pub mod migrate;
+pub mod migrate_bound_pool_account;
+pub mod migrate_staking_pool_account;
 pub mod migrate_to_raydium;
 pub mod new_pool;
+pub mod open_points_escrow;
+pub mod open_referrer_stats;
+pub mod place_limit_order;
+pub mod propose_creator_transfer;
+pub mod read_twap;
+pub mod read_pool_registry;
+pub mod read_referrer_stats;
+pub mod read_snapshots;
+pub mod reclaim_expired_airdrop;
+pub mod recover_stuck_tokens;
+pub mod register_referral_code;
+pub mod route_fees_to_stakers;
 pub mod send_airdrop_funds;
+pub mod send_airdrop_funds_batch;
+pub mod set_creator_exemption;
+pub mod simulate_migration;
+pub mod stake;
 pub mod swap_x;
+pub mod swap_x_exact_out;
 pub mod swap_y;
+pub mod swap_y_batch;
+pub mod swap_y_exact_out;
+pub mod swap_y_native;
+pub mod transfer_admin;
+pub mod unstake;
+pub mod update_fees;
+pub mod update_metadata;
+pub mod update_points_epoch;
+pub mod update_pool_guards;
+pub mod withdraw_admin_fees;
+pub mod withdraw_creator_fees;
@@ -1,13 +1,25 @@
 use crate::consts::{
-    ANCHOR_DISCRIMINATOR, BP_FEE_KEY, DEFAULT_MAX_M, DEFAULT_MAX_M_LP,
-    DEFAULT_PRICE_FACTOR_DENOMINATOR, DEFAULT_PRICE_FACTOR_NUMERATOR, MAX_AIRDROPPED_TOKENS,
-    MAX_MEME_TOKENS,
+    ANCHOR_DISCRIMINATOR, BP_FEE_KEY, CREATOR_COOLDOWN_SECS, DEFAULT_CREATOR_FEE_BPS, DEFAULT_MAX_M,
+    DEFAULT_MAX_M_LP, DEFAULT_MAX_PRICE_IMPACT_BPS, DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS,
+    DEFAULT_MIN_MEME_IN, DEFAULT_MIN_MEME_RESERVE, DEFAULT_MIN_QUOTE_IN,
+    DEFAULT_MIN_SLOT_BETWEEN_TRADES, DEFAULT_MIN_UNIQUE_BUYERS, DEFAULT_PRICE_FACTOR_DENOMINATOR,
+    DEFAULT_PRICE_FACTOR_NUMERATOR, DEFAULT_WALLET_COOLDOWN_SECS, MAX_AIRDROPPED_TOKENS,
+    MAX_BURN_UNSOLD_BPS, MAX_CREATOR_LOCKED_TOKENS, MAX_CREATOR_VESTING_PERIOD,
+    MAX_FIRST_BUYER_BONUS_BPS, MAX_LINEAR, MAX_MEME_TOKENS, MAX_QUOTE_DECIMALS, MIN_LINEAR,
 };
+use crate::endpoints::create_metadata::freeze_authority_allowed;
 use crate::err;
 use crate::err::AmmError;
-use crate::models::bound::{compute_alpha_abs, compute_beta, BoundPool, Config, Decimals};
+use crate::migration;
+use crate::models::bound::{
+    check_price_factor, compute_alpha_abs, compute_beta, compute_linear_alpha_abs, BoundPool,
+    Config, CurveType, Decimals, GraduationMode, MigrationTarget, ResidualFeeMode,
+};
+use crate::models::creator_lock::CreatorLock;
+use crate::models::creator_stats::CreatorStats;
 use crate::models::fees::FEE;
-use crate::models::fees::{Fees, MEME_FEE};
+use crate::models::fees::Fees;
+use crate::models::pool_registry::{PoolRegistry, PoolRegistryHead};
 use crate::models::target_config::TargetConfig;
 use crate::models::Reserve;
 use anchor_lang::prelude::*;
@@ -33,13 +45,26 @@ impl<'info> NewPool<'info> {
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Creates a CPI context for minting the creator's locked allocation to
+    /// its own vault, apart from the supply the curve and migration draw on.
+    fn mint_locked_tokens(&self) -> CpiContext<'_, '_, '_, 'info, token::MintTo<'info>> {
+        let cpi_accounts = token::MintTo {
+            mint: self.meme_mint.to_account_info(),
+            to: self.creator_lock_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
 }
 
 /// Handles the creation of a new pool.
 ///
 /// This function initializes a new pool by minting meme tokens, setting up the pool's configuration,
 /// and updating the pool's state. It ensures that the meme mint authority is the pool signer, and
-/// that the meme mint does not have a freeze authority. It also verifies that the quote vault is of
+/// that the meme mint's freeze authority is either absent or the pool signer. It also verifies that the quote vault is of
 /// the correct mint, owned by the pool signer, and does not have close or delegate authorities.
 /// Additionally, it checks the fee quote vault's mint, ownership, and authorities.
 ///
@@ -62,7 +87,35 @@ impl<'info> NewPool<'info> {
 /// # Parameters
 /// * `ctx` - The context containing all necessary accounts
 /// * `airdropped_tokens` - Amount of tokens for airdrop (max 100M)
-pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
+/// * `creator_locked_tokens` - Slice of the trading tokens locked for the creator (max 50M)
+/// * `creator_lock_period` - Seconds until the creator allocation can be claimed
+/// * `creator_vesting_period` - Seconds after the lock over which the allocation vests
+/// * `graduation_mode` - Threshold which allows the pool to migrate
+/// * `fee_meme_percent` - Fee on meme tokens in `FEE_PRECISION` units (max 5%)
+/// * `virtual_sol_reserves` - Unbacked SOL the curve starts from (below the target)
+/// * `virtual_meme_reserves` - Meme held back as already sold at the virtual SOL supply
+/// * `migration_target` - AMM the pool graduates into
+/// * `first_buyer_bonus_bps` - Extra meme for the first buyer, in bps of their buy
+/// * `burn_unsold_bps` - Share of the unsold curve meme burned at migration
+/// * `residual_fee_mode` - Where fees of trades after migration go
+/// * `curve_type` - Shape of the curve, a linear one needs virtual SOL
+#[allow(clippy::too_many_arguments)]
+pub fn handle(
+    ctx: Context<NewPool>,
+    airdropped_tokens: u64,
+    creator_locked_tokens: u64,
+    creator_lock_period: i64,
+    creator_vesting_period: i64,
+    graduation_mode: GraduationMode,
+    fee_meme_percent: u64,
+    virtual_sol_reserves: u64,
+    virtual_meme_reserves: u64,
+    migration_target: MigrationTarget,
+    first_buyer_bonus_bps: u16,
+    burn_unsold_bps: u16,
+    residual_fee_mode: ResidualFeeMode,
+    curve_type: CurveType,
+) -> Result<()> {
     let accs = ctx.accounts;
 
     // Step 1: Initial Checks
@@ -77,6 +130,49 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
         return Err(error!(AmmError::AirdroppedTokensOvercap));
     }
 
+    // Ensure the creator doesn't lock away too much of the trading supply
+    if creator_locked_tokens > MAX_CREATOR_LOCKED_TOKENS {
+        return Err(error!(AmmError::CreatorLockedTokensOvercap));
+    }
+
+    // The bonus is paid from the supply held off the curve, keep it modest
+    if first_buyer_bonus_bps > MAX_FIRST_BUYER_BONUS_BPS {
+        return Err(error!(AmmError::FirstBuyerBonusOvercap));
+    }
+
+    // Most of the unsold meme still has to seed the AMM
+    if burn_unsold_bps > MAX_BURN_UNSOLD_BPS {
+        return Err(error!(AmmError::BurnUnsoldOvercap));
+    }
+
+    // Creator lock must last between 1 and 13 days
+    if !(MIN_LINEAR..=MAX_LINEAR).contains(&creator_lock_period) {
+        return Err(error!(AmmError::InvalidVestingPeriod));
+    }
+
+    // Buyers' tokens are released at once, so any creator vesting outlasts theirs
+    if !(0..=MAX_CREATOR_VESTING_PERIOD).contains(&creator_vesting_period) {
+        return Err(error!(AmmError::InvalidVestingPeriod));
+    }
+
+    // The pool must be able to graduate where it's headed
+    migration::check_supported(migration_target)?;
+
+    // Creation doesn't need it, but a mint sorting after the quote mint will
+    // never migrate to Raydium, so tell the creator now rather than then
+    let mints_ordered =
+        migration::validate_mint_ordering(&accs.meme_mint.key(), &accs.quote_mint.key()).is_ok();
+    if migration_target == MigrationTarget::Raydium && !mints_ordered {
+        msg!("Warning: meme mint sorts after the quote mint, Raydium migration will fail");
+    }
+
+    // Keep a single creator from spam-launching pools
+    accs.creator_stats.record_creation(
+        accs.creator.key(),
+        Clock::get()?.unix_timestamp,
+        CREATOR_COOLDOWN_SECS,
+    )?;
+
     // Step 2: Minting Meme Tokens to the pool program
     // Prepare the seeds for the pool signer PDA
     let seeds = &[
@@ -87,12 +183,21 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
 
     let signer_seeds = &[&seeds[..]];
 
-    // Mint all 1B tokens to pool vault
+    // Mint all 1B tokens, the creator lock to its own vault and the rest
+    // to the pool vault
     token::mint_to(
         accs.mint_meme_tokens().with_signer(signer_seeds),
-        MAX_MEME_TOKENS as u64, // 1B total tokens
+        MAX_MEME_TOKENS as u64 - creator_locked_tokens, // 1B total tokens less the lock
     )
     .unwrap();
+    if creator_locked_tokens > 0 {
+        token::mint_to(
+            accs.mint_locked_tokens().with_signer(signer_seeds),
+            creator_locked_tokens,
+        )?;
+    }
+    accs.meme_vault.reload()?;
+    let meme_vault_amount = accs.meme_vault.amount;
 
     // Step 3: Configuring Pool Settings
     let pool = &mut accs.pool;
@@ -100,55 +205,76 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
     // Set up fee collection vault
     pool.fee_vault_quote = accs.fee_quote_vault.key();
 
-    // Initialize SOL reserve
+    // Initialize quote reserve in whichever mint the pool is quoted in
     pool.quote_reserve = Reserve {
-        tokens: 0,                     // Start with 0 SOL
-        mint: accs.quote_mint.key(),   // SOL mint address
-        vault: accs.quote_vault.key(), // SOL vault address
+        tokens: 0,                     // Start with 0 quote tokens
+        mint: accs.quote_mint.key(),   // Quote mint address (WSOL, USDC, ...)
+        vault: accs.quote_vault.key(), // Quote vault address
     };
 
     // Configure trading fees
     pool.fees = Fees {
-        fee_meme_percent: MEME_FEE,
+        fee_meme_percent,
         fee_quote_percent: FEE,
     };
+    pool.fees.check_meme_fee()?;
 
     // Step 4: Setting Up Price Mathematics
-    // Calculate SOL decimal precision (1B = 1 SOL)
+    // Refuse quote mints the curve math can't scale before running it
+    if accs.quote_mint.decimals > MAX_QUOTE_DECIMALS {
+        return Err(error!(AmmError::InvalidDecimalsConfig));
+    }
+
+    // Calculate quote decimal precision from the real mint (1B = 1 SOL, 1M = 1 USDC)
     let mint_decimals = 10_u128
         .checked_pow(accs.quote_mint.decimals as u32)
-        .unwrap();
+        .ok_or(error!(AmmError::DecimalsMismatch))?;
 
     // Configure bonding curve parameters
     let gamma_s = accs.target_config.token_target_amount as u128; // SOL target
-    let gamma_m = DEFAULT_MAX_M; // 690M trading tokens
+    let gamma_m = DEFAULT_MAX_M - creator_locked_tokens as u128; // 690M trading less the lock
     let omega_m = DEFAULT_MAX_M_LP; // 310M LP tokens
     let price_factor_num = DEFAULT_PRICE_FACTOR_NUMERATOR; // Price adjustment
     let price_factor_denom = DEFAULT_PRICE_FACTOR_DENOMINATOR; // factors
 
-    // Calculate price curve slope (α)
-    let (alpha_abs, decimals) = compute_alpha_abs(
-        gamma_s,
-        mint_decimals,
-        gamma_m,
-        omega_m,
-        price_factor_num,
-        price_factor_denom,
-    )?;
+    // Keep the price factor in a range the curve math can handle
+    check_price_factor(price_factor_num, price_factor_denom)?;
+
+    // Calculate price curve slope (α) and starting price (β), which a
+    // linear curve goes without
+    let (alpha_abs, decimals, beta) = match curve_type {
+        CurveType::PositiveIntercept => {
+            let (alpha_abs, decimals) = compute_alpha_abs(
+                gamma_s,
+                mint_decimals,
+                gamma_m,
+                omega_m,
+                price_factor_num,
+                price_factor_denom,
+            )?;
+            let beta = compute_beta(
+                gamma_s,
+                mint_decimals,
+                gamma_m,
+                omega_m,
+                price_factor_num,
+                price_factor_denom,
+                decimals,
+            )?;
+
+            (alpha_abs, decimals, beta)
+        }
+        CurveType::Linear => {
+            let (alpha_abs, decimals) = compute_linear_alpha_abs(gamma_s, mint_decimals, gamma_m)?;
+
+            (alpha_abs, decimals, 0)
+        }
+    };
 
     // Step 5: Finalizing Pool Configuration
     pool.config = Config {
-        alpha_abs, // Price curve slope (α)
-        beta: compute_beta(
-            // Starting price (β)
-            gamma_s,
-            mint_decimals,
-            gamma_m,
-            omega_m,
-            price_factor_num,
-            price_factor_denom,
-            decimals,
-        )?,
+        alpha_abs,               // Price curve slope (α)
+        beta,                    // Starting price (β)
         gamma_s: gamma_s as u64, // SOL target amount
         gamma_m: gamma_m as u64, // Trading token amount
         omega_m: omega_m as u64, // LP token amount
@@ -160,18 +286,74 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
             beta: decimals,              // For starting price
             quote: mint_decimals as u64, // For SOL
         },
+        min_slot_between_trades: DEFAULT_MIN_SLOT_BETWEEN_TRADES, // Anti-sandwich guard
+        creator_fee_bps: DEFAULT_CREATOR_FEE_BPS,                 // Creator's cut of quote fees
+        graduation_mode,                                          // Migration trigger
+        max_price_impact_bps: DEFAULT_MAX_PRICE_IMPACT_BPS,       // Per-swap price move cap
+        virtual_sol_reserves,                                     // Opening price offset
+        virtual_meme_reserves,                                    // Meme held off the curve
+        min_quote_in: DEFAULT_MIN_QUOTE_IN,                       // Dust buy floor
+        min_meme_in: DEFAULT_MIN_MEME_IN,                         // Dust sell floor
+        wallet_cooldown_secs: DEFAULT_WALLET_COOLDOWN_SECS,       // Per-wallet swap spacing
+        migration_target,                                         // AMM graduated into
+        migration_price_tolerance_bps: DEFAULT_MIGRATION_PRICE_TOLERANCE_BPS, // AMM price guard
+        first_buyer_bonus_bps,                                    // Extra meme for the first buyer
+        min_meme_reserve: DEFAULT_MIN_MEME_RESERVE,               // Sell floor
+        burn_unsold_bps,                                          // Unsold meme burned at migration
+        min_unique_buyers: DEFAULT_MIN_UNIQUE_BUYERS,             // Holders needed to migrate
+        residual_fee_mode,                                        // Post-migration fee destination
+        curve_type,                                               // Curve shape
     };
 
+    // The curve math relies on sane precisions matching the real mint
+    pool.config.decimals.validate()?;
+    pool.config
+        .decimals
+        .check_quote_mint(accs.quote_mint.decimals)?;
+
+    // The derived curve must sell the trading supply at the quote target
+    pool.check_curve_consistency()?;
+
     // Step 6: Setting Up Token Distribution
     // Configure token reserve
-    pool.meme_reserve.tokens = DEFAULT_MAX_M as u64; // 690M for trading
+    pool.meme_reserve.tokens = gamma_m as u64; // 690M for trading minus creator lock
     pool.meme_reserve.mint = accs.meme_mint.key(); // Token mint address
     pool.meme_reserve.vault = accs.meme_vault.key(); // Token vault address
 
+    // Sells measure what came off the curve against the trading supply
+    pool.check_meme_reserve()?;
+
+    // Nothing bought yet, and the vault backs the whole trading supply
+    pool.check_funded(meme_vault_amount)?;
+
+    // Virtual offsets must leave something to buy before the target
+    pool.check_virtual_reserves()?;
+
     // Final settings
     pool.locked = false; // Pool ready for trading
-    pool.creator_addr = accs.sender.key(); // Creator address
+    pool.creator_addr = accs.creator.key(); // Creator address, not the payer
     pool.airdropped_tokens = airdropped_tokens; // Set airdrop amount
+    pool.last_twap_slot = Clock::get()?.slot; // TWAP accumulates from launch
+    pool.first_buy_done = false; // Bonus waits for the first buyer
+    pool.pending_creator = Pubkey::default(); // No creator handoff pending
+    pool.staker_fees_quote = 0; // Nothing owed to stakers yet
+    pool.version = BoundPool::VERSION; // Current account layout
+
+    // Step 7: Locking the Creator Allocation
+    // Tokens stay in the lock vault, off the curve, until the lock expires
+    let creator_lock = &mut accs.creator_lock;
+    creator_lock.pool = accs.pool.key();
+    creator_lock.creator = accs.creator.key();
+    creator_lock.amount = creator_locked_tokens;
+    creator_lock.unlock_ts = Clock::get()?.unix_timestamp + creator_lock_period;
+    creator_lock.vesting_period = creator_vesting_period;
+    creator_lock.claimed_amount = 0;
+    creator_lock.claimed = false;
+
+    // Step 8: Listing the Pool
+    // Front-ends page through the registry instead of scanning all accounts
+    let pool_key = accs.pool.key();
+    accs.registry_head.register(&mut accs.registry_page, pool_key)?;
 
     Ok(())
 }
@@ -186,8 +368,12 @@ pub fn handle(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
 #[derive(Accounts)]
 pub struct NewPool<'info> {
     #[account(mut)]
-    /// The account of the sender initiating the pool creation.
+    /// The account of the sender initiating the pool creation. Pays for the
+    /// new accounts.
     pub sender: Signer<'info>,
+    /// The pool's creator, the only signer allowed into creator-gated
+    /// endpoints. May be the same wallet as `sender`.
+    pub creator: Signer<'info>,
     #[account(
         init,
         payer = sender,
@@ -197,12 +383,61 @@ pub struct NewPool<'info> {
     )]
     /// The account representing the pool being created.
     pub pool: Account<'info, BoundPool>,
+    #[account(
+        init,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + CreatorLock::INIT_SPACE,
+        seeds = [CreatorLock::CREATOR_LOCK_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    /// The account holding the creator's locked allocation.
+    pub creator_lock: Box<Account<'info, CreatorLock>>,
+    #[account(
+        init,
+        payer = sender,
+        token::mint = meme_mint,
+        token::authority = creator_lock,
+        seeds = [CreatorLock::VAULT_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    /// The vault the creator's locked allocation sits in until claimed.
+    pub creator_lock_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + CreatorStats::INIT_SPACE,
+        seeds = [CreatorStats::CREATOR_STATS_PREFIX, creator.key().as_ref()],
+        bump
+    )]
+    /// The creator's launch history, for the creation rate limit.
+    pub creator_stats: Box<Account<'info, CreatorStats>>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PoolRegistryHead::INIT_SPACE,
+        seeds = [PoolRegistryHead::REGISTRY_HEAD_PREFIX],
+        bump
+    )]
+    /// Count of registered pools, picking the registry page below.
+    pub registry_head: Box<Account<'info, PoolRegistryHead>>,
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + PoolRegistry::INIT_SPACE,
+        seeds = [
+            PoolRegistry::REGISTRY_PREFIX.as_ref(),
+            &registry_head.current_page().to_le_bytes(),
+        ],
+        bump
+    )]
+    /// The registry page the pool is listed on.
+    pub registry_page: Box<Account<'info, PoolRegistry>>,
     #[account(
         mut,
         constraint = meme_mint.mint_authority == COption::Some(pool_signer.key())
             @ err::acc("Meme mint authority must be the pool signer"),
-        constraint = meme_mint.freeze_authority == COption::None
-            @ err::acc("Meme mint mustn't have a freeze authority"),
+        constraint = freeze_authority_allowed(&meme_mint.freeze_authority, &pool_signer.key())
+            @ err::acc("Meme mint freeze authority must be the pool signer or none"),
     )]
     /// The account representing the meme mint.
     pub meme_mint: Account<'info, Mint>,
@@ -218,7 +453,8 @@ pub struct NewPool<'info> {
     )]
     /// The account representing the quote vault.
     pub quote_vault: Account<'info, TokenAccount>,
-    /// The account representing the quote mint.
+    /// The mint the pool is quoted in. Any SPL mint works; swaps are
+    /// checked against the mint stored in `pool.quote_reserve`.
     pub quote_mint: Account<'info, Mint>,
     #[account(
         constraint = fee_quote_vault.mint == quote_mint.key()
@@ -0,0 +1,52 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, POINTS_MINT, POINTS_PDA};
+use crate::models::points_escrow::PointsEscrow;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{Mint, Token, TokenAccount};
+
+/// Opens the signer's points escrow, which its referral points vest in
+/// while the points epoch has a `points_vesting_secs`. Creates the shared
+/// escrow vault on first use.
+pub fn handle(ctx: Context<OpenPointsEscrow>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.points_escrow.owner = accs.owner.key();
+
+    msg!("points_escrow_opened: {}", accs.owner.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenPointsEscrow<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + PointsEscrow::INIT_SPACE,
+        seeds = [PointsEscrow::POINTS_ESCROW_PREFIX, owner.key().as_ref()],
+        bump
+    )]
+    pub points_escrow: Account<'info, PointsEscrow>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        token::mint = points_mint,
+        token::authority = points_pda,
+        seeds = [PointsEscrow::VAULT_PREFIX],
+        bump
+    )]
+    pub points_escrow_vault: Account<'info, TokenAccount>,
+
+    #[account(constraint = points_mint.key() == POINTS_MINT.key())]
+    pub points_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA owning the points pool and the escrow vault
+    #[account(seeds = [POINTS_PDA], bump)]
+    pub points_pda: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
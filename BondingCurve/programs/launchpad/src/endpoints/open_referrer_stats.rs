@@ -0,0 +1,32 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::models::referrer_stats::ReferrerStats;
+use anchor_lang::prelude::*;
+
+/// Opens the signer's referrer stats, which buys passing them alongside the
+/// signer's `referrer_points` accumulate into.
+pub fn handle(ctx: Context<OpenReferrerStats>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.referrer_stats.referrer = accs.referrer.key();
+
+    msg!("referrer_stats_opened: {}", accs.referrer.key());
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct OpenReferrerStats<'info> {
+    #[account(mut)]
+    pub referrer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = referrer,
+        space = ANCHOR_DISCRIMINATOR + ReferrerStats::INIT_SPACE,
+        seeds = [ReferrerStats::REFERRER_STATS_PREFIX, referrer.key().as_ref()],
+        bump
+    )]
+    pub referrer_stats: Account<'info, ReferrerStats>,
+
+    pub system_program: Program<'info, System>,
+}
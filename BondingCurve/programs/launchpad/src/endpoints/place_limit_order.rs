@@ -0,0 +1,105 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::limit_order::{LimitDirection, LimitOrder};
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+
+/// Places a buy filled by `execute_limit_order` once the spot price crosses
+/// `trigger_price`. The quote tokens move to the order's escrow right away.
+///
+/// # Arguments
+/// * `ctx` - The context containing the order and escrow accounts
+/// * `order_id` - Distinguishes the owner's orders on the pool
+/// * `trigger_price` - Spot price to wait for and worst average fill price, in
+///   `BoundPool::spot_price` units
+/// * `quote_amount` - Quote tokens to spend, fees included
+/// * `direction` - Side of the trigger which fills the order
+pub fn handle(
+    ctx: Context<PlaceLimitOrder>,
+    order_id: u64,
+    trigger_price: u64,
+    quote_amount: u64,
+    direction: LimitDirection,
+) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if quote_amount == 0 || trigger_price == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Orders on a pool which can't trade, or migrated, would never fill
+    accs.pool.check_tradeable()?;
+    accs.pool.check_not_migrated()?;
+
+    token::transfer(
+        CpiContext::new(
+            accs.token_program.to_account_info(),
+            Transfer {
+                from: accs.user_sol.to_account_info(),
+                to: accs.escrow.to_account_info(),
+                authority: accs.owner.to_account_info(),
+            },
+        ),
+        quote_amount,
+    )?;
+
+    let order = &mut accs.limit_order;
+    order.pool = accs.pool.key();
+    order.owner = accs.owner.key();
+    order.order_id = order_id;
+    order.trigger_price = trigger_price;
+    order.quote_amount = quote_amount;
+    order.direction = direction;
+
+    msg!("limit_order_placed: {} {}", order_id, quote_amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct PlaceLimitOrder<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + LimitOrder::INIT_SPACE,
+        seeds = [
+            LimitOrder::LIMIT_ORDER_PREFIX.as_ref(),
+            pool.key().as_ref(),
+            owner.key().as_ref(),
+            &order_id.to_le_bytes(),
+        ],
+        bump
+    )]
+    pub limit_order: Box<Account<'info, LimitOrder>>,
+
+    /// Holds the order's quote tokens until it fills or is cancelled
+    #[account(
+        init,
+        payer = owner,
+        token::mint = quote_mint,
+        token::authority = limit_order,
+        seeds = [LimitOrder::ESCROW_PREFIX.as_ref(), limit_order.key().as_ref()],
+        bump
+    )]
+    pub escrow: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = quote_mint.key() == pool.quote_reserve.mint @ AmmError::InvalidTokenMints)]
+    pub quote_mint: Account<'info, Mint>,
+
+    /// The owner's quote token account funding the escrow
+    #[account(
+        mut,
+        constraint = user_sol.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints
+    )]
+    pub user_sol: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
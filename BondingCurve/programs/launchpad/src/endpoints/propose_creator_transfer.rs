@@ -0,0 +1,25 @@
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+
+/// Proposes `new_creator` to take over the pool. The creator only changes
+/// once that wallet signs `accept_creator_transfer`; proposing the default
+/// key withdraws the proposal.
+pub fn handle(ctx: Context<ProposeCreatorTransfer>, new_creator: Pubkey) -> Result<()> {
+    ctx.accounts.pool.propose_creator(new_creator);
+
+    msg!("creator_transfer_proposed: {}", new_creator);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ProposeCreatorTransfer<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = pool.is_creator(&creator.key()) @ AmmError::InvalidAccountInput
+    )]
+    pub pool: Account<'info, BoundPool>,
+}
@@ -0,0 +1,26 @@
+use crate::models::pool_registry::PoolRegistry;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the pools listed on one registry page, in creation order, as a
+/// serialized `Vec<Pubkey>` via return data.
+///
+/// # Arguments
+/// * `ctx` - The context containing the registry page
+/// * `page` - Index of the page to read
+pub fn handle(ctx: Context<ReadPoolRegistry>, page: u32) -> Result<()> {
+    let pools = &ctx.accounts.registry_page.pools;
+
+    msg!("registry_page: {} pools: {}", page, pools.len());
+
+    set_return_data(&pools.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct ReadPoolRegistry<'info> {
+    #[account(seeds = [PoolRegistry::REGISTRY_PREFIX.as_ref(), &page.to_le_bytes()], bump)]
+    pub registry_page: Box<Account<'info, PoolRegistry>>,
+}
@@ -0,0 +1,23 @@
+use crate::models::referrer_stats::ReferrerStats;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+pub fn handle(ctx: Context<ReadReferrerStats>) -> Result<()> {
+    let stats = &ctx.accounts.referrer_stats;
+
+    msg!(
+        "referrer: {}\n total_points_earned: {}\n referral_count: {}",
+        stats.referrer,
+        stats.total_points_earned,
+        stats.referral_count
+    );
+
+    set_return_data(&stats.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReadReferrerStats<'info> {
+    pub referrer_stats: Account<'info, ReferrerStats>,
+}
@@ -0,0 +1,24 @@
+use crate::models::bound::BoundPool;
+use crate::models::price_snapshot::{PriceSnapshot, PriceSnapshots};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Returns the pool's recorded price snapshots, oldest first, as a serialized
+/// `Vec<PriceSnapshot>` via return data.
+pub fn handle(ctx: Context<ReadSnapshots>) -> Result<()> {
+    let snapshots: Vec<PriceSnapshot> = ctx.accounts.price_snapshots.ordered();
+
+    msg!("snapshots: {}", snapshots.len());
+
+    set_return_data(&snapshots.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReadSnapshots<'info> {
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(seeds = [PriceSnapshots::PRICE_SNAPSHOTS_PREFIX, pool.key().as_ref()], bump)]
+    pub price_snapshots: Box<Account<'info, PriceSnapshots>>,
+}
@@ -0,0 +1,40 @@
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// Result of `read_twap`, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct TwapReading {
+    /// Average spot price over the window, scaled like `spot_price`
+    pub average_price: u64,
+    /// Accumulator value at `slot`, the start of the caller's next window
+    pub price_cumulative: u128,
+    /// Slot the reading was taken at
+    pub slot: u64,
+}
+
+pub fn handle(ctx: Context<ReadTwap>, start_cumulative: u128, start_slot: u64) -> Result<()> {
+    let pool = &ctx.accounts.pool;
+    let slot = Clock::get()?.slot;
+
+    let reading = TwapReading {
+        average_price: pool.read_twap(start_cumulative, start_slot, slot)?,
+        price_cumulative: pool.cumulative_price_at(slot)?,
+        slot,
+    };
+
+    msg!(
+        "twap: {}\n price_cumulative: {}",
+        reading.average_price,
+        reading.price_cumulative
+    );
+
+    set_return_data(&reading.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReadTwap<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
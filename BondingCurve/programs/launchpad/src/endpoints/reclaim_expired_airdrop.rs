@@ -0,0 +1,76 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::global_config::GlobalConfig;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> ReclaimExpiredAirdrop<'info> {
+    /// Creates a CPI context for moving the unclaimed airdrop tokens back
+    /// into the staking vault.
+    fn return_to_staking(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.airdrop_token_vault.to_account_info(),
+            to: self.staking_meme_vault.to_account_info(),
+            authority: self.sender.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Sweeps whatever is left in the airdrop distributor's vault back into the
+/// staking vault once the airdrop window expired. The tokens rejoin
+/// `to_airdrop` for the next airdrop.
+///
+/// # Errors
+/// * `AmmError::AirdropNotExpired` - If the window has no expiry or hasn't expired yet
+/// * `AmmError::NoZeroTokens` - If nothing is left to reclaim
+pub fn handle(ctx: Context<ReclaimExpiredAirdrop>) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let amount = accs.airdrop_token_vault.amount;
+
+    accs.staking
+        .reclaim_airdrop(amount, Clock::get()?.unix_timestamp)?;
+
+    token::transfer(accs.return_to_staking(), amount)?;
+
+    msg!("airdrop_reclaimed: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct ReclaimExpiredAirdrop<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can reclaim airdrops")
+    )]
+    pub sender: Signer<'info>,
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut)]
+    pub staking: Box<Account<'info, StakingPool>>,
+    /// Staking Pool Signer
+    /// CHECK: live phase pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+    #[account(
+        mut,
+        constraint = staking_meme_vault.owner == staking_pool_signer_pda.key()
+            @ err::acc("Staking vault must belong to the staking pool signer")
+    )]
+    pub staking_meme_vault: Box<Account<'info, TokenAccount>>,
+    /// The distributor's vault `send_airdrop_funds` paid the airdrop into
+    #[account(
+        mut,
+        constraint = airdrop_token_vault.mint == staking_meme_vault.mint
+            @ AmmError::InvalidTokenMints,
+        constraint = airdrop_token_vault.owner == sender.key()
+    )]
+    pub airdrop_token_vault: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,82 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+/// Moves tokens sent by mistake to a token account of the pool signer out to
+/// `destination`. The pool's own reserve vaults can never be drained this way.
+///
+/// # Arguments
+/// * `ctx` - The context containing the stuck vault and its destination
+/// * `amount` - Tokens to recover
+pub fn handle(ctx: Context<RecoverStuckTokens>, amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    token::transfer(
+        CpiContext::new(
+            accs.token_program.to_account_info(),
+            Transfer {
+                from: accs.stuck_vault.to_account_info(),
+                to: accs.destination.to_account_info(),
+                authority: accs.pool_signer.to_account_info(),
+            },
+        )
+        .with_signer(&[&seeds[..]]),
+        amount,
+    )?;
+
+    msg!(
+        "stuck_tokens_recovered: {} {}",
+        accs.stuck_vault.mint,
+        amount
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RecoverStuckTokens<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can recover stuck tokens")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    pub pool: Account<'info, BoundPool>,
+
+    /// CHECK: PDA signer for the pool - seeds validation ensures this is the correct pool authority
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+
+    /// A token account of the pool signer holding tokens sent by mistake
+    #[account(
+        mut,
+        constraint = !pool.is_reserve_vault(&stuck_vault.key())
+            @ AmmError::ReserveVaultNotRecoverable,
+        constraint = stuck_vault.owner == pool_signer.key()
+    )]
+    pub stuck_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = destination.mint == stuck_vault.mint @ AmmError::InvalidTokenMints
+    )]
+    pub destination: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
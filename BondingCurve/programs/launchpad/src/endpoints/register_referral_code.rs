@@ -0,0 +1,47 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::models::referral_code::ReferralCode;
+use anchor_lang::prelude::*;
+
+/// Registers `code` as the signer's referral code. Buys passing the code
+/// account in pay their referral points to the signer. Fails if the code, in
+/// any casing, is already taken.
+///
+/// # Arguments
+/// * `code` - Up to 16 printable ASCII characters
+///
+/// # Errors
+/// * `AmmError::InvalidReferralCode` - If the code is empty, too long or not printable ASCII
+pub fn handle(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+    ReferralCode::validate_code(&code)?;
+
+    let accs = ctx.accounts;
+
+    accs.referral_code.owner = accs.owner.key();
+    accs.referral_code.code = code;
+
+    msg!(
+        "referral_code_registered: {} -> {}",
+        accs.referral_code.code,
+        accs.owner.key()
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(code: String)]
+pub struct RegisterReferralCode<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(
+        init,
+        payer = owner,
+        space = ANCHOR_DISCRIMINATOR + ReferralCode::INIT_SPACE,
+        seeds = [ReferralCode::REFERRAL_CODE_PREFIX.as_ref(), &ReferralCode::code_seed(&code)],
+        bump
+    )]
+    pub referral_code: Account<'info, ReferralCode>,
+
+    pub system_program: Program<'info, System>,
+}
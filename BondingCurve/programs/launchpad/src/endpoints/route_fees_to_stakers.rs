@@ -0,0 +1,95 @@
+use crate::consts::BP_FEE_KEY;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> RouteFeesToStakers<'info> {
+    fn send_quote_fees(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: self.staking_quote_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Moves quote fees to the staking pool, where they are shared among stakers
+/// by stake. Fees set aside for stakers after migration go first, the rest
+/// comes out of the accrued admin quote fees.
+///
+/// # Arguments
+/// * `quote_amount` - Quote fees to route, at most `staker_fees_quote + admin_fees_quote`
+///
+/// # Errors
+/// * `AmmError::NoZeroTokens` - If routing 0 tokens
+/// * `AmmError::InsufficientAccruedFees` - If the amount exceeds what has accrued
+/// * `AmmError::NoStakers` - If nothing is staked to receive the rewards
+pub fn handle(ctx: Context<RouteFeesToStakers>, quote_amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if quote_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    accs.pool.take_fees_for_stakers(quote_amount)?;
+    accs.staking.add_rewards(quote_amount)?;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    token::transfer(
+        accs.send_quote_fees().with_signer(signer_seeds),
+        quote_amount,
+    )?;
+
+    msg!("routed_quote_fees: {}", quote_amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct RouteFeesToStakers<'info> {
+    #[account(constraint = admin.key() == BP_FEE_KEY @ err::acc("Only the fee key can route fees"))]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub staking: Box<Account<'info, StakingPool>>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = staking_quote_vault.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = staking_quote_vault.owner == staking_pool_signer_pda.key()
+            @ AmmError::InvalidAccountInput
+    )]
+    pub staking_quote_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
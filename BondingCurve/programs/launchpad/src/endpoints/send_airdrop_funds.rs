@@ -1,5 +1,5 @@
-use crate::consts::SWAP_AUTH_KEY;
 use crate::err;
+use crate::models::global_config::GlobalConfig;
 use crate::models::staking::StakingPool;
 use anchor_lang::context::{Context, CpiContext};
 use anchor_lang::prelude::*;
@@ -31,12 +31,15 @@ pub fn handle(ctx: Context<SendAirdropFunds>) -> Result<()> {
 
     let staking_signer_seeds = &[&staking_seeds[..]];
 
-    accs.staking.to_airdrop = 0;
+    // Airdrops only go out while the claim window is open
+    accs.staking.check_airdrop_window(Clock::get()?.unix_timestamp)?;
+
+    let amount = std::mem::take(&mut accs.staking.to_airdrop);
 
     token::transfer(
         accs.transfer_airdrop_meme_ctx()
             .with_signer(staking_signer_seeds),
-        accs.staking.to_airdrop,
+        amount,
     )
     .unwrap();
 
@@ -68,9 +71,14 @@ pub struct SendAirdropFunds<'info> {
         associated_token::authority = airdrop_owner
     )]
     pub airdrop_token_vault: Box<Account<'info, TokenAccount>>,
-    #[account(constraint = airdrop_owner.key() == SWAP_AUTH_KEY)]
+    #[account(
+        constraint = global_config.is_admin(&airdrop_owner.key())
+            @ err::acc("Only the admin can receive airdrop funds")
+    )]
     /// CHECK: constraint
     pub airdrop_owner: AccountInfo<'info>,
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
 
     pub system_program: Program<'info, System>,
     pub token_program: Program<'info, Token>,
@@ -95,7 +103,11 @@ mod tests {
     fn create_test_staking_pool(to_airdrop: u64) -> StakingPool {
         StakingPool {
             to_airdrop,
-            padding: [0; 32],
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            airdrop_start_ts: 0,
+            airdrop_end_ts: 0,
+            version: StakingPool::VERSION,
         }
     }
 
@@ -122,12 +134,23 @@ mod tests {
 
     #[test]
     fn test_airdrop_owner_authority_validation() {
-        let correct_airdrop_owner = SWAP_AUTH_KEY;
-        let incorrect_airdrop_owner = Pubkey::new_unique();
+        // The global config starts out with the swap authority as admin
+        let mut config = GlobalConfig {
+            admin: SWAP_AUTH_KEY,
+            pending_admin: Pubkey::default(),
+        };
+        let next_admin = Pubkey::new_unique();
+
+        // Airdrop owner must be the current admin
+        assert!(config.is_admin(&SWAP_AUTH_KEY));
+        assert!(!config.is_admin(&Pubkey::new_unique()));
 
-        // Airdrop owner must match SWAP_AUTH_KEY
-        assert_eq!(correct_airdrop_owner, SWAP_AUTH_KEY);
-        assert_ne!(incorrect_airdrop_owner, SWAP_AUTH_KEY);
+        // After an admin transfer the airdrop funds follow the new admin, and
+        // the hard-coded swap authority loses them
+        config.propose_admin(&SWAP_AUTH_KEY, next_admin).unwrap();
+        config.accept_admin(&next_admin).unwrap();
+        assert!(config.is_admin(&next_admin));
+        assert!(!config.is_admin(&SWAP_AUTH_KEY));
 
         println!("✅ Airdrop owner authority validation test passed!");
     }
@@ -291,9 +314,9 @@ mod test_utils_airdrop {
 
     /// Validate airdrop pool state
     pub fn validate_airdrop_pool_state(pool: &StakingPool) -> bool {
-        // Pool should have valid padding and reasonable to_airdrop amount
+        // Pool should be on the current layout with a reasonable to_airdrop amount
         pool.to_airdrop <= 100_000_000_000_000 && // Max 100M tokens (from MAX_AIRDROPPED_TOKENS)
-        pool.padding.len() == 32
+        pool.version == StakingPool::VERSION
     }
 
     /// Simulate airdrop completion
@@ -320,12 +343,20 @@ mod test_utils_airdrop {
     fn test_airdrop_pool_validation() {
         let valid_pool = StakingPool {
             to_airdrop: 50_000_000, // 50M tokens (valid)
-            padding: [0; 32],
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            airdrop_start_ts: 0,
+            airdrop_end_ts: 0,
+            version: StakingPool::VERSION,
         };
 
         let invalid_pool = StakingPool {
             to_airdrop: 200_000_000_000_000, // 200M tokens (exceeds max)
-            padding: [0; 32],
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            airdrop_start_ts: 0,
+            airdrop_end_ts: 0,
+            version: StakingPool::VERSION,
         };
 
         assert!(validate_airdrop_pool_state(&valid_pool));
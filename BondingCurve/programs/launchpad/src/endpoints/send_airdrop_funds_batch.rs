@@ -0,0 +1,103 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::global_config::GlobalConfig;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token;
+use anchor_spl::token::{Mint, Token, TokenAccount, Transfer};
+
+/// Sends airdrops to several recipients at once. The recipients' meme token
+/// accounts are passed as remaining accounts, in the order of `recipients`.
+///
+/// # Arguments
+/// * `recipients` - Wallets receiving an airdrop, at most `MAX_AIRDROP_RECIPIENTS`
+/// * `amounts` - Meme tokens sent to each recipient
+///
+/// # Errors
+/// * `AmmError::AirdropExceedsAllocation` - If the amounts add up to more than `to_airdrop`
+/// * `AmmError::AirdropWindowClosed` - If called outside the airdrop window
+pub fn handle<'info>(
+    ctx: Context<'_, '_, 'info, 'info, SendAirdropFundsBatch<'info>>,
+    recipients: Vec<Pubkey>,
+    amounts: Vec<u64>,
+) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if recipients.len() != amounts.len() || ctx.remaining_accounts.len() != recipients.len() {
+        return Err(error!(AmmError::InvalidArg));
+    }
+
+    // Airdrops only go out while the claim window is open
+    accs.staking.check_airdrop_window(Clock::get()?.unix_timestamp)?;
+
+    let total = accs.staking.take_airdrop(&amounts)?;
+
+    let staking_key = accs.staking.key();
+    let staking_seeds = &[
+        StakingPool::SIGNER_PDA_PREFIX,
+        staking_key.as_ref(),
+        &[ctx.bumps.staking_pool_signer_pda],
+    ];
+    let staking_signer_seeds = &[&staking_seeds[..]];
+
+    for ((recipient, amount), vault_info) in
+        recipients.iter().zip(amounts).zip(ctx.remaining_accounts)
+    {
+        let vault = Account::<TokenAccount>::try_from(vault_info)?;
+
+        if vault.mint != accs.meme_mint.key() || vault.owner != *recipient {
+            return Err(
+                err::acc("Airdrop vault must be the recipient's meme token account").into(),
+            );
+        }
+
+        let cpi_accounts = Transfer {
+            from: accs.staking_meme_vault.to_account_info(),
+            to: vault_info.clone(),
+            authority: accs.staking_pool_signer_pda.to_account_info(),
+        };
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.to_account_info(),
+                cpi_accounts,
+                staking_signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    msg!(
+        "airdrop_recipients: {}\n airdropped: {}",
+        recipients.len(),
+        total
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SendAirdropFundsBatch<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can send airdrops")
+    )]
+    pub sender: Signer<'info>,
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+    #[account(mut, constraint = staking.to_airdrop != 0)]
+    pub staking: Box<Account<'info, StakingPool>>,
+    /// Staking Pool Signer
+    /// CHECK: live phase pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+    #[account(mut)]
+    pub staking_meme_vault: Box<Account<'info, TokenAccount>>,
+    #[account(
+        constraint = meme_mint.key() == staking_meme_vault.mint
+            @ err::acc("Invalid meme mint")
+    )]
+    pub meme_mint: Box<Account<'info, Mint>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,44 @@
+use crate::consts::ANCHOR_DISCRIMINATOR;
+use crate::err;
+use crate::models::creator_stats::CreatorStats;
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Lifts or restores the launch rate limit for `creator`.
+pub fn handle(ctx: Context<SetCreatorExemption>, exempt: bool) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.creator_stats.creator = accs.creator.key();
+    accs.creator_stats.exempt = exempt;
+
+    msg!("creator_exemption: {} {}", accs.creator.key(), exempt);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SetCreatorExemption<'info> {
+    #[account(
+        mut,
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can exempt creators")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    /// CHECK: Any wallet can be exempted, only its key is stored
+    pub creator: AccountInfo<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = sender,
+        space = ANCHOR_DISCRIMINATOR + CreatorStats::INIT_SPACE,
+        seeds = [CreatorStats::CREATOR_STATS_PREFIX, creator.key().as_ref()],
+        bump
+    )]
+    pub creator_stats: Account<'info, CreatorStats>,
+
+    pub system_program: Program<'info, System>,
+}
@@ -0,0 +1,155 @@
+use crate::consts::DECIMALS_S;
+use crate::endpoints::migrate_to_raydium::{
+    apply_migration_fee, calculate_burn_amount, calculate_migration_amounts,
+    migration_threshold_reached,
+};
+use crate::err::AmmError;
+use crate::libraries::MulDiv;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
+
+/// What `migrate_to_raydium` would do if called now, returned by `simulate_migration`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]
+pub struct MigrationPreview {
+    /// Whether the migration threshold is met
+    pub threshold_reached: bool,
+    /// Meme tokens seeding the AMM
+    pub meme_amount: u64,
+    /// Quote tokens seeding the AMM
+    pub quote_amount: u64,
+    /// Quote tokens skimmed to the protocol fee vault
+    pub migration_fee: u64,
+    /// Unsold meme tokens burned instead of seeding the AMM
+    pub meme_burned: u64,
+    /// Meme tokens left on the curve afterwards
+    pub remaining_meme: u64,
+    /// Quote tokens left on the curve afterwards
+    pub remaining_quote: u64,
+    /// Initial AMM price in quote units per meme unit, scaled by 1e9
+    pub amm_price: u64,
+}
+
+/// Computes the migration split the same way `migrate_to_raydium` does. What
+/// stays on the curve comes off the reserves, as the migration books it; the
+/// vaults also hold fees and the LP supply.
+pub fn preview_migration(pool: &BoundPool) -> Result<MigrationPreview> {
+    let meme_burned = calculate_burn_amount(pool)?;
+    let (meme_amount, quote_amount) = calculate_migration_amounts(pool)?;
+    let (meme_amount, quote_amount, migration_fee) =
+        apply_migration_fee(meme_amount, quote_amount)?;
+
+    let amm_price = if meme_amount == 0 {
+        0
+    } else {
+        quote_amount
+            .mul_div_floor(DECIMALS_S as u64, meme_amount)
+            .ok_or(error!(AmmError::MathOverflow))?
+    };
+
+    Ok(MigrationPreview {
+        threshold_reached: migration_threshold_reached(pool)?,
+        meme_amount,
+        quote_amount,
+        migration_fee,
+        meme_burned,
+        remaining_meme: pool
+            .meme_reserve
+            .tokens
+            .checked_sub(meme_amount + meme_burned)
+            .ok_or(error!(AmmError::MathOverflow))?,
+        remaining_quote: pool
+            .quote_reserve
+            .tokens
+            .checked_sub(quote_amount + migration_fee)
+            .ok_or(error!(AmmError::MathOverflow))?,
+        amm_price,
+    })
+}
+
+pub fn handle(ctx: Context<SimulateMigration>) -> Result<()> {
+    let preview = preview_migration(&ctx.accounts.pool)?;
+
+    msg!(
+        "migration_meme: {}\n migration_quote: {}\n amm_price: {}",
+        preview.meme_amount,
+        preview.quote_amount,
+        preview.amm_price
+    );
+
+    set_return_data(&preview.try_to_vec()?);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct SimulateMigration<'info> {
+    pub pool: Account<'info, BoundPool>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::migrate_to_raydium::{
+        calculate_migration_threshold, debit_migrated_reserves, plan_migration,
+    };
+    use crate::models::bound::test_pool;
+
+    // Books a buy of `sol_in` on the pool the way swap_y does
+    fn buy(pool: &mut BoundPool, sol_in: u64) {
+        let swap = pool.swap_amounts(sol_in, 0, true).unwrap();
+        pool.admin_fees_quote += swap.admin_fee_in;
+        pool.quote_reserve.tokens += swap.amount_in;
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+    }
+
+    #[test]
+    fn test_preview_at_threshold() {
+        // ARRANGE: Buy through the curve in 5 SOL steps until it can graduate,
+        // keeping the state one buy short
+        let mut pool = test_pool();
+        let threshold = calculate_migration_threshold(&pool).unwrap();
+        let mut short = pool.clone();
+        while pool.meme_sold().unwrap() < threshold {
+            short = pool.clone();
+            buy(&mut pool, 5_000_000_000);
+        }
+
+        println!("🧪 Testing migration preview at the threshold");
+
+        // ACT: Preview and round-trip through return data encoding
+        let preview = preview_migration(&pool).unwrap();
+        let decoded = MigrationPreview::try_from_slice(&preview.try_to_vec().unwrap()).unwrap();
+
+        // ASSERT: Split matches the migration itself
+        let (meme_amount, quote_amount, migration_fee) = plan_migration(&pool).unwrap();
+        let meme_burned = calculate_burn_amount(&pool).unwrap();
+        assert_eq!(decoded, preview);
+        assert!(decoded.threshold_reached);
+        assert_eq!(decoded.meme_amount, meme_amount);
+        assert_eq!(decoded.quote_amount, quote_amount);
+        assert_eq!(decoded.migration_fee, migration_fee);
+        assert_eq!(decoded.meme_burned, meme_burned);
+        assert_eq!(
+            decoded.amm_price,
+            (quote_amount as u128 * DECIMALS_S / meme_amount as u128) as u64
+        );
+
+        // What's left is what the migration leaves on the reserves
+        let mut migrated = pool.clone();
+        debit_migrated_reserves(
+            &mut migrated,
+            meme_amount + meme_burned,
+            quote_amount + migration_fee,
+        )
+        .unwrap();
+        assert_eq!(decoded.remaining_meme, migrated.meme_reserve.tokens);
+        assert_eq!(decoded.remaining_quote, migrated.quote_reserve.tokens);
+        assert!(decoded.remaining_meme > 0 && decoded.remaining_quote > 0);
+
+        // One buy earlier the threshold isn't reached yet
+        assert!(!preview_migration(&short).unwrap().threshold_reached);
+
+        println!("✅ Migration preview test passed!");
+    }
+}
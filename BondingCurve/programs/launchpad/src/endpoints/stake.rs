@@ -0,0 +1,83 @@
+use crate::err::AmmError;
+use crate::models::stake_account::StakeAccount;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> Stake<'info> {
+    /// Creates a CPI context for moving the staker's meme tokens into the
+    /// staking vault.
+    fn send_meme_to_vault(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.user_meme.to_account_info(),
+            to: self.staking_meme_vault.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Deposits `amount` meme tokens into the staking pool.
+///
+/// # Errors
+/// * `AmmError::NoZeroTokens` - If staking 0 tokens
+/// * `AmmError::InsufficientBalance` - If the wallet holds fewer tokens
+pub fn handle(ctx: Context<Stake>, amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if amount > accs.user_meme.amount {
+        return Err(error!(AmmError::InsufficientBalance));
+    }
+
+    accs.stake_account.staking = accs.staking.key();
+    accs.stake_account.owner = accs.owner.key();
+    let now = Clock::get()?.unix_timestamp;
+    accs.staking.stake(&mut accs.stake_account, amount, now)?;
+
+    token::transfer(accs.send_meme_to_vault(), amount)?;
+
+    msg!("staked: {}", amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Stake<'info> {
+    #[account(mut)]
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub staking: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + StakeAccount::INIT_SPACE,
+        seeds = [StakeAccount::STAKE_ACCOUNT_PREFIX, staking.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = staking_meme_vault.owner == staking_pool_signer_pda.key()
+            @ AmmError::InvalidAccountInput
+    )]
+    pub staking_meme_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_meme.mint == staking_meme_vault.mint @ AmmError::InvalidTokenMints,
+        constraint = user_meme.owner == owner.key()
+    )]
+    pub user_meme: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
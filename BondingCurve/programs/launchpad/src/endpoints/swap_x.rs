@@ -1,7 +1,16 @@
+use crate::consts::{POINTS_EPOCH_SEED, POINTS_MINT};
+use crate::endpoints::swap_y::{check_max_coin_in, get_swap_points, SwapEvent};
 use crate::err::AmmError;
 use crate::models::bound::BoundPool;
+use crate::models::fee_exemption::FeeExemption;
+use crate::models::points_epoch::PointsEpoch;
+use crate::models::price_snapshot::{PriceSnapshot, PriceSnapshots};
+use crate::models::wallet_activity::WalletActivity;
+use crate::models::SwapAmount;
 use anchor_lang::prelude::*;
-use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, spl_token, Burn, CloseAccount, Mint, Token, TokenAccount, Transfer};
+use std::borrow::Cow;
+use std::cmp::min;
 
 impl<'info> SwapCoinX<'info> {
     /// Creates a CPI context for transferring meme tokens from user to pool
@@ -39,6 +48,62 @@ impl<'info> SwapCoinX<'info> {
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    /// Creates a CPI context for closing the user's WSOL account
+    ///
+    /// Closing a native account hands its wrapped SOL and rent to the owner
+    /// as plain lamports.
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the close account CPI
+    fn close_user_sol(&self) -> CpiContext<'_, '_, '_, 'info, CloseAccount<'info>> {
+        let cpi_accounts = CloseAccount {
+            account: self.user_sol.to_account_info(),
+            destination: self.owner.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for burning points from the user's points account
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the token burn CPI
+    fn burn_user_points(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.points_mint.to_account_info(),
+            from: self.user_points.to_account_info(),
+            authority: self.owner.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Creates a CPI context for burning meme fees in the pool's vault
+    ///
+    /// # Returns
+    /// * `CpiContext` - The context for the token burn CPI
+    fn burn_meme_fees(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: self.meme_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    /// Pool to quote the sell against, fee free when the owner is exempt
+    pub fn quoting_pool(&self) -> Cow<'_, BoundPool> {
+        match self.fee_exemption {
+            Some(_) => Cow::Owned(self.pool.without_fees()),
+            None => Cow::Borrowed(&self.pool),
+        }
+    }
 }
 
 /// Handles the swap of meme tokens for SOL with direct transfer
@@ -51,57 +116,171 @@ impl<'info> SwapCoinX<'info> {
 /// * `ctx` - The context containing all required accounts
 /// * `coin_in_amount` - The amount of meme tokens to swap
 /// * `coin_y_min_value` - The minimum amount of SOL to receive (slippage protection)
+/// * `unwrap_sol` - Close `user_sol` afterwards so the SOL arrives as native lamports
+/// * `max_coin_in_amount` - Most meme tokens the caller meant to sell (0 = no limit)
 ///
 /// # Returns
 /// * `Result<()>` - Result indicating success or containing error
 ///
 /// # Errors
 /// * `AmmError::NoZeroTokens` - If attempting to swap 0 tokens
-/// * `AmmError::PoolIsLocked` - If the pool is currently locked
-pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u64) -> Result<()> {
-    let accs = ctx.accounts;
+/// * `AmmError::AmountExceedsMax` - If selling more than `max_coin_in_amount`
+/// * `AmmError::PoolIsLocked` - If the pool is locked awaiting migration
+/// * `AmmError::TradeTooSoon` - If the previous trade on the pool was too recent
+/// * `AmmError::InvalidAccountInput` - If unwrapping a non-WSOL or foreign `user_sol`
+pub fn handle(
+    mut ctx: Context<SwapCoinX>,
+    coin_in_amount: u64,
+    coin_y_min_value: u64,
+    unwrap_sol: bool,
+    max_coin_in_amount: u64,
+) -> Result<()> {
+    let accs = &mut ctx.accounts;
 
     // Validate that the input amount is not zero
     if coin_in_amount == 0 {
         return Err(error!(AmmError::NoZeroTokens));
     }
 
-    // Check if user has sufficient meme tokens
-    if coin_in_amount > accs.user_meme.amount {
-        return Err(error!(AmmError::InsufficientBalance));
+    // Only close WSOL accounts the signer owns
+    if unwrap_sol
+        && !can_unwrap_user_sol(
+            &accs.pool.quote_reserve.mint,
+            &accs.user_sol.owner,
+            &accs.owner.key(),
+        )
+    {
+        return Err(error!(AmmError::InvalidAccountInput));
     }
 
-    // Check if the pool is locked
-    if accs.pool.locked {
-        return Err(error!(AmmError::PoolIsLocked));
-    }
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Refuse dust sells
+    accs.pool.check_min_trade(coin_in_amount, false)?;
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
 
     // Calculate swap amounts based on bonding curve
     let swap_amount = accs
-        .pool
-        .swap_amounts(coin_in_amount, coin_y_min_value, false);
+        .quoting_pool()
+        .swap_amounts(coin_in_amount, coin_y_min_value, false)?;
 
-    // Transfer meme tokens from user to pool
-    token::transfer(
-        accs.send_meme_to_pool(),
-        swap_amount.amount_in + swap_amount.admin_fee_in,
+    execute_sell(&mut ctx, swap_amount, max_coin_in_amount)?;
+
+    // Hand the payout, and the account's rent, over as native lamports
+    if unwrap_sol {
+        token::close_account(ctx.accounts.close_user_sol())?;
+    }
+
+    Ok(())
+}
+
+/// Whether `user_sol` may be closed to unwrap a sell's payout
+///
+/// Only WSOL accounts owned by the signer qualify, so the instruction can
+/// never close somebody else's account or a non-native one.
+///
+/// # Arguments
+/// * `quote_mint` - The pool's quote mint
+/// * `token_owner` - Owner recorded in `user_sol`
+/// * `signer` - The transaction signer
+pub fn can_unwrap_user_sol(quote_mint: &Pubkey, token_owner: &Pubkey, signer: &Pubkey) -> bool {
+    *quote_mint == spl_token::native_mint::ID && token_owner == signer
+}
+
+/// Points burned from a seller: what a buy worth the sell's gross SOL would
+/// have earned, or nothing when the epoch doesn't burn on sells
+///
+/// # Arguments
+/// * `sell_quote` - SOL value of the sell, before fees
+/// * `points_epoch` - Current points epoch with points rate and burn toggle
+/// * `percent_sold` - Pool progress after the sell, where an equivalent buy would start
+pub fn get_sell_burn_points(
+    sell_quote: u64,
+    points_epoch: &PointsEpoch,
+    percent_sold: u64,
+) -> Result<u64> {
+    if !points_epoch.burn_on_sell {
+        return Ok(0);
+    }
+
+    get_swap_points(sell_quote, points_epoch, percent_sold)
+}
+
+/// Settles a quoted sell: pulls the meme tokens in, books fees and reserves
+/// and pays out the SOL. Shared by the exact-in and exact-out sell endpoints.
+///
+/// # Arguments
+/// * `ctx` - The context containing all required accounts
+/// * `swap_amount` - The quoted sell amounts
+/// * `max_coin_in_amount` - Most meme tokens the caller meant to sell (0 = no limit)
+pub fn execute_sell<'info>(
+    ctx: &mut Context<'_, '_, '_, '_, SwapCoinX<'info>>,
+    swap_amount: SwapAmount,
+    max_coin_in_amount: u64,
+) -> Result<()> {
+    let accs = &mut ctx.accounts;
+    let bumps = &ctx.bumps;
+    let sent = swap_amount.amount_in + swap_amount.admin_fee_in;
+
+    // Catch a fat-fingered amount before it sweeps the balance
+    check_max_coin_in(sent, max_coin_in_amount)?;
+
+    // Check if user has sufficient meme tokens
+    if sent > accs.user_meme.amount {
+        return Err(error!(AmmError::InsufficientBalance));
+    }
+
+    // Refuse sells moving the price too far in one go
+    accs.pool.check_price_impact(&swap_amount, false)?;
+
+    // Enforce the owner's cooldown between swaps on this pool
+    accs.wallet_activity.record_trade(
+        accs.pool.key(),
+        accs.owner.key(),
+        Clock::get()?.unix_timestamp,
+        accs.pool.config.wallet_cooldown_secs,
     )?;
 
+    // Fail fast on a lookalike pool signer
+    BoundPool::assert_signer_pda(&crate::ID, &accs.pool.key(), &accs.pool_signer.key())?;
+
+    // Transfer meme tokens from user to pool. Meme mints are legacy Token
+    // mints, which withhold no transfer fee, so the vault receives all of it
+    token::transfer(accs.send_meme_to_pool(), sent)?;
+
     let pool_state = &mut accs.pool;
 
-    // Update admin fees
-    pool_state.admin_fees_meme += swap_amount.admin_fee_in;
-    pool_state.admin_fees_quote += swap_amount.admin_fee_out;
+    // Close the TWAP interval at the pre-trade price
+    pool_state.update_twap(Clock::get()?.slot)?;
+
+    // Book the fees where they're owed
+    let meme_fee_burned =
+        pool_state.accrue_swap_fees(swap_amount.admin_fee_out, swap_amount.admin_fee_in)?;
 
     // Update pool reserves
     pool_state.meme_reserve.tokens += swap_amount.amount_in;
     pool_state.quote_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
 
+    // Track lifetime volume
+    pool_state.record_volume(&swap_amount, false)?;
+
+    // Keep the short price history indexers build candles from
+    if let Some(snapshots) = &mut accs.price_snapshots {
+        snapshots.record(PriceSnapshot {
+            slot: Clock::get()?.slot,
+            spot_price: pool_state.spot_price()?,
+            quote_reserve: pool_state.quote_reserve.tokens,
+        });
+    }
+
     // Create signer seeds for pool PDA
     let seeds = &[
         BoundPool::SIGNER_PDA_PREFIX,
         &accs.pool.key().to_bytes()[..],
-        &[ctx.bumps.pool_signer],
+        &[bumps.pool_signer],
     ];
 
     let signer_seeds = &[&seeds[..]];
@@ -112,6 +291,29 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
         swap_amount.amount_out,
     )?;
 
+    // A migrated pool may burn its meme fees instead of keeping them
+    if meme_fee_burned > 0 {
+        token::burn(
+            accs.burn_meme_fees().with_signer(signer_seeds),
+            meme_fee_burned,
+        )?;
+    }
+
+    // Take back the points an equivalent buy would have earned, as far as
+    // the seller still holds them
+    let burn_points = min(
+        accs.user_points.amount,
+        get_sell_burn_points(
+            swap_amount.amount_out + swap_amount.admin_fee_out,
+            &accs.points_epoch,
+            accs.pool.percent_sold()?,
+        )?,
+    );
+    if burn_points > 0 {
+        token::burn(accs.burn_user_points(), burn_points)?;
+        msg!("Burned {} points from the seller", burn_points);
+    }
+
     // Log swap amounts
     msg!(
         "swapped_in: {}\n swapped_out: {}",
@@ -119,8 +321,33 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
         swap_amount.amount_out
     );
 
+    let swap_event = SwapEvent {
+        pool: accs.pool.key(),
+        owner: accs.owner.key(),
+        buy_meme: false,
+        amount_in: swap_amount.amount_in,
+        amount_out: swap_amount.amount_out,
+        admin_fee_in: swap_amount.admin_fee_in,
+        admin_fee_out: swap_amount.admin_fee_out,
+        points_emitted: 0,
+        spot_price: accs.pool.spot_price()?,
+        price_decimals: accs.pool.price_decimals(),
+    };
+
+    // Accounting bugs fail loudly on test builds instead of draining the pool
+    #[cfg(any(test, feature = "localnet-testing"))]
+    {
+        accs.meme_vault.reload()?;
+        accs.quote_vault.reload()?;
+        accs.pool
+            .check_reserve_invariant(accs.meme_vault.amount, accs.quote_vault.amount)?;
+    }
+
+    emit_cpi!(swap_event);
+
     Ok(())
 }
+
 /// Account validation struct for swapping meme tokens for SOL
 ///
 /// This struct validates that all required accounts are present and properly configured
@@ -129,12 +356,20 @@ pub fn handle(ctx: Context<SwapCoinX>, coin_in_amount: u64, coin_y_min_value: u6
 /// # Account Requirements
 /// * `pool` - The mutable bonding curve pool account
 /// * `meme_vault` - The pool's meme token vault account
+/// * `meme_mint` - The pool's meme mint, for fee burns
 /// * `quote_vault` - The pool's SOL vault account
 /// * `user_meme` - The user's meme token account
 /// * `user_sol` - The user's SOL token account to receive swapped tokens
-/// * `owner` - The signer/owner of the meme tokens
+/// * `owner` - The signer/owner of the meme tokens, receives lamports when unwrapping
 /// * `pool_signer` - PDA with authority over pool accounts
+/// * `user_points` - The user's points account, burned from on sells when enabled
+/// * `points_epoch` - Current points epoch with the burn-on-sell toggle
+/// * `points_mint` - The points token mint
+/// * `wallet_activity` - The owner's last swap on this pool, for the cooldown
+/// * `price_snapshots` - Optional price history of the pool, recorded into when passed
 /// * `token_program` - The Solana Token Program
+/// * `system_program` - Creates the wallet activity account
+#[event_cpi]
 #[derive(Accounts)]
 pub struct SwapCoinX<'info> {
     #[account(mut)]
@@ -146,6 +381,12 @@ pub struct SwapCoinX<'info> {
     )]
     pub meme_vault: Account<'info, TokenAccount>,
 
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.mint == meme_mint.key() @ AmmError::InvalidTokenMints
+    )]
+    pub meme_mint: Box<Account<'info, Mint>>,
+
     #[account(
         mut,
         constraint = pool.quote_reserve.vault == quote_vault.key()
@@ -159,22 +400,71 @@ pub struct SwapCoinX<'info> {
     )]
     pub user_meme: Account<'info, TokenAccount>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = user_sol.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints
+    )]
     pub user_sol: Account<'info, TokenAccount>,
 
+    #[account(mut)]
     pub owner: Signer<'info>,
 
     /// CHECK: pda signer
     #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
     pub pool_signer: AccountInfo<'info>,
 
+    /// Optional fee exemption of the owner on this pool
+    #[account(
+        seeds = [FeeExemption::FEE_EXEMPTION_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    /// The user's points account, burned from when the epoch burns on sells
+    #[account(
+        mut,
+        token::mint = points_mint,
+        token::authority = owner,
+    )]
+    pub user_points: Account<'info, TokenAccount>,
+
+    /// The current points epoch account with points rate and burn toggle
+    #[account(seeds = [POINTS_EPOCH_SEED], bump)]
+    pub points_epoch: Account<'info, PointsEpoch>,
+
+    /// The points token mint, its supply shrinks with every burn
+    #[account(mut, constraint = points_mint.key() == POINTS_MINT.key())]
+    pub points_mint: Account<'info, Mint>,
+
+    /// The owner's last swap on this pool, created on the first swap
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + WalletActivity::INIT_SPACE,
+        seeds = [WalletActivity::WALLET_ACTIVITY_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub wallet_activity: Box<Account<'info, WalletActivity>>,
+
+    /// Optional price history of this pool, recorded into when passed
+    #[account(
+        mut,
+        seeds = [PriceSnapshots::PRICE_SNAPSHOTS_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub price_snapshots: Option<Box<Account<'info, PriceSnapshots>>>,
+
     pub token_program: Program<'info, Token>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::fees::Fees;
+    use crate::models::bound::{migrated_test_pool, ResidualFeeMode};
+    use crate::models::fees::{Fees, FEE};
+    use crate::models::points_epoch::PointsEmission;
 
     #[test]
     fn test_zero_amount_validation() {
@@ -253,6 +543,148 @@ mod tests {
 
         println!("✅ Fee calculation test passed!");
     }
+
+    #[test]
+    fn test_unwrap_guard() {
+        let signer = Pubkey::new_unique();
+        let native = spl_token::native_mint::ID;
+
+        // Only the signer's own WSOL account can be closed
+        assert!(can_unwrap_user_sol(&native, &signer, &signer));
+        assert!(!can_unwrap_user_sol(&native, &Pubkey::new_unique(), &signer));
+        assert!(!can_unwrap_user_sol(&Pubkey::new_unique(), &signer, &signer));
+
+        println!("✅ Unwrap guard test passed!");
+    }
+
+    #[test]
+    fn test_unwrap_pays_native_lamports() {
+        // A signer holding an empty WSOL account sells for 0.5 SOL
+        let rent = 2_039_280;
+        let amount_out = 500_000_000;
+        let wallet_before = 1_000_000_000u64;
+        let wsol_account = rent;
+
+        // Payout lands in the WSOL account, closing it moves everything over
+        let (_, received) = super::test_utils::simulate_token_transfer(amount_out, amount_out);
+        let wallet_after = wallet_before + wsol_account + received;
+
+        // Native balance grows by the swap output plus the account's rent
+        assert_eq!(wallet_after - wallet_before - rent, amount_out);
+
+        println!("✅ Native unwrap payout test passed!");
+    }
+
+    fn create_test_points_epoch(burn_on_sell: bool) -> PointsEpoch {
+        PointsEpoch {
+            epoch_number: 1,
+            points_per_sol_num: 1000,
+            points_per_sol_denom: 1_000_000_000,
+            max_points_per_epoch: 0,
+            points_emitted: 0,
+            burn_on_sell,
+            emission_mode: PointsEmission::Flat,
+            points_vesting_secs: 0,
+            padding: [0; 2],
+        }
+    }
+
+    #[test]
+    fn test_sell_burn_matches_buy_points() {
+        // ARRANGE: An epoch burning on sells, 1000 points per SOL
+        let epoch = create_test_points_epoch(true);
+        let sell_quote = 2_500_000_000; // 2.5 SOL gross
+
+        println!("🧪 Testing the points burned on a sell");
+
+        // ACT
+        let burned = get_sell_burn_points(sell_quote, &epoch, 0).unwrap();
+
+        // ASSERT: A round trip gives back exactly what the buy earned
+        assert_eq!(burned, get_swap_points(sell_quote, &epoch, 0).unwrap());
+        assert_eq!(burned, 2_500);
+
+        // The burn is capped at what the seller still holds
+        assert_eq!(min(1_000, burned), 1_000);
+
+        println!("✅ Sell burn amount test passed!");
+    }
+
+    #[test]
+    fn test_sell_burn_toggle() {
+        let sell_quote = 2_500_000_000;
+
+        // Epochs without the flag leave the seller's points alone
+        assert_eq!(
+            get_sell_burn_points(sell_quote, &create_test_points_epoch(false), 0).unwrap(),
+            0
+        );
+        assert!(get_sell_burn_points(sell_quote, &create_test_points_epoch(true), 0).unwrap() > 0);
+
+        println!("✅ Sell burn toggle test passed!");
+    }
+
+    #[test]
+    fn test_sell_burn_set_by_update() {
+        // ARRANGE: An epoch leaving sellers' points alone
+        let mut epoch = create_test_points_epoch(false);
+        let sell_quote = 2_500_000_000;
+        assert_eq!(get_sell_burn_points(sell_quote, &epoch, 0).unwrap(), 0);
+
+        println!("🧪 Testing the sell burn switched on by the admin");
+
+        // ACT
+        let mut settings = epoch.settings();
+        settings.burn_on_sell = true;
+        epoch.update_settings(settings).unwrap();
+
+        // ASSERT: The same sell now burns what a buy would have earned
+        assert_eq!(get_sell_burn_points(sell_quote, &epoch, 0).unwrap(), 2_500);
+
+        println!("✅ Updated sell burn test passed!");
+    }
+
+    #[test]
+    fn test_residual_sell_after_migration() {
+        // ARRANGE: A migrated pool paying its residual fees to stakers
+        let mut pool = migrated_test_pool(ResidualFeeMode::Stakers);
+        pool.fees.fee_meme_percent = FEE;
+        let (meme_before, quote_before) = (pool.meme_reserve.tokens, pool.quote_reserve.tokens);
+        let price_before = pool.spot_price().unwrap();
+
+        println!("🧪 Testing a sell against a migrated pool's residual reserves");
+
+        // ACT: Quote and book 1M meme the way the swap_x handler does
+        pool.check_tradeable().unwrap();
+        let swap = pool.swap_amounts(1_000_000_000_000, 0, false).unwrap();
+        let burned = pool
+            .accrue_swap_fees(swap.admin_fee_out, swap.admin_fee_in)
+            .unwrap();
+        pool.meme_reserve.tokens += swap.amount_in;
+        pool.quote_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+
+        // ASSERT: Priced off the reserves, whose product never shrinks
+        assert!(swap.amount_out > 0);
+        assert!(pool.spot_price().unwrap() < price_before);
+        assert!(
+            pool.meme_reserve.tokens as u128 * pool.quote_reserve.tokens as u128
+                >= meme_before as u128 * quote_before as u128
+        );
+
+        // Stakers get the quote fee, the meme fee is burned
+        assert!(burned > 0);
+        assert_eq!(burned, swap.admin_fee_in);
+        assert_eq!(pool.staker_fees_quote, swap.admin_fee_out);
+        assert_eq!((pool.admin_fees_quote, pool.admin_fees_meme), (0, 0));
+
+        // Exact-out sells only run on the curve
+        assert_eq!(
+            pool.sell_meme_exact_out_amounts(1_000_000, 0).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Residual sell test passed!");
+    }
 }
 
 /// Additional test utilities module
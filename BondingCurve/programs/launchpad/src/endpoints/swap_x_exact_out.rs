@@ -0,0 +1,36 @@
+use crate::endpoints::swap_x::{execute_sell, SwapCoinX};
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+// Handler function for selling meme tokens for an exact amount of SOL
+//
+// # Arguments
+// * `ctx` - The context containing all required accounts
+// * `sol_out_amount` - Amount of SOL to receive
+// * `max_meme_in` - Maximum amount of meme tokens to sell, fees included
+pub fn handle(mut ctx: Context<SwapCoinX>, sol_out_amount: u64, max_meme_in: u64) -> Result<()> {
+    // Get accounts from context
+    let accs = &mut ctx.accounts;
+
+    // Check that requested output is not zero
+    if sol_out_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
+
+    // Calculate the meme tokens required for the requested output
+    let swap_amount = accs
+        .quoting_pool()
+        .sell_meme_exact_out_amounts(sol_out_amount, max_meme_in)?;
+
+    // Refuse dust sells, judged by the meme they pay in
+    accs.pool
+        .check_min_trade(swap_amount.amount_in + swap_amount.admin_fee_in, false)?;
+
+    execute_sell(&mut ctx, swap_amount, max_meme_in)
+}
@@ -1,17 +1,35 @@
 // Import necessary constants from the crate
-use crate::consts::{POINTS_MINT, POINTS_PDA};
+use crate::consts::{BPS_DENOMINATOR, POINTS_EPOCH_SEED, POINTS_MINT, POINTS_PDA};
 // Import error handling
 use crate::err::AmmError;
 // Import math utilities
 use crate::libraries::MulDiv;
 // Import bonding curve pool model
 use crate::models::bound::BoundPool;
-// Import points epoch model
-use crate::models::points_epoch::PointsEpoch;
+// Import buyer record model
+use crate::models::buyer_record::BuyerRecord;
+// Import fee exemption model
+use crate::models::fee_exemption::FeeExemption;
+// Import swap amount model
+use crate::models::SwapAmount;
+// Import points epoch models
+use crate::models::points_epoch::{PointsEmission, PointsEpoch};
+// Import points escrow model
+use crate::models::points_escrow::PointsEscrow;
+// Import price snapshot models
+use crate::models::price_snapshot::{PriceSnapshot, PriceSnapshots};
+// Import referral code model
+use crate::models::referral_code::ReferralCode;
+// Import referrer stats model
+use crate::models::referrer_stats::ReferrerStats;
+// Import wallet activity model
+use crate::models::wallet_activity::WalletActivity;
 // Import Anchor lang prelude
 use anchor_lang::prelude::*;
 // Import SPL token program types
-use anchor_spl::token::{self, Mint, Token, TokenAccount, Transfer};
+use anchor_spl::token::{self, Burn, Mint, Token, TokenAccount, Transfer};
+// Import Cow for quoting against a fee free copy of the pool
+use std::borrow::Cow;
 // Import min function for points calculation
 use std::cmp::min;
 
@@ -39,6 +57,26 @@ impl<'info> SwapCoinY<'info> {
         let cpi_program = self.token_program.to_account_info();
         CpiContext::new(cpi_program, cpi_accounts)
     }
+
+    // Helper function to create CPI context for burning meme fees in the vault
+    fn burn_meme_fees(&self) -> CpiContext<'_, '_, '_, 'info, Burn<'info>> {
+        let cpi_accounts = Burn {
+            mint: self.meme_mint.to_account_info(),
+            from: self.meme_vault.to_account_info(),
+            authority: self.pool_signer_pda.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    // Pool to quote the buy against, fee free when the owner is exempt
+    pub fn quoting_pool(&self) -> Cow<'_, BoundPool> {
+        match self.fee_exemption {
+            Some(_) => Cow::Owned(self.pool.without_fees()),
+            None => Cow::Borrowed(&self.pool),
+        }
+    }
 }
 
 // Handler function for swapping SOL for meme tokens
@@ -47,93 +85,208 @@ impl<'info> SwapCoinY<'info> {
 // * `ctx` - The context containing all required accounts
 // * `coin_in_amount` - Amount of SOL to swap
 // * `coin_x_min_value` - Minimum amount of meme tokens to receive
-pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, coin_x_min_value: u64) -> Result<()> {
+// * `max_coin_in_amount` - Most SOL the caller meant to spend (0 = no limit)
+pub fn handle(
+    mut ctx: Context<SwapCoinY>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    max_coin_in_amount: u64,
+) -> Result<()> {
     // Get accounts from context
-    let accs = ctx.accounts;
+    let accs = &mut ctx.accounts;
 
     // Check that input amount is not zero
     if coin_in_amount == 0 {
         return Err(error!(AmmError::NoZeroTokens));
     }
 
-    // Check that pool is not locked
-    if accs.pool.locked {
-        return Err(error!(AmmError::PoolIsLocked));
-    }
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Refuse dust buys
+    accs.pool.check_min_trade(coin_in_amount, true)?;
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
 
     // Calculate swap amounts
     let swap_amount = accs
-        .pool
-        .swap_amounts(coin_in_amount, coin_x_min_value, true);
+        .quoting_pool()
+        .swap_amounts(coin_in_amount, coin_x_min_value, true)?;
 
-    // Transfer SOL from user to pool
-    token::transfer(
-        accs.send_user_tokens(),
-        swap_amount.amount_in + swap_amount.admin_fee_in,
+    execute_buy(&mut ctx, swap_amount, max_coin_in_amount)
+}
+
+// Checks the user's quote account holds the amount they're buying with
+//
+// # Arguments
+// * `balance` - Token amount of the user's quote account
+// * `coin_in_amount` - Amount of SOL to swap
+pub fn check_sol_balance(balance: u64, coin_in_amount: u64) -> Result<()> {
+    if coin_in_amount > balance {
+        return Err(error!(AmmError::InsufficientBalance));
+    }
+
+    Ok(())
+}
+
+// Rejects a swap paying in more than the caller's own limit
+//
+// # Arguments
+// * `coin_in_amount` - Amount the swap pays in
+// * `max_coin_in_amount` - Caller's upper bound, 0 for no limit
+pub fn check_max_coin_in(coin_in_amount: u64, max_coin_in_amount: u64) -> Result<()> {
+    if max_coin_in_amount != 0 && coin_in_amount > max_coin_in_amount {
+        return Err(error!(AmmError::AmountExceedsMax));
+    }
+
+    Ok(())
+}
+
+// Moves the tokens of an already quoted buy, distributes referral points and
+// updates the pool state. Shared by all buy flavours.
+//
+// # Arguments
+// * `ctx` - The context containing all required accounts
+// * `swap_amount` - The quoted buy amounts
+// * `max_coin_in_amount` - Most SOL the caller meant to spend (0 = no limit)
+pub fn execute_buy<'info>(
+    ctx: &mut Context<'_, '_, '_, '_, SwapCoinY<'info>>,
+    swap_amount: SwapAmount,
+    max_coin_in_amount: u64,
+) -> Result<()> {
+    let accs = &mut ctx.accounts;
+    let bumps = &ctx.bumps;
+    let coin_in_amount = swap_amount.amount_in + swap_amount.admin_fee_in;
+
+    // Catch a fat-fingered amount before it sweeps the balance
+    check_max_coin_in(coin_in_amount, max_coin_in_amount)?;
+
+    // Fail with a clear error instead of inside the token program
+    check_sol_balance(accs.user_sol.amount, coin_in_amount)?;
+
+    // Refuse buys moving the price too far in one go
+    accs.pool.check_price_impact(&swap_amount, true)?;
+
+    // Enforce the owner's cooldown between swaps on this pool
+    accs.wallet_activity.record_trade(
+        accs.pool.key(),
+        accs.owner.key(),
+        Clock::get()?.unix_timestamp,
+        accs.pool.config.wallet_cooldown_secs,
+    )?;
+
+    // Fail fast on a lookalike pool signer
+    BoundPool::assert_signer_pda(
+        &crate::ID,
+        &accs.pool.key(),
+        &accs.pool_signer_pda.key(),
     )?;
 
+    // Transfer SOL from user to pool
+    token::transfer(accs.send_user_tokens(), coin_in_amount)?;
+
     // Create pool signer PDA seeds for meme token transfer
     let pool_signer_seeds = &[
         BoundPool::SIGNER_PDA_PREFIX,
         &accs.pool.key().to_bytes()[..],
-        &[ctx.bumps.pool_signer_pda],
+        &[bumps.pool_signer_pda],
     ];
 
+    // The very first buyer gets a bonus on top, from the supply off the curve
+    let first_buyer_bonus = accs.pool.claim_first_buyer_bonus(swap_amount.amount_out)?;
+    if first_buyer_bonus > 0 {
+        msg!("First buyer bonus: {} meme tokens", first_buyer_bonus);
+    }
+
     // Transfer meme tokens directly to user's wallet
     token::transfer(
         accs.send_meme_to_user()
             .with_signer(&[&pool_signer_seeds[..]]),
-        swap_amount.amount_out,
+        swap_amount.amount_out + first_buyer_bonus,
     )?;
 
     // Create points PDA signer seeds
-    let point_pda: &[&[u8]] = &[POINTS_PDA, &[ctx.bumps.points_pda]];
+    let point_pda: &[&[u8]] = &[POINTS_PDA, &[bumps.points_pda]];
     let point_pda_seeds = &[&point_pda[..]];
 
     // Get available points amount
     let available_points_amt = accs.points_acc.amount;
 
-    // Calculate points for swap
-    let points = get_swap_points(
+    // Calculate points for swap, clamped to available amount. A drained
+    // points pool skips the points block below altogether.
+    let clamped_points = get_clamped_swap_points(
+        available_points_amt,
         swap_amount.amount_in + swap_amount.admin_fee_in,
         &accs.points_epoch,
-    );
-    // Clamp points to available amount
-    let clamped_points = min(available_points_amt, points);
+        accs.pool.percent_sold()?,
+    )?;
+
+    // Points actually distributed, reported in the swap event
+    let mut points_emitted = 0;
 
     // Transfer points if available
     if clamped_points > 0 {
+        // Vesting epochs pay through the referrer's escrow instead
+        let vesting_secs = accs.points_epoch.points_vesting_secs;
+        let escrow_vault = match (&accs.points_escrow, &accs.points_escrow_vault) {
+            (Some(_), Some(vault)) if vesting_secs > 0 => Some(vault.to_account_info()),
+            _ => None,
+        };
+
         // Check if referrer account exists
         if let Some(referrer) = &mut accs.referrer_points {
-            // Referrer gets ALL the points (100%, not 25%)
-            let referrer_points = clamped_points; // Give full amount to referrer
-
-            // Clamp to available amount in points pool
-            let clamped_referrer_points = min(available_points_amt, referrer_points);
-
-            // If there are points to give to referrer
-            if clamped_referrer_points > 0 {
-                // Setup transfer accounts for referrer
-                let cpi_accounts = Transfer {
-                    from: accs.points_acc.to_account_info(),
-                    to: referrer.to_account_info(),
-                    authority: accs.points_pda.to_account_info(),
-                };
-
-                // Get token program account
-                let cpi_program = accs.token_program.to_account_info();
-
-                // Transfer ALL points to referrer only
-                token::transfer(
-                    CpiContext::new(cpi_program, cpi_accounts).with_signer(point_pda_seeds),
-                    clamped_referrer_points,
-                )?;
-
-                // Log referrer reward
-                msg!(
-                    "Referrer received {} points for successful referral!",
-                    clamped_referrer_points
-                );
+            if vesting_secs > 0 && escrow_vault.is_none() {
+                // Nowhere to vest the points into, so none are emitted
+                msg!("Referrer has no points escrow - no points distributed.");
+            } else {
+                // Referrer gets ALL the points (100%, not 25%), within the epoch cap
+                let referrer_points = accs.points_epoch.emit_points(clamped_points)?;
+
+                // Clamp to available amount in points pool
+                let clamped_referrer_points = min(available_points_amt, referrer_points);
+
+                // If there are points to give to referrer
+                if clamped_referrer_points > 0 {
+                    // Setup transfer accounts for referrer, or its escrow
+                    let cpi_accounts = Transfer {
+                        from: accs.points_acc.to_account_info(),
+                        to: escrow_vault.unwrap_or_else(|| referrer.to_account_info()),
+                        authority: accs.points_pda.to_account_info(),
+                    };
+
+                    // Get token program account
+                    let cpi_program = accs.token_program.to_account_info();
+
+                    // Transfer ALL points to referrer only
+                    token::transfer(
+                        CpiContext::new(cpi_program, cpi_accounts).with_signer(point_pda_seeds),
+                        clamped_referrer_points,
+                    )?;
+
+                    if let Some(escrow) = accs.points_escrow.as_mut().filter(|_| vesting_secs > 0) {
+                        // Claimable through claim_points once the delay passed
+                        escrow.deposit(
+                            clamped_referrer_points,
+                            Clock::get()?.unix_timestamp,
+                            vesting_secs,
+                        )?;
+
+                        msg!(
+                            "Referrer earned {} points, claimable after {} seconds",
+                            clamped_referrer_points,
+                            vesting_secs
+                        );
+                    } else {
+                        // Log referrer reward
+                        msg!(
+                            "Referrer received {} points for successful referral!",
+                            clamped_referrer_points
+                        );
+                    }
+
+                    points_emitted = clamped_referrer_points;
+                }
             }
         } else {
             // No referrer = no points distributed at all!
@@ -142,21 +295,60 @@ pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, coin_x_min_value: u6
         }
     }
 
+    // Count the buy towards the referrer's leaderboard totals
+    let referrer_stats = accs.referrer_stats.as_deref_mut().map(|stats| &mut **stats);
+    record_referral_stats(referrer_stats, points_emitted)?;
+
     // Get mutable reference to pool
     let pool = &mut accs.pool;
 
-    // Update pool admin fees
-    pool.admin_fees_quote += swap_amount.admin_fee_in;
-    pool.admin_fees_meme += swap_amount.admin_fee_out;
+    // Close the TWAP interval at the pre-trade price
+    pool.update_twap(Clock::get()?.slot)?;
+
+    // Book the fees where they're owed
+    let meme_fee_burned =
+        pool.accrue_swap_fees(swap_amount.admin_fee_in, swap_amount.admin_fee_out)?;
 
     // Update pool reserves
     pool.quote_reserve.tokens += swap_amount.amount_in;
     pool.meme_reserve.tokens -= swap_amount.amount_out + swap_amount.admin_fee_out;
 
+    // Track lifetime volume
+    pool.record_volume(&swap_amount, true)?;
+
+    // Keep the short price history indexers build candles from
+    if let Some(snapshots) = &mut accs.price_snapshots {
+        snapshots.record(PriceSnapshot {
+            slot: Clock::get()?.slot,
+            spot_price: pool.spot_price()?,
+            quote_reserve: pool.quote_reserve.tokens,
+        });
+    }
+
+    // Count the owner once across all of its buys
+    let pool_key = pool.key();
+    pool.record_buyer(
+        &mut accs.buyer_record,
+        pool_key,
+        accs.owner.key(),
+        Clock::get()?.slot,
+    )?;
+
     // Lock pool if meme tokens depleted
-    if pool.meme_reserve.tokens == 0 {
-        pool.locked = true;
-    };
+    let pool_locked = pool.lock_if_sold_out().then(|| PoolLocked {
+        pool: pool_key,
+        meme_reserve: pool.meme_reserve.tokens,
+        quote_reserve: pool.quote_reserve.tokens,
+        reason: LockReason::SoldOut,
+    });
+
+    // A migrated pool may burn its meme fees instead of keeping them
+    if meme_fee_burned > 0 {
+        token::burn(
+            accs.burn_meme_fees().with_signer(&[&pool_signer_seeds[..]]),
+            meme_fee_burned,
+        )?;
+    }
 
     // Log swap amounts
     msg!(
@@ -165,48 +357,189 @@ pub fn handle(ctx: Context<SwapCoinY>, coin_in_amount: u64, coin_x_min_value: u6
         swap_amount.amount_out
     );
 
+    let swap_event = SwapEvent {
+        pool: accs.pool.key(),
+        owner: accs.owner.key(),
+        buy_meme: true,
+        amount_in: swap_amount.amount_in,
+        amount_out: swap_amount.amount_out,
+        admin_fee_in: swap_amount.admin_fee_in,
+        admin_fee_out: swap_amount.admin_fee_out,
+        points_emitted,
+        spot_price: accs.pool.spot_price()?,
+        price_decimals: accs.pool.price_decimals(),
+    };
+
+    // Accounting bugs fail loudly on test builds instead of draining the pool
+    #[cfg(any(test, feature = "localnet-testing"))]
+    {
+        accs.meme_vault.reload()?;
+        accs.quote_vault.reload()?;
+        accs.pool
+            .check_reserve_invariant(accs.meme_vault.amount, accs.quote_vault.amount)?;
+    }
+
+    if let Some(pool_locked) = pool_locked {
+        emit_cpi!(pool_locked);
+    }
+    emit_cpi!(swap_event);
+
     Ok(())
 }
 
+// Why a pool stopped trading
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LockReason {
+    // The last meme tokens on the curve were bought
+    SoldOut,
+    // Liquidity is being moved to the AMM
+    Migration,
+}
+
+// Emitted when a pool gets locked
+#[event]
+pub struct PoolLocked {
+    pub pool: Pubkey,
+    pub meme_reserve: u64,
+    pub quote_reserve: u64,
+    pub reason: LockReason,
+}
+
+// Emitted after every executed swap
+#[event]
+pub struct SwapEvent {
+    pub pool: Pubkey,
+    pub owner: Pubkey,
+    pub buy_meme: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub admin_fee_in: u64,
+    pub admin_fee_out: u64,
+    // Referral points distributed by the swap, after epoch cap clamping
+    pub points_emitted: u64,
+    // Spot price after the swap, see `BoundPool::spot_price`
+    pub spot_price: u64,
+    // Decimal places of `spot_price`, from the pool's quote decimals
+    pub price_decimals: u8,
+}
+
 // Calculate points earned for a swap
 //
 // # Arguments
 // * `buy_amount` - Amount of SOL being swapped
-// * `points_epoch` - Current points epoch with points rate
-pub fn get_swap_points(buy_amount: u64, points_epoch: &PointsEpoch) -> u64 {
-    buy_amount
+// * `points_epoch` - Current points epoch with points rate and emission mode
+// * `percent_sold` - Pool progress before the swap, see `BoundPool::percent_sold`
+pub fn get_swap_points(
+    buy_amount: u64,
+    points_epoch: &PointsEpoch,
+    percent_sold: u64,
+) -> Result<u64> {
+    let points = buy_amount
         .mul_div_floor(
             points_epoch.points_per_sol_num,
             points_epoch.points_per_sol_denom,
         )
-        .unwrap()
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    match points_epoch.emission_mode {
+        PointsEmission::Flat => Ok(points),
+        PointsEmission::Decaying => points
+            .mul_div_floor(
+                BPS_DENOMINATOR.saturating_sub(percent_sold),
+                BPS_DENOMINATOR,
+            )
+            .ok_or(error!(AmmError::MathOverflow)),
+    }
+}
+
+// Points a buy earns, clamped to what is left in the points pool. Nothing is
+// calculated once the pool is drained, so the swap can't fail over points.
+//
+// # Arguments
+// * `available_points` - Points left in the points pool
+// * `buy_amount` - Amount of SOL being swapped
+// * `points_epoch` - Current points epoch with points rate and emission mode
+// * `percent_sold` - Pool progress before the swap, see `BoundPool::percent_sold`
+pub fn get_clamped_swap_points(
+    available_points: u64,
+    buy_amount: u64,
+    points_epoch: &PointsEpoch,
+    percent_sold: u64,
+) -> Result<u64> {
+    if available_points == 0 {
+        return Ok(0);
+    }
+
+    let points = get_swap_points(buy_amount, points_epoch, percent_sold)?;
+
+    Ok(min(available_points, points))
+}
+
+// Checks an optional referrer points account isn't aliased to the points pool
+// or the user's own points account, either of which would misroute the bonus
+pub fn referrer_account_valid(
+    referrer_points: Option<Pubkey>,
+    points_acc: Pubkey,
+    user_points: Pubkey,
+) -> bool {
+    match referrer_points {
+        Some(referrer) => referrer != points_acc && referrer != user_points,
+        None => true,
+    }
+}
+
+// Checks the referrer points account passed alongside a referral code belongs
+// to the code's owner, so codes can't be used to redirect referral points
+pub fn referrer_matches_code(code_owner: Pubkey, referrer_owner: Option<Pubkey>) -> bool {
+    referrer_owner == Some(code_owner)
+}
+
+// Counts a referred buy in the referrer's stats, but only if it earned the
+// referrer points. Buys past the epoch cap or with the points pool drained
+// would otherwise inflate the referral count
+pub fn record_referral_stats(stats: Option<&mut ReferrerStats>, points_emitted: u64) -> Result<()> {
+    match stats {
+        Some(stats) if points_emitted > 0 => stats.record_referral(points_emitted),
+        _ => Ok(()),
+    }
 }
 
 // Account validation struct for swapping SOL for meme tokens
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(coin_in_amount: u64, coin_x_min_value: u64)]
 pub struct SwapCoinY<'info> {
     // The pool account that will be modified during the swap
     #[account(mut)]
-    pool: Account<'info, BoundPool>,
+    pub pool: Account<'info, BoundPool>,
 
     // The pool's meme token vault that holds meme tokens
     #[account(
         mut,
         constraint = pool.meme_reserve.vault == meme_vault.key()
     )]
-    meme_vault: Account<'info, TokenAccount>,
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    // The pool's meme mint, burned from once a migrated pool burns its fees
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.mint == meme_mint.key() @ AmmError::InvalidTokenMints
+    )]
+    pub meme_mint: Box<Account<'info, Mint>>,
 
     // The pool's quote token vault that holds SOL
     #[account(
         mut,
         constraint = pool.quote_reserve.vault == quote_vault.key()
     )]
-    quote_vault: Account<'info, TokenAccount>,
+    pub quote_vault: Account<'info, TokenAccount>,
 
-    // The user's SOL token account that will send tokens
-    #[account(mut)]
-    user_sol: Account<'info, TokenAccount>,
+    // The user's quote token account that will send tokens, in the pool's quote mint
+    #[account(
+        mut,
+        constraint = user_sol.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints
+    )]
+    pub user_sol: Account<'info, TokenAccount>,
 
     // The user's meme token account that will receive tokens directly
     #[account(
@@ -214,7 +547,7 @@ pub struct SwapCoinY<'info> {
         constraint = user_meme.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
         constraint = user_meme.owner == owner.key()
     )]
-    user_meme: Account<'info, TokenAccount>,
+    pub user_meme: Account<'info, TokenAccount>,
 
     // The user's points token account that will receive points
     #[account(
@@ -222,7 +555,7 @@ pub struct SwapCoinY<'info> {
         token::mint = points_mint,
         token::authority = owner,
     )]
-    user_points: Account<'info, TokenAccount>,
+    pub user_points: Account<'info, TokenAccount>,
 
     // Optional referrer points account to receive referral points
     #[account(
@@ -230,44 +563,126 @@ pub struct SwapCoinY<'info> {
         token::mint = points_mint,
         constraint = referrer_points.owner != user_points.owner
     )]
-    referrer_points: Option<Account<'info, TokenAccount>>,
+    pub referrer_points: Option<Account<'info, TokenAccount>>,
 
-    // The current points epoch account with points rate info
-    points_epoch: Account<'info, PointsEpoch>,
+    // Optional referral code resolving to the referrer, whose points account
+    // must then be the one passed as `referrer_points`
+    #[account(
+        constraint = referrer_matches_code(
+            referral_code.owner,
+            referrer_points.as_ref().map(|referrer| referrer.owner),
+        ) @ AmmError::InvalidReferrerAccount
+    )]
+    pub referral_code: Option<Account<'info, ReferralCode>>,
+
+    // The current points epoch account with points rate info and emission cap
+    #[account(mut, seeds = [POINTS_EPOCH_SEED], bump)]
+    pub points_epoch: Account<'info, PointsEpoch>,
 
     // The points token mint account
     #[account(mut, constraint = points_mint.key() == POINTS_MINT.key())]
-    points_mint: Account<'info, Mint>,
+    pub points_mint: Account<'info, Mint>,
 
     // The points PDA token account that holds points to distribute
     #[account(
         mut,
         token::mint = points_mint,
-        token::authority = points_pda
+        token::authority = points_pda,
+        constraint = referrer_account_valid(
+            referrer_points.as_ref().map(|referrer| referrer.key()),
+            points_acc.key(),
+            user_points.key(),
+        ) @ AmmError::InvalidReferrerAccount
     )]
-    points_acc: Account<'info, TokenAccount>,
+    pub points_acc: Account<'info, TokenAccount>,
+
+    // Referrer's points escrow, which referral points vest in while the
+    // epoch has a `points_vesting_secs`
+    #[account(
+        mut,
+        seeds = [PointsEscrow::POINTS_ESCROW_PREFIX, points_escrow.owner.as_ref()],
+        bump,
+        constraint = referrer_points.as_ref().map(|referrer| referrer.owner)
+            == Some(points_escrow.owner) @ AmmError::InvalidReferrerAccount
+    )]
+    pub points_escrow: Option<Box<Account<'info, PointsEscrow>>>,
+
+    // Points token vault backing every escrow
+    #[account(mut, seeds = [PointsEscrow::VAULT_PREFIX], bump)]
+    pub points_escrow_vault: Option<Box<Account<'info, TokenAccount>>>,
+
+    // Referrer's leaderboard totals, counted into when passed
+    #[account(
+        mut,
+        seeds = [ReferrerStats::REFERRER_STATS_PREFIX, referrer_stats.referrer.as_ref()],
+        bump,
+        constraint = referrer_points.as_ref().map(|referrer| referrer.owner)
+            == Some(referrer_stats.referrer) @ AmmError::InvalidReferrerAccount
+    )]
+    pub referrer_stats: Option<Box<Account<'info, ReferrerStats>>>,
 
     // The owner/signer of the transaction
     #[account(mut)]
-    owner: Signer<'info>,
+    pub owner: Signer<'info>,
 
     /// CHECK: PDA signer for points distribution - seeds validation ensures this is the correct PDA
     #[account(seeds = [POINTS_PDA], bump)]
-    points_pda: AccountInfo<'info>,
+    pub points_pda: AccountInfo<'info>,
 
     /// CHECK: PDA signer for the pool - seeds validation ensures this is the correct pool authority
     #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
-    pool_signer_pda: AccountInfo<'info>,
+    pub pool_signer_pda: AccountInfo<'info>,
+
+    // Record of the owner's buys from this pool, created on the first buy
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + BuyerRecord::INIT_SPACE,
+        seeds = [BuyerRecord::BUYER_RECORD_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub buyer_record: Box<Account<'info, BuyerRecord>>,
+
+    // The owner's last swap on this pool, created on the first swap
+    #[account(
+        init_if_needed,
+        payer = owner,
+        space = 8 + WalletActivity::INIT_SPACE,
+        seeds = [WalletActivity::WALLET_ACTIVITY_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub wallet_activity: Box<Account<'info, WalletActivity>>,
+
+    // Optional fee exemption of the owner on this pool
+    #[account(
+        seeds = [FeeExemption::FEE_EXEMPTION_PREFIX, pool.key().as_ref(), owner.key().as_ref()],
+        bump
+    )]
+    pub fee_exemption: Option<Account<'info, FeeExemption>>,
+
+    // Optional price history of this pool, recorded into when passed
+    #[account(
+        mut,
+        seeds = [PriceSnapshots::PRICE_SNAPSHOTS_PREFIX, pool.key().as_ref()],
+        bump
+    )]
+    pub price_snapshots: Option<Box<Account<'info, PriceSnapshots>>>,
 
     // The SPL token program
-    token_program: Program<'info, Token>,
+    pub token_program: Program<'info, Token>,
+
+    // The system program, needed to create the buyer record
+    pub system_program: Program<'info, System>,
 }
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::consts::{POINTS_MINT, POINTS_PDA};
-    use crate::models::bound::{BoundPool, Config, Decimals};
-    use crate::models::fees::Fees;
+    use crate::models::bound::{
+        migrated_test_pool, BoundPool, Config, CurveType, Decimals, GraduationMode,
+        MigrationTarget, ResidualFeeMode,
+    };
+    use crate::models::fees::{Fees, FEE};
     use crate::models::points_epoch::PointsEpoch;
     use crate::models::Reserve;
 
@@ -305,11 +720,39 @@ mod tests {
                     beta: 1_000_000_000,
                     quote: 1_000_000_000,
                 },
+                min_slot_between_trades: 0,
+                creator_fee_bps: 0,
+                graduation_mode: GraduationMode::TokenTarget,
+                max_price_impact_bps: 0,
+                virtual_sol_reserves: 0,
+                virtual_meme_reserves: 0,
+                min_quote_in: 0,
+                min_meme_in: 0,
+                wallet_cooldown_secs: 0,
+                migration_target: MigrationTarget::Raydium,
+                migration_price_tolerance_bps: 0,
+                first_buyer_bonus_bps: 0,
+                min_meme_reserve: 0,
+                burn_unsold_bps: 0,
+                min_unique_buyers: 0,
+                residual_fee_mode: ResidualFeeMode::Treasury,
+                curve_type: CurveType::PositiveIntercept,
             },
             airdropped_tokens: 0,
             locked: false,
             pool_migration: false,
             pool_key: Pubkey::default(),
+            last_trade_slot: 0,
+            total_quote_volume: 0,
+            total_meme_volume: 0,
+            unique_buyers: 0,
+            creator_fees_quote: 0,
+            price_cumulative: 0,
+            last_twap_slot: 0,
+            first_buy_done: false,
+            pending_creator: Pubkey::default(),
+            staker_fees_quote: 0,
+            version: BoundPool::VERSION,
         }
     }
 
@@ -319,7 +762,12 @@ mod tests {
             epoch_number: 1,
             points_per_sol_num: 1000, // 1000 points per SOL numerator
             points_per_sol_denom: 1,  // denominator = 1 (so 1000 points per SOL)
-            padding: [0; 8],
+            max_points_per_epoch: 0,  // uncapped
+            points_emitted: 0,
+            burn_on_sell: false,
+            emission_mode: PointsEmission::Flat,
+            points_vesting_secs: 0,
+            padding: [0; 2],
         }
     }
 
@@ -369,7 +817,7 @@ mod tests {
         let buy_amount = 100; // 100 SOL
 
         // Calculate expected points: 100 SOL * 1000 points/SOL = 100,000 points
-        let expected_points = get_swap_points(buy_amount, &points_epoch);
+        let expected_points = get_swap_points(buy_amount, &points_epoch, 0).unwrap();
         let manual_calculation =
             buy_amount * points_epoch.points_per_sol_num / points_epoch.points_per_sol_denom;
 
@@ -393,7 +841,7 @@ mod tests {
 
         // User swaps 50 SOL
         let buy_amount = 50;
-        let calculated_points = get_swap_points(buy_amount, &points_epoch);
+        let calculated_points = get_swap_points(buy_amount, &points_epoch, 0).unwrap();
 
         // All points should go to referrer (not user!)
         let referrer_points = calculated_points; // 100% to referrer
@@ -415,7 +863,7 @@ mod tests {
         let buy_amount = 100;
 
         // Calculate points that would be earned
-        let calculated_points = get_swap_points(buy_amount, &points_epoch);
+        let calculated_points = get_swap_points(buy_amount, &points_epoch, 0).unwrap();
 
         // With no referrer, NO points are distributed at all
         let distributed_points = 0; // No referrer = no points!
@@ -432,7 +880,7 @@ mod tests {
         let available_points = 10_000; // Only 10k points available
         let buy_amount = 100; // Would normally earn 100k points
 
-        let calculated_points = get_swap_points(buy_amount, &points_epoch);
+        let calculated_points = get_swap_points(buy_amount, &points_epoch, 0).unwrap();
         let clamped_points = std::cmp::min(available_points, calculated_points);
 
         assert_eq!(calculated_points, 100_000);
@@ -444,6 +892,165 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_referrer_stats_skip_capped_buys() {
+        // ARRANGE: An epoch capped at 60k points and an empty leaderboard entry
+        let mut points_epoch = create_test_points_epoch();
+        points_epoch.max_points_per_epoch = 60_000;
+        let mut stats = ReferrerStats {
+            referrer: Pubkey::new_unique(),
+            total_points_earned: 0,
+            referral_count: 0,
+        };
+
+        println!("🧪 Testing referrer stats once the epoch cap is hit");
+
+        // ACT: Three 50 SOL buys, the second one reaching the cap
+        for buy_amount in [50, 50, 50] {
+            let points = get_swap_points(buy_amount, &points_epoch, 0).unwrap();
+            let points_emitted = points_epoch.emit_points(points).unwrap();
+
+            record_referral_stats(Some(&mut stats), points_emitted).unwrap();
+        }
+
+        // ASSERT: The capped buy paid nothing and isn't counted as a referral
+        assert_eq!(points_epoch.points_emitted, 60_000);
+        assert_eq!(stats.total_points_earned, 60_000);
+        assert_eq!(stats.referral_count, 2);
+
+        // Without stats passed there's nothing to record
+        record_referral_stats(None, 10_000).unwrap();
+
+        println!("✅ Referrer stats epoch cap test passed!");
+    }
+
+    #[test]
+    fn test_drained_points_pool_skips_points() {
+        // ARRANGE: An empty points pool and an epoch whose rate can't be computed
+        let mut points_epoch = create_test_points_epoch();
+        points_epoch.points_per_sol_denom = 0;
+
+        println!("🧪 Testing buys against a drained points pool");
+
+        // ACT & ASSERT: Nothing to distribute, so the points block is skipped
+        // and the buy goes through instead of failing on the rate
+        assert_eq!(get_clamped_swap_points(0, 100, &points_epoch, 0).unwrap(), 0);
+
+        // With points left the rate is evaluated again
+        assert_eq!(
+            get_clamped_swap_points(10_000, 100, &points_epoch, 0).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        points_epoch.points_per_sol_denom = 1;
+        assert_eq!(
+            get_clamped_swap_points(10_000, 100, &points_epoch, 0).unwrap(),
+            10_000
+        );
+
+        println!("✅ Drained points pool test passed!");
+    }
+
+    #[test]
+    fn test_decaying_points_favor_early_buyers() {
+        // ARRANGE: The same 100 SOL buy early and late in the launch
+        let mut points_epoch = create_test_points_epoch();
+        points_epoch.emission_mode = PointsEmission::Decaying;
+        let mut pool = create_test_pool_with_meme();
+        let buy_amount = 100;
+
+        println!("🧪 Testing decaying points emission");
+
+        // ACT
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        let early = get_swap_points(buy_amount, &points_epoch, pool.percent_sold().unwrap());
+        pool.meme_reserve.tokens = pool.config.gamma_m / 4;
+        let late = get_swap_points(buy_amount, &points_epoch, pool.percent_sold().unwrap());
+
+        // ASSERT: Full rate before anything sold, a quarter once 75% is gone
+        assert_eq!(early.unwrap(), 100_000);
+        assert_eq!(late.unwrap(), 25_000);
+
+        // Nothing left to earn on a sold out curve
+        assert_eq!(get_swap_points(buy_amount, &points_epoch, 10_000).unwrap(), 0);
+
+        // The flat default ignores progress
+        points_epoch.emission_mode = PointsEmission::Flat;
+        assert_eq!(get_swap_points(buy_amount, &points_epoch, 7_500).unwrap(), 100_000);
+
+        println!("✅ Decaying points test passed!");
+    }
+
+    #[test]
+    fn test_decaying_points_set_by_update() {
+        // ARRANGE: A flat epoch and a buy once 75% of the curve is sold
+        let mut points_epoch = create_test_points_epoch();
+        assert_eq!(points_epoch.emission_mode, PointsEmission::Flat);
+        assert_eq!(get_swap_points(100, &points_epoch, 7_500).unwrap(), 100_000);
+
+        println!("🧪 Testing decaying points switched on by the admin");
+
+        // ACT: The admin switches the epoch to decaying emission
+        let mut settings = points_epoch.settings();
+        settings.emission_mode = PointsEmission::Decaying;
+        let previous = points_epoch.update_settings(settings).unwrap();
+
+        // ASSERT: The same late buy now earns a quarter of the rate
+        assert_eq!(previous.emission_mode, PointsEmission::Flat);
+        assert_eq!(get_swap_points(100, &points_epoch, 7_500).unwrap(), 25_000);
+
+        println!("✅ Updated emission mode test passed!");
+    }
+
+    #[test]
+    fn test_only_first_buy_gets_bonus() {
+        // ARRANGE: A 5% first-buyer bonus
+        let mut pool = create_test_pool_with_meme();
+        pool.config.first_buyer_bonus_bps = 500;
+
+        println!("🧪 Testing the first-buyer bonus");
+
+        // ACT
+        let first = pool.claim_first_buyer_bonus(1_000_000).unwrap();
+        let second = pool.claim_first_buyer_bonus(1_000_000).unwrap();
+
+        // ASSERT: Only the first buy is rewarded, and reserves aren't touched
+        assert_eq!(first, 50_000);
+        assert_eq!(second, 0);
+        assert!(pool.first_buy_done);
+        assert_eq!(pool.meme_reserve.tokens, 500_000_000);
+
+        // Without a bonus the first buy still counts as done
+        let mut pool = create_test_pool_with_meme();
+        assert_eq!(pool.claim_first_buyer_bonus(1_000_000).unwrap(), 0);
+        assert!(pool.first_buy_done);
+
+        println!("✅ First-buyer bonus test passed!");
+    }
+
+    #[test]
+    fn test_points_overflow_is_an_error() {
+        // ARRANGE: A points rate no u64 can hold for a whale sized buy
+        let mut points_epoch = create_test_points_epoch();
+        points_epoch.points_per_sol_num = u64::MAX;
+
+        // ACT & ASSERT: Overflow surfaces as an error instead of a panic
+        assert_eq!(
+            get_swap_points(u64::MAX, &points_epoch, 0).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        // So does a misconfigured epoch dividing by zero
+        points_epoch.points_per_sol_num = 1000;
+        points_epoch.points_per_sol_denom = 0;
+        assert_eq!(
+            get_swap_points(100, &points_epoch, 0).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        println!("✅ Points overflow test passed!");
+    }
+
     #[test]
     fn test_zero_amount_error() {
         let coin_in_amount = 0; // This should fail
@@ -493,6 +1100,41 @@ mod tests {
         println!("✅ Insufficient SOL balance test passed!");
     }
 
+    #[test]
+    fn test_underfunded_user_sol_rejected() {
+        let pool = create_test_pool_with_meme();
+        let user_sol = create_test_token_account(pool.quote_reserve.mint, Pubkey::new_unique(), 50);
+
+        // One token short fails before any transfer is attempted
+        assert_eq!(
+            check_sol_balance(user_sol.amount, 51).unwrap_err(),
+            error!(AmmError::InsufficientBalance)
+        );
+
+        // Spending the whole balance is fine
+        assert!(check_sol_balance(user_sol.amount, 50).is_ok());
+
+        println!("✅ Underfunded user_sol test passed!");
+    }
+
+    #[test]
+    fn test_max_coin_in_amount() {
+        println!("🧪 Testing the caller's max_coin_in_amount rail");
+
+        // An extra zero on a 10 SOL buy is caught
+        assert_eq!(
+            check_max_coin_in(100_000_000_000, 10_000_000_000).unwrap_err(),
+            error!(AmmError::AmountExceedsMax)
+        );
+
+        // Up to the limit goes through, as does anything without one
+        check_max_coin_in(10_000_000_000, 10_000_000_000).unwrap();
+        check_max_coin_in(9_999_999_999, 10_000_000_000).unwrap();
+        check_max_coin_in(u64::MAX, 0).unwrap();
+
+        println!("✅ max_coin_in_amount test passed!");
+    }
+
     #[test]
     fn test_account_mint_validation() {
         let pool = create_test_pool_with_meme();
@@ -558,6 +1200,56 @@ mod tests {
         println!("✅ Referrer constraint test passed!");
     }
 
+    #[test]
+    fn test_referrer_account_aliasing_rejected() {
+        let points_acc = Pubkey::new_unique();
+        let user_points = Pubkey::new_unique();
+        let referrer_points = Pubkey::new_unique();
+
+        // No referrer, or a distinct one, is fine
+        assert!(referrer_account_valid(None, points_acc, user_points));
+        assert!(referrer_account_valid(
+            Some(referrer_points),
+            points_acc,
+            user_points
+        ));
+
+        // Passing the points pool or the user's own account as referrer is not
+        assert!(!referrer_account_valid(
+            Some(points_acc),
+            points_acc,
+            user_points
+        ));
+        assert!(!referrer_account_valid(
+            Some(user_points),
+            points_acc,
+            user_points
+        ));
+
+        println!("✅ Referrer aliasing test passed!");
+    }
+
+    #[test]
+    fn test_referral_code_resolves_referrer() {
+        // ARRANGE: "alice" registered by Alice's wallet
+        let alice = Pubkey::new_unique();
+        let code = ReferralCode {
+            owner: alice,
+            code: "alice".to_string(),
+        };
+
+        println!("🧪 Testing referral code resolution");
+
+        // ACT & ASSERT: The code resolves to Alice's points account only
+        assert!(referrer_matches_code(code.owner, Some(alice)));
+        assert!(!referrer_matches_code(code.owner, Some(Pubkey::new_unique())));
+
+        // A code without a referrer account to pay has nothing to resolve to
+        assert!(!referrer_matches_code(code.owner, None));
+
+        println!("✅ Referral code resolution test passed!");
+    }
+
     #[test]
     fn test_pda_derivation() {
         let pool_key = Pubkey::new_unique();
@@ -599,6 +1291,51 @@ mod tests {
 
         println!("✅ Integration test framework ready!");
     }
+
+    #[test]
+    fn test_residual_buy_after_migration() {
+        // ARRANGE: A migrated pool burning its residual fees, with a meme fee
+        let mut pool = migrated_test_pool(ResidualFeeMode::Burn);
+        pool.fees.fee_meme_percent = FEE;
+        let (meme_before, quote_before) = (pool.meme_reserve.tokens, pool.quote_reserve.tokens);
+        let price_before = pool.spot_price().unwrap();
+
+        println!("🧪 Testing a buy against a migrated pool's residual reserves");
+
+        // ACT: Quote and book the buy the way the swap_y handler does
+        pool.check_tradeable().unwrap();
+        let swap = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        let burned = pool
+            .accrue_swap_fees(swap.admin_fee_in, swap.admin_fee_out)
+            .unwrap();
+        pool.quote_reserve.tokens += swap.amount_in;
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+
+        // ASSERT: Priced off the reserves, whose product never shrinks
+        assert!(swap.amount_out > 0);
+        assert!(pool.spot_price().unwrap() > price_before);
+        assert!(
+            pool.meme_reserve.tokens as u128 * pool.quote_reserve.tokens as u128
+                >= meme_before as u128 * quote_before as u128
+        );
+
+        // The meme fee is burned and the quote fee deepens the reserve
+        assert!(burned > 0);
+        assert_eq!(burned, swap.admin_fee_out);
+        assert_eq!(
+            pool.quote_reserve.tokens,
+            quote_before + swap.amount_in + swap.admin_fee_in
+        );
+        assert_eq!((pool.admin_fees_quote, pool.admin_fees_meme), (0, 0));
+
+        // Exact-out buys only run on the curve
+        assert_eq!(
+            pool.buy_meme_exact_out_amounts(1_000_000, 0).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Residual buy test passed!");
+    }
 }
 
 /// Additional test utilities for swap Y
@@ -0,0 +1,33 @@
+use crate::endpoints::swap_y::{execute_buy, SwapCoinY};
+use anchor_lang::prelude::*;
+
+// Handler function for several back to back buys in one instruction
+//
+// # Arguments
+// * `ctx` - The context containing all required accounts
+// * `amounts` - SOL to spend on each buy, at most 16 entries
+// * `min_outs` - Minimum meme tokens to receive from each buy
+pub fn handle(mut ctx: Context<SwapCoinY>, amounts: Vec<u64>, min_outs: Vec<u64>) -> Result<()> {
+    // Get accounts from context
+    let accs = &mut ctx.accounts;
+
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Every buy of the batch has to clear the dust floor on its own
+    for &amount in &amounts {
+        accs.pool.check_min_trade(amount, true)?;
+    }
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
+
+    // Quote every buy against the curve left by the previous one
+    let swap_amount = accs
+        .quoting_pool()
+        .buy_meme_batch_amounts(&amounts, &min_outs)?;
+
+    // Settle the whole batch as a single buy; it never spends more than the
+    // amounts given, so there's no separate limit to hold it to
+    execute_buy(&mut ctx, swap_amount, 0)
+}
@@ -0,0 +1,37 @@
+use crate::endpoints::swap_y::{execute_buy, SwapCoinY};
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+// Handler function for buying an exact amount of meme tokens
+//
+// # Arguments
+// * `ctx` - The context containing all required accounts
+// * `coin_out_amount` - Amount of meme tokens to receive
+// * `max_sol_in` - Maximum amount of SOL to spend, fees included
+pub fn handle(mut ctx: Context<SwapCoinY>, coin_out_amount: u64, max_sol_in: u64) -> Result<()> {
+    // Get accounts from context
+    let accs = &mut ctx.accounts;
+
+    // Check that requested output is not zero
+    if coin_out_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
+
+    // Calculate the SOL required for the requested output
+    let swap_amount = accs
+        .quoting_pool()
+        .buy_meme_exact_out_amounts(coin_out_amount, max_sol_in)?;
+
+    // Refuse dust buys, judged by the SOL they pay in
+    accs.pool
+        .check_min_trade(swap_amount.amount_in + swap_amount.admin_fee_in, true)?;
+
+    // The SOL limit already bounded the quote, hold the transfer to it too
+    execute_buy(&mut ctx, swap_amount, max_sol_in)
+}
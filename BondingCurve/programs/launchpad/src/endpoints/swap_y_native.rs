@@ -0,0 +1,227 @@
+use crate::endpoints::swap_y::{check_sol_balance, execute_buy, SwapCoinY};
+use crate::err::AmmError;
+use crate::models::SwapAmount;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token::{self, spl_token, CloseAccount, SyncNative};
+
+// Handler function for buying meme tokens with native SOL
+//
+// `user_sol` is a temporary WSOL account owned by the signer, usually created
+// earlier in the same transaction. The lamports are wrapped into it, the buy
+// runs as usual and the account is closed, so its rent and any unspent SOL go
+// back to the signer.
+//
+// # Arguments
+// * `ctx` - The context containing all required accounts
+// * `coin_in_amount` - Amount of native SOL to swap
+// * `coin_x_min_value` - Minimum amount of meme tokens to receive
+// * `max_coin_in_amount` - Most SOL the caller meant to spend (0 = no limit)
+pub fn handle(
+    mut ctx: Context<SwapCoinY>,
+    coin_in_amount: u64,
+    coin_x_min_value: u64,
+    max_coin_in_amount: u64,
+) -> Result<()> {
+    // Get accounts from context
+    let accs = &mut ctx.accounts;
+
+    // Check that input amount is not zero
+    if coin_in_amount == 0 {
+        return Err(error!(AmmError::NoZeroTokens));
+    }
+
+    // Only pools quoted in SOL can be paid with lamports
+    if accs.pool.quote_reserve.mint != spl_token::native_mint::ID {
+        return Err(error!(AmmError::InvalidTokenMints));
+    }
+
+    // The temporary account gets closed, so it has to be the signer's own
+    if accs.user_sol.owner != accs.owner.key() {
+        return Err(error!(AmmError::InvalidAccountInput));
+    }
+
+    // Check that pool is open for trading
+    accs.pool.check_tradeable()?;
+
+    // Refuse dust buys
+    accs.pool.check_min_trade(coin_in_amount, true)?;
+
+    // Enforce the per-pool minimum slot distance between trades
+    accs.pool.check_trade_slot(Clock::get()?.slot)?;
+
+    // Calculate swap amounts
+    let swap_amount = accs
+        .quoting_pool()
+        .swap_amounts(coin_in_amount, coin_x_min_value, true)?;
+
+    // Fail with a clear error instead of inside the system program
+    check_sol_balance(accs.owner.lamports(), coin_in_amount)?;
+
+    // Wrap the lamports into the temporary account
+    system_program::transfer(
+        CpiContext::new(
+            accs.system_program.to_account_info(),
+            system_program::Transfer {
+                from: accs.owner.to_account_info(),
+                to: accs.user_sol.to_account_info(),
+            },
+        ),
+        coin_in_amount,
+    )?;
+    // A plain lamport transfer doesn't move the token amount; sync it so the
+    // SPL transfer in the buy sees the deposit
+    token::sync_native(CpiContext::new(
+        accs.token_program.to_account_info(),
+        SyncNative {
+            account: accs.user_sol.to_account_info(),
+        },
+    ))?;
+    accs.user_sol.reload()?;
+    check_wrapped_amount(accs.user_sol.amount, coin_in_amount)?;
+
+    let refund = native_buy_refund(coin_in_amount, &swap_amount);
+
+    execute_buy(&mut ctx, swap_amount, max_coin_in_amount)?;
+
+    msg!("Unwrapping {} unspent lamports", refund);
+
+    // Close the temporary account, returning rent and leftovers to the signer
+    let accs = &ctx.accounts;
+    token::close_account(CpiContext::new(
+        accs.token_program.to_account_info(),
+        CloseAccount {
+            account: accs.user_sol.to_account_info(),
+            destination: accs.owner.to_account_info(),
+            authority: accs.owner.to_account_info(),
+        },
+    ))
+}
+
+// Checks the temporary account's synced token amount covers the wrapped
+// lamports, so the buy never books SOL the account can't hand over
+//
+// # Arguments
+// * `synced` - Token amount of the WSOL account after `sync_native`
+// * `wrapped` - Lamports wrapped for this buy
+pub fn check_wrapped_amount(synced: u64, wrapped: u64) -> Result<()> {
+    if synced < wrapped {
+        return Err(error!(AmmError::WsolNotSynced));
+    }
+
+    Ok(())
+}
+
+// Lamports wrapped for a native buy but not spent on it, e.g. when the buy is
+// capped at the curve's remaining supply
+//
+// # Arguments
+// * `wrapped` - Lamports wrapped before the buy
+// * `swap_amount` - The quoted buy amounts
+pub fn native_buy_refund(wrapped: u64, swap_amount: &SwapAmount) -> u64 {
+    wrapped.saturating_sub(swap_amount.amount_in + swap_amount.admin_fee_in)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::bound::{test_pool, BoundPool};
+    use crate::models::Reserve;
+
+    // SOL quoted pool on the default curve, `quote_tokens` already raised
+    fn create_native_pool(quote_tokens: u64) -> BoundPool {
+        let pool = test_pool();
+        let mut pool = BoundPool {
+            quote_reserve: Reserve {
+                mint: spl_token::native_mint::ID,
+                ..pool.quote_reserve
+            },
+            ..pool
+        };
+
+        // Move along the curve to the requested raise
+        if quote_tokens > 0 {
+            let sold = pool.compute_delta_m(0, quote_tokens).unwrap();
+            pool.meme_reserve.tokens -= sold;
+            pool.quote_reserve.tokens = quote_tokens;
+        }
+
+        pool
+    }
+
+    #[test]
+    fn test_native_buy_lamport_flow() {
+        // ARRANGE: A signer wrapping 1 SOL into a fresh WSOL account
+        let rent = 2_039_280;
+        let wrapped = 1_000_000_000;
+        let start_lamports = 10_000_000_000u64;
+        let pool = create_native_pool(0);
+
+        println!("🧪 Testing the native SOL buy lamport flow");
+
+        // ACT: Create the account, wrap, buy and close it again
+        let swap = pool.swap_amounts(wrapped, 0, true).unwrap();
+        let mut wallet = start_lamports - rent - wrapped;
+        let temp_account = rent + wrapped - (swap.amount_in + swap.admin_fee_in);
+        wallet += temp_account;
+
+        // ASSERT: The signer paid exactly the buy, rent came back
+        assert_eq!(native_buy_refund(wrapped, &swap), 0);
+        assert_eq!(start_lamports - wallet, swap.amount_in + swap.admin_fee_in);
+        assert!(swap.amount_out > 0);
+
+        println!("✅ Native buy lamport flow test passed!");
+    }
+
+    #[test]
+    fn test_native_buy_synced_amount_matches_state() {
+        // ARRANGE: A fresh WSOL account holding only rent, then 1 SOL wrapped
+        let rent = 2_039_280u64;
+        let wrapped = 1_000_000_000u64;
+        let mut pool = create_native_pool(0);
+        let account_lamports = rent + wrapped;
+
+        println!("🧪 Testing the synced WSOL amount against pool state");
+
+        // ACT: sync_native sets the token amount to lamports above rent
+        let synced = account_lamports - rent;
+        let swap = pool.swap_amounts(wrapped, 0, true).unwrap();
+        let quote_before = pool.quote_reserve.tokens;
+        pool.quote_reserve.tokens += swap.amount_in;
+        let left_in_account = synced - swap.amount_in - swap.admin_fee_in;
+
+        // ASSERT: The synced amount covers the buy and what the pool booked
+        // is exactly what left the account, less the admin fee
+        assert!(check_wrapped_amount(synced, wrapped).is_ok());
+        assert_eq!(
+            synced - left_in_account,
+            pool.quote_reserve.tokens - quote_before + swap.admin_fee_in
+        );
+        assert_eq!(left_in_account, native_buy_refund(wrapped, &swap));
+
+        // An unsynced account still reads 0 and must be refused
+        assert_eq!(
+            check_wrapped_amount(0, wrapped).unwrap_err(),
+            error!(AmmError::WsolNotSynced)
+        );
+
+        println!("✅ Synced WSOL amount test passed!");
+    }
+
+    #[test]
+    fn test_native_buy_refunds_capped_fill() {
+        // ARRANGE: 1 SOL short of the target, so a 10 SOL buy gets capped
+        let pool = create_native_pool(84_000_000_000);
+        let wrapped = 10_000_000_000;
+
+        // ACT
+        let swap = pool.swap_amounts(wrapped, 0, true).unwrap();
+        let refund = native_buy_refund(wrapped, &swap);
+
+        // ASSERT: What the capped buy didn't spend is unwrapped on close
+        assert!(refund > 0);
+        assert_eq!(refund + swap.amount_in + swap.admin_fee_in, wrapped);
+
+        println!("✅ Native buy capped refund test passed!");
+    }
+}
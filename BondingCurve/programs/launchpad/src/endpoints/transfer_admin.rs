@@ -0,0 +1,24 @@
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Proposes `new_admin` as the next admin. The role only moves once that
+/// wallet signs `accept_admin`; proposing the default key withdraws the
+/// proposal.
+pub fn handle(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.global_config
+        .propose_admin(&accs.admin.key(), new_admin)?;
+
+    msg!("admin_proposed: {}", new_admin);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct TransferAdmin<'info> {
+    pub admin: Signer<'info>,
+
+    #[account(mut, seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+}
@@ -0,0 +1,87 @@
+use crate::err::AmmError;
+use crate::models::stake_account::StakeAccount;
+use crate::models::staking::StakingPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> Unstake<'info> {
+    /// Creates a CPI context for returning staked tokens, and the airdrop
+    /// share paid with them, from the staking vault to the staker.
+    fn send_meme_to_user(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.staking_meme_vault.to_account_info(),
+            to: self.user_meme.to_account_info(),
+            authority: self.staking_pool_signer_pda.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Withdraws `amount` staked meme tokens, along with their proportional share
+/// of the remaining `to_airdrop` allocation while the airdrop window is open.
+/// The quote rewards they earned stay claimable through `claim_staking_rewards`.
+///
+/// # Errors
+/// * `AmmError::NoZeroTokens` - If unstaking 0 tokens
+/// * `AmmError::InsufficientBalance` - If fewer tokens are staked
+pub fn handle(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    let now = Clock::get()?.unix_timestamp;
+    let airdrop_share = accs.staking.unstake(&mut accs.stake_account, amount, now)?;
+
+    let staking_seeds = &[
+        StakingPool::SIGNER_PDA_PREFIX,
+        &accs.staking.key().to_bytes()[..],
+        &[ctx.bumps.staking_pool_signer_pda],
+    ];
+
+    let staking_signer_seeds = &[&staking_seeds[..]];
+
+    token::transfer(
+        accs.send_meme_to_user().with_signer(staking_signer_seeds),
+        amount + airdrop_share,
+    )?;
+
+    msg!("unstaked: {}\n airdrop_share: {}", amount, airdrop_share);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct Unstake<'info> {
+    pub owner: Signer<'info>,
+
+    #[account(mut)]
+    pub staking: Box<Account<'info, StakingPool>>,
+
+    #[account(
+        mut,
+        seeds = [StakeAccount::STAKE_ACCOUNT_PREFIX, staking.key().as_ref(), owner.key().as_ref()],
+        bump,
+        constraint = stake_account.owner == owner.key() @ AmmError::InvalidAccountInput
+    )]
+    pub stake_account: Box<Account<'info, StakeAccount>>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [StakingPool::SIGNER_PDA_PREFIX, staking.key().as_ref()], bump)]
+    pub staking_pool_signer_pda: AccountInfo<'info>,
+
+    #[account(
+        mut,
+        constraint = staking_meme_vault.owner == staking_pool_signer_pda.key()
+            @ AmmError::InvalidAccountInput
+    )]
+    pub staking_meme_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(
+        mut,
+        constraint = user_meme.mint == staking_meme_vault.mint @ AmmError::InvalidTokenMints,
+        constraint = user_meme.owner == owner.key()
+    )]
+    pub user_meme: Box<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -0,0 +1,59 @@
+use crate::err;
+use crate::models::bound::BoundPool;
+use crate::models::fees::Fees;
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Replaces the fees of a live pool. Both rates are capped at 5% and the
+/// pool must not have migrated yet.
+///
+/// # Arguments
+/// * `ctx` - The context containing the pool
+/// * `fee_quote_percent` - New quote fee in `FEE_PRECISION` units
+/// * `fee_meme_percent` - New meme fee in `FEE_PRECISION` units
+pub fn handle(
+    ctx: Context<UpdateFees>,
+    fee_quote_percent: u64,
+    fee_meme_percent: u64,
+) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    let previous = pool.update_fees(Fees {
+        fee_meme_percent,
+        fee_quote_percent,
+    })?;
+
+    emit!(FeesUpdated {
+        pool: pool.key(),
+        old_fee_quote_percent: previous.fee_quote_percent,
+        old_fee_meme_percent: previous.fee_meme_percent,
+        fee_quote_percent,
+        fee_meme_percent,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateFees<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can update fees")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+}
+
+#[event]
+pub struct FeesUpdated {
+    pub pool: Pubkey,
+    pub old_fee_quote_percent: u64,
+    pub old_fee_meme_percent: u64,
+    pub fee_quote_percent: u64,
+    pub fee_meme_percent: u64,
+}
@@ -0,0 +1,135 @@
+use crate::endpoints::create_metadata::validate_uri;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::metadata::mpl_token_metadata::types::{Creator, DataV2};
+use anchor_spl::metadata::{
+    update_metadata_accounts_v2, Metadata, MetadataAccount, UpdateMetadataAccountsV2,
+};
+
+impl<'info> UpdateMetadata<'info> {
+    fn update_metadata_account_v2(
+        &self,
+    ) -> CpiContext<'_, '_, '_, 'info, UpdateMetadataAccountsV2<'info>> {
+        let cpi_accounts = UpdateMetadataAccountsV2 {
+            metadata: self.meme_mpl_metadata.to_account_info(),
+            update_authority: self.pool_signer.to_account_info(),
+        };
+        let cpi_program = self.metadata_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Builds the data for a URI-only update. Everything else is carried over
+/// from the current metadata, with Metaplex's null padding stripped.
+pub fn uri_update_data(
+    name: &str,
+    symbol: &str,
+    seller_fee_basis_points: u16,
+    creators: Option<Vec<Creator>>,
+    new_uri: String,
+) -> DataV2 {
+    DataV2 {
+        name: name.trim_end_matches('\0').to_string(),
+        symbol: symbol.trim_end_matches('\0').to_string(),
+        uri: new_uri,
+        seller_fee_basis_points,
+        creators,
+        collection: None,
+        uses: None,
+    }
+}
+
+/// Replaces the token's metadata URI, leaving name and symbol untouched.
+///
+/// # Errors
+/// * `AmmError::PoolAlreadyMigrated` - If the pool has graduated; metadata is frozen from then on
+/// * `AmmError::MetadataFieldTooLong` - If `new_uri` exceeds the Metaplex limit
+pub fn handle(ctx: Context<UpdateMetadata>, new_uri: String) -> Result<()> {
+    let accs = ctx.accounts;
+
+    if accs.pool.pool_migration {
+        return Err(error!(AmmError::PoolAlreadyMigrated));
+    }
+
+    validate_uri(&new_uri)?;
+
+    let current = &accs.meme_mpl_metadata;
+    let data = uri_update_data(
+        &current.name,
+        &current.symbol,
+        current.seller_fee_basis_points,
+        current.creators.clone(),
+        new_uri,
+    );
+
+    let pool_key = accs.pool.key();
+    let seeds: &[&[u8]] = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        pool_key.as_ref(),
+        &[ctx.bumps.pool_signer],
+    ];
+
+    update_metadata_accounts_v2(
+        accs.update_metadata_account_v2().with_signer(&[seeds]),
+        None, // new_update_authority
+        Some(data),
+        None, // primary_sale_happened
+        None, // is_mutable
+    )?;
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    pub creator: Signer<'info>,
+    #[account(constraint = pool.is_creator(&creator.key()) @ AmmError::InvalidAccountInput)]
+    pub pool: Account<'info, BoundPool>,
+
+    /// Metaplex metadata of the pool's meme mint
+    #[account(
+        mut,
+        constraint = meme_mpl_metadata.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints
+    )]
+    pub meme_mpl_metadata: Box<Account<'info, MetadataAccount>>,
+
+    /// CHECK: pool_pda, the metadata update authority
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+    /// Program owning NFT metadata
+    pub metadata_program: Program<'info, Metadata>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_uri_update_keeps_name_and_symbol() {
+        // Metaplex stores name/symbol padded with nulls
+        let name = format!("{:\0<32}", "Doge Coin");
+        let symbol = format!("{:\0<10}", "DOGE");
+        let creators = Some(vec![Creator {
+            address: Pubkey::new_unique(),
+            verified: true,
+            share: 100,
+        }]);
+
+        let data = uri_update_data(
+            &name,
+            &symbol,
+            0,
+            creators.clone(),
+            "https://example.com/fixed.json".to_string(),
+        );
+
+        assert_eq!(data.uri, "https://example.com/fixed.json");
+        assert_eq!(data.name, "Doge Coin");
+        assert_eq!(data.symbol, "DOGE");
+        assert_eq!(data.seller_fee_basis_points, 0);
+        assert_eq!(data.creators, creators);
+
+        println!("✅ URI-only metadata update test passed!");
+    }
+}
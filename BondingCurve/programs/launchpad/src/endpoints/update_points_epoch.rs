@@ -0,0 +1,48 @@
+use crate::consts::POINTS_EPOCH_SEED;
+use crate::err;
+use crate::models::global_config::GlobalConfig;
+use crate::models::points_epoch::{PointsEpoch, PointsEpochSettings};
+use anchor_lang::prelude::*;
+
+/// Replaces the points rules of the points epoch. Raising `epoch_number`
+/// starts a new epoch, resetting the points emitted under its cap.
+///
+/// # Arguments
+/// * `ctx` - The context containing the points epoch
+/// * `settings` - The new points rules
+///
+/// # Errors
+/// * `AmmError::InvalidArg` - If the epoch number goes back or the rate has a 0 denominator
+pub fn handle(ctx: Context<UpdatePointsEpoch>, settings: PointsEpochSettings) -> Result<()> {
+    let points_epoch = &mut ctx.accounts.points_epoch;
+
+    let previous = points_epoch.update_settings(settings)?;
+
+    emit!(PointsEpochUpdated {
+        old_settings: previous,
+        settings,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePointsEpoch<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can update the points epoch")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut, seeds = [POINTS_EPOCH_SEED], bump)]
+    pub points_epoch: Account<'info, PointsEpoch>,
+}
+
+#[event]
+pub struct PointsEpochUpdated {
+    pub old_settings: PointsEpochSettings,
+    pub settings: PointsEpochSettings,
+}
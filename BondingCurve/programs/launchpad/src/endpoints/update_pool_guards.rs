@@ -0,0 +1,46 @@
+use crate::err;
+use crate::models::bound::{BoundPool, PoolGuards};
+use crate::models::global_config::GlobalConfig;
+use anchor_lang::prelude::*;
+
+/// Replaces the optional trading guards of a pool which hasn't migrated.
+/// A zero value disables the guard, as at launch.
+///
+/// # Arguments
+/// * `ctx` - The context containing the pool
+/// * `guards` - The new guards
+pub fn handle(ctx: Context<UpdatePoolGuards>, guards: PoolGuards) -> Result<()> {
+    let pool = &mut ctx.accounts.pool;
+
+    let previous = pool.update_guards(guards)?;
+
+    emit!(PoolGuardsUpdated {
+        pool: pool.key(),
+        old_guards: previous,
+        guards,
+    });
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct UpdatePoolGuards<'info> {
+    #[account(
+        constraint = global_config.is_admin(&sender.key())
+            @ err::acc("Only the admin can update pool guards")
+    )]
+    pub sender: Signer<'info>,
+
+    #[account(seeds = [GlobalConfig::GLOBAL_CONFIG_PREFIX], bump)]
+    pub global_config: Account<'info, GlobalConfig>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+}
+
+#[event]
+pub struct PoolGuardsUpdated {
+    pub pool: Pubkey,
+    pub old_guards: PoolGuards,
+    pub guards: PoolGuards,
+}
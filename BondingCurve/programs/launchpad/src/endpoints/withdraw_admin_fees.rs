@@ -0,0 +1,112 @@
+use crate::consts::BP_FEE_KEY;
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> WithdrawAdminFees<'info> {
+    fn send_quote_fees(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: self.fee_quote_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+
+    fn send_meme_fees(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.meme_vault.to_account_info(),
+            to: self.fee_meme_vault.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Sweeps part of the accrued admin fees to the fee vaults.
+///
+/// # Arguments
+/// * `quote_amount` - Quote fees to withdraw, at most `admin_fees_quote`
+/// * `meme_amount` - Meme fees to withdraw, at most `admin_fees_meme`
+///
+/// # Errors
+/// * `AmmError::InsufficientAccruedFees` - If either amount exceeds what has accrued
+pub fn handle(ctx: Context<WithdrawAdminFees>, quote_amount: u64, meme_amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.pool.take_admin_fees(quote_amount, meme_amount)?;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    if quote_amount > 0 {
+        token::transfer(
+            accs.send_quote_fees().with_signer(signer_seeds),
+            quote_amount,
+        )?;
+    }
+
+    if meme_amount > 0 {
+        token::transfer(accs.send_meme_fees().with_signer(signer_seeds), meme_amount)?;
+    }
+
+    msg!(
+        "withdrawn_quote_fees: {}\n withdrawn_meme_fees: {}",
+        quote_amount,
+        meme_amount
+    );
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawAdminFees<'info> {
+    #[account(constraint = admin.key() == BP_FEE_KEY @ err::acc("Only the fee key can withdraw fees"))]
+    pub admin: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.meme_reserve.vault == meme_vault.key()
+    )]
+    pub meme_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = pool.fee_vault_quote == fee_quote_vault.key()
+    )]
+    pub fee_quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = fee_meme_vault.mint == pool.meme_reserve.mint @ AmmError::InvalidTokenMints,
+        constraint = fee_meme_vault.owner == BP_FEE_KEY
+            @ err::acc("Fee meme vault authority must match fee key"),
+    )]
+    pub fee_meme_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
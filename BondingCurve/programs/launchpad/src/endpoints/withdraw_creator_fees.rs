@@ -0,0 +1,77 @@
+use crate::err;
+use crate::err::AmmError;
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::{self, Token, TokenAccount, Transfer};
+
+impl<'info> WithdrawCreatorFees<'info> {
+    fn send_quote_fees(&self) -> CpiContext<'_, '_, '_, 'info, Transfer<'info>> {
+        let cpi_accounts = Transfer {
+            from: self.quote_vault.to_account_info(),
+            to: self.creator_quote_account.to_account_info(),
+            authority: self.pool_signer.to_account_info(),
+        };
+
+        let cpi_program = self.token_program.to_account_info();
+        CpiContext::new(cpi_program, cpi_accounts)
+    }
+}
+
+/// Sends part of the creator's accrued quote fees to the creator.
+///
+/// # Arguments
+/// * `quote_amount` - Quote fees to withdraw, at most `creator_fees_quote`
+///
+/// # Errors
+/// * `AmmError::InsufficientAccruedFees` - If the amount exceeds what has accrued
+pub fn handle(ctx: Context<WithdrawCreatorFees>, quote_amount: u64) -> Result<()> {
+    let accs = ctx.accounts;
+
+    accs.pool.take_creator_fees(quote_amount)?;
+
+    let seeds = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        &accs.pool.key().to_bytes()[..],
+        &[ctx.bumps.pool_signer],
+    ];
+
+    let signer_seeds = &[&seeds[..]];
+
+    if quote_amount > 0 {
+        token::transfer(
+            accs.send_quote_fees().with_signer(signer_seeds),
+            quote_amount,
+        )?;
+    }
+
+    msg!("withdrawn_creator_fees: {}", quote_amount);
+
+    Ok(())
+}
+
+#[derive(Accounts)]
+pub struct WithdrawCreatorFees<'info> {
+    #[account(constraint = pool.is_creator(&creator.key()) @ err::acc("Only the pool creator can withdraw creator fees"))]
+    pub creator: Signer<'info>,
+
+    #[account(mut)]
+    pub pool: Account<'info, BoundPool>,
+
+    #[account(
+        mut,
+        constraint = pool.quote_reserve.vault == quote_vault.key()
+    )]
+    pub quote_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_quote_account.mint == pool.quote_reserve.mint @ AmmError::InvalidTokenMints,
+    )]
+    pub creator_quote_account: Account<'info, TokenAccount>,
+
+    /// CHECK: pda signer
+    #[account(seeds = [BoundPool::SIGNER_PDA_PREFIX, pool.key().as_ref()], bump)]
+    pub pool_signer: AccountInfo<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
@@ -59,15 +59,112 @@ pub enum AmmError {
     AirdroppedTokensOvercap,
     InvalidVestingPeriod,
     #[msg("Migration threshold not reached - need 80% of tokens sold")]
-    MigrationThresholdNotReached,
+    MigrationThresholdNotMet,
     #[msg("Pool has already been migrated to Raydium")]
     AlreadyMigrated,
 
-    #[msg("Invalid token order - token_0 must be smaller than token_1")]
-    InvalidTokenOrder,
+    #[msg("Invalid mint ordering - meme mint must be smaller than quote mint")]
+    InvalidMintOrdering,
 
     #[msg("Pool already migrated")]
     PoolAlreadyMigrated,
+
+    #[msg("Not enough slots passed since the last trade on this pool")]
+    TradeTooSoon,
+
+    #[msg("Creator allocation is still locked")]
+    CreatorAllocationLocked,
+    CreatorLockedTokensOvercap,
+    #[msg("Price factor must be between 1 and 100")]
+    PriceFactorOutOfRange,
+    #[msg("Requested amount exceeds the pool reserve")]
+    ExceedsPoolReserve,
+    #[msg("Metadata name, symbol or uri exceeds the Metaplex length limit")]
+    MetadataFieldTooLong,
+    #[msg("Metadata name and symbol must not be empty")]
+    MetadataFieldEmpty,
+    #[msg("Requested withdrawal exceeds the accrued admin fees")]
+    InsufficientAccruedFees,
+    #[msg("Not enough quote liquidity to seed the AMM pool")]
+    InsufficientMigrationLiquidity,
+    #[msg("Configured quote decimals don't match the quote mint")]
+    DecimalsMismatch,
+    #[msg("Too many entries in a batched swap")]
+    BatchTooLarge,
+    #[msg("Swap moves the price more than the pool allows")]
+    PriceImpactTooHigh,
+    #[msg("Referrer points account can't be the points pool or the user's account")]
+    InvalidReferrerAccount,
+    #[msg("Airdrop amounts exceed what is left to airdrop")]
+    AirdropExceedsAllocation,
+    #[msg("Fee percent above the allowed maximum")]
+    FeeTooHigh,
+    #[msg("Nothing is staked to distribute rewards to")]
+    NoStakers,
+    #[msg("Pool signer doesn't match the pool's signer PDA")]
+    InvalidPoolSigner,
+    #[msg("Decimals must be powers of ten within the supported range")]
+    InvalidDecimalsConfig,
+    #[msg("Curve parameters don't sell the trading supply at the quote target")]
+    CurveParameterMismatch,
+    #[msg("Virtual reserves leave no curve to trade on")]
+    InvalidVirtualReserves,
+    #[msg("Launch can't be aborted once trading has happened")]
+    LaunchAlreadyTraded,
+    #[msg("Trade is below the pool's minimum size")]
+    BelowMinimumTrade,
+    #[msg("Pool account layout version can't be upgraded")]
+    UnsupportedPoolVersion,
+    #[msg("Vault balances no longer cover the pool's reserves and fees")]
+    ReserveInvariantViolated,
+    #[msg("Wallet traded on this pool too recently")]
+    WalletCooldown,
+    #[msg("Airdrop window is closed")]
+    AirdropWindowClosed,
+    #[msg("Airdrop window hasn't expired yet")]
+    AirdropNotExpired,
+    #[msg("Referral codes are 1 to 16 printable ASCII characters")]
+    InvalidReferralCode,
+    #[msg("Wrapped SOL account doesn't reflect the deposited lamports")]
+    WsolNotSynced,
+    #[msg("Creator launched a pool too recently")]
+    CreationRateLimited,
+    #[msg("Meme reserve exceeds the trading supply")]
+    MemeReserveExceedsSupply,
+    #[msg("Spot price hasn't crossed the limit order's trigger")]
+    LimitOrderNotTriggered,
+    #[msg("The pool's reserve vaults can't be recovered from")]
+    ReserveVaultNotRecoverable,
+    #[msg("No migration backend serves this target")]
+    MigrationTargetUnsupported,
+    #[msg("Pool migrates to a different target")]
+    MigrationTargetMismatch,
+    #[msg("Seeded AMM price strays too far from the curve's spot price")]
+    MigrationPriceDeviation,
+    #[msg("Pool registry page is full")]
+    RegistryPageFull,
+    #[msg("Pool registry page isn't the current one")]
+    InvalidRegistryPage,
+    #[msg("Pool vaults weren't funded as expected at creation")]
+    PoolNotProperlyFunded,
+    #[msg("First buyer bonus exceeds the maximum")]
+    FirstBuyerBonusOvercap,
+    #[msg("Raydium accounts don't match the ones derived for this pool")]
+    InvalidRaydiumAccounts,
+    #[msg("Signer isn't the program admin")]
+    NotAdmin,
+    #[msg("Signer isn't the proposed admin")]
+    NotPendingAdmin,
+    #[msg("Signer isn't the proposed creator")]
+    NotPendingCreator,
+    #[msg("Share of unsold meme burned at migration exceeds the maximum")]
+    BurnUnsoldOvercap,
+    #[msg("Referral points are still vesting")]
+    PointsStillVesting,
+    #[msg("Pool doesn't have enough distinct buyers to migrate")]
+    InsufficientHolders,
+    #[msg("Swap amount exceeds the caller's maximum")]
+    AmountExceedsMax,
 }
 
 #[allow(dead_code)]
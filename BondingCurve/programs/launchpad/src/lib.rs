@@ -3,9 +3,15 @@ mod endpoints;
 mod err;
 mod libraries;
 mod math;
+mod migration;
 mod models;
 
 use crate::endpoints::*;
+use crate::models::bound::{
+    CurveType, GraduationMode, MigrationTarget, PoolGuards, ResidualFeeMode,
+};
+use crate::models::points_epoch::PointsEpochSettings;
+use crate::models::limit_order::LimitDirection;
 use anchor_lang::prelude::*;
 use core as core_;
 
@@ -30,8 +36,90 @@ pub mod launchpad {
     ///
     /// # Arguments
     /// * `airdropped_tokens` - Amount of tokens reserved for airdrops (max 100M)
-    pub fn new_pool(ctx: Context<NewPool>, airdropped_tokens: u64) -> Result<()> {
-        new_pool::handle(ctx, airdropped_tokens)
+    /// * `creator_locked_tokens` - Trading tokens locked for the creator (max 50M)
+    /// * `creator_lock_period` - Lock duration in seconds (1-13 days)
+    /// * `creator_vesting_period` - Linear vesting after the lock in seconds (max 365 days)
+    /// * `graduation_mode` - Migrate on quote raised (`QuotePercent`) or tokens sold (`TokenTarget`)
+    /// * `fee_meme_percent` - Meme token fee, 1e9 = 100% (max 5%)
+    /// * `virtual_sol_reserves` - Virtual SOL raising the opening price (0 disables)
+    /// * `virtual_meme_reserves` - Meme held off the curve at the virtual SOL supply
+    /// * `migration_target` - AMM the pool graduates into (`Raydium` is the only backend so far)
+    /// * `first_buyer_bonus_bps` - Extra meme for the first buyer, in bps of their buy (max 10%)
+    /// * `burn_unsold_bps` - Share of the unsold curve meme burned at migration (max 50%)
+    /// * `residual_fee_mode` - Post-migration fees to the treasury, burned or to stakers
+    /// * `curve_type` - `PositiveIntercept` curve or `Linear`, which needs `virtual_sol_reserves`
+    #[allow(clippy::too_many_arguments)]
+    pub fn new_pool(
+        ctx: Context<NewPool>,
+        airdropped_tokens: u64,
+        creator_locked_tokens: u64,
+        creator_lock_period: i64,
+        creator_vesting_period: i64,
+        graduation_mode: GraduationMode,
+        fee_meme_percent: u64,
+        virtual_sol_reserves: u64,
+        virtual_meme_reserves: u64,
+        migration_target: MigrationTarget,
+        first_buyer_bonus_bps: u16,
+        burn_unsold_bps: u16,
+        residual_fee_mode: ResidualFeeMode,
+        curve_type: CurveType,
+    ) -> Result<()> {
+        new_pool::handle(
+            ctx,
+            airdropped_tokens,
+            creator_locked_tokens,
+            creator_lock_period,
+            creator_vesting_period,
+            graduation_mode,
+            fee_meme_percent,
+            virtual_sol_reserves,
+            virtual_meme_reserves,
+            migration_target,
+            first_buyer_bonus_bps,
+            burn_unsold_bps,
+            residual_fee_mode,
+            curve_type,
+        )
+    }
+
+    /// Lifts or restores the one-launch-per-cooldown limit for a creator
+    /// Only callable by the admin
+    pub fn set_creator_exemption(ctx: Context<SetCreatorExemption>, exempt: bool) -> Result<()> {
+        set_creator_exemption::handle(ctx, exempt)
+    }
+
+    /// Releases the creator's locked allocation once the lock has expired
+    /// Only callable by the pool creator
+    pub fn claim_creator_allocation(ctx: Context<ClaimCreatorAllocation>) -> Result<()> {
+        claim_creator_allocation::handle(ctx)
+    }
+
+    /// Unwinds a launch before its first trade: returns the creator and
+    /// airdrop allocations and any quote sent to the pool, burns the rest
+    /// and closes the pool accounts
+    /// Only callable by the pool creator
+    pub fn abort_launch(ctx: Context<AbortLaunch>) -> Result<()> {
+        abort_launch::handle(ctx)
+    }
+
+    /// Proposes a new creator for the pool, who takes over once they call
+    /// `accept_creator_transfer`
+    /// Only callable by the pool creator
+    ///
+    /// # Arguments
+    /// * `new_creator` - Proposed creator, the default key withdraws a proposal
+    pub fn propose_creator_transfer(
+        ctx: Context<ProposeCreatorTransfer>,
+        new_creator: Pubkey,
+    ) -> Result<()> {
+        propose_creator_transfer::handle(ctx, new_creator)
+    }
+
+    /// Takes over the pool as its creator, along with its unclaimed allocation
+    /// Only callable by the wallet proposed with `propose_creator_transfer`
+    pub fn accept_creator_transfer(ctx: Context<AcceptCreatorTransfer>) -> Result<()> {
+        accept_creator_transfer::handle(ctx)
     }
 
     /// Creates token metadata for the launched memecoin
@@ -40,17 +128,101 @@ pub mod launchpad {
     /// * `name` - Token name (e.g., "Doge Coin")
     /// * `symbol` - Token symbol (e.g., "DOGE")  
     /// * `uri` - Metadata URI pointing to off-chain JSON with image/description
+    /// * `renounce_freeze` - Permanently remove the mint's freeze authority
     pub fn create_metadata(
         ctx: Context<CreateMetadata>,
         name: String,
         symbol: String,
         uri: String,
+        renounce_freeze: bool,
     ) -> Result<()> {
-        create_metadata::handle(ctx, name, symbol, uri)
+        create_metadata::handle(ctx, name, symbol, uri, renounce_freeze)
+    }
+
+    /// Replaces the token's metadata URI (name and symbol stay fixed)
+    /// Only the creator can call it, and only before migration
+    ///
+    /// # Arguments
+    /// * `new_uri` - New metadata URI pointing to off-chain JSON
+    pub fn update_metadata(ctx: Context<UpdateMetadata>, new_uri: String) -> Result<()> {
+        update_metadata::handle(ctx, new_uri)
     }
 
     // ===== Trading Functions =====
 
+    /// View pool state: reserves, fees, status and lifetime volume
+    /// Serialized `PoolState` is returned via return data
+    pub fn get_pool_state(ctx: Context<GetPoolState>) -> Result<()> {
+        get_pool_state::handle(ctx)
+    }
+
+    /// Time-weighted average spot price since `start_slot`
+    /// Serialized `TwapReading` is returned via return data
+    ///
+    /// # Arguments
+    /// * `start_cumulative` - `price_cumulative` read at the start of the window
+    /// * `start_slot` - Slot of that reading
+    pub fn read_twap(
+        ctx: Context<ReadTwap>,
+        start_cumulative: u128,
+        start_slot: u64,
+    ) -> Result<()> {
+        read_twap::handle(ctx, start_cumulative, start_slot)
+    }
+
+    /// Preview a swap in one call: output, fees, points, price impact and
+    /// whether the pool trades at all
+    /// Serialized `SwapQuote` is returned via return data
+    ///
+    /// # Arguments
+    /// * `buy_meme` - `true` to quote a buy, `false` to quote a sell
+    /// * `amount_in` - Tokens paid into the swap, fees included
+    pub fn get_quote(ctx: Context<GetQuote>, buy_meme: bool, amount_in: u64) -> Result<()> {
+        get_quote::handle(ctx, buy_meme, amount_in)
+    }
+
+    /// Recommended compute budget for an instruction, so clients can request
+    /// more than the default for migrations and batches
+    /// Serialized `ComputeEstimate` is returned via return data
+    ///
+    /// # Arguments
+    /// * `operation` - Instruction to budget
+    /// * `size` - Buys or recipients of a batch, ignored otherwise
+    pub fn estimate_compute(
+        ctx: Context<EstimateCompute>,
+        operation: ComputeOperation,
+        size: u8,
+    ) -> Result<()> {
+        estimate_compute::handle(ctx, operation, size)
+    }
+
+    /// Create the pool's price snapshot ring buffer, recorded into by swaps
+    /// passing it in
+    pub fn init_price_snapshots(ctx: Context<InitPriceSnapshots>) -> Result<()> {
+        init_price_snapshots::handle(ctx)
+    }
+
+    /// The pool's last swaps as (slot, spot price, quote reserve), oldest first
+    /// Serialized `Vec<PriceSnapshot>` is returned via return data
+    pub fn read_snapshots(ctx: Context<ReadSnapshots>) -> Result<()> {
+        read_snapshots::handle(ctx)
+    }
+
+    /// Pools listed on one page of the registry, in creation order
+    /// Serialized `Vec<Pubkey>` is returned via return data
+    ///
+    /// # Arguments
+    /// * `page` - Registry page to read, starting at 0
+    pub fn read_pool_registry(ctx: Context<ReadPoolRegistry>, page: u32) -> Result<()> {
+        read_pool_registry::handle(ctx, page)
+    }
+
+    /// A referrer's lifetime referral points and referred buys
+    /// Serialized `ReferrerStats` is returned via return data
+    pub fn read_referrer_stats(ctx: Context<ReadReferrerStats>) -> Result<()> {
+        read_referrer_stats::handle(ctx)
+    }
+
     /// Preview swap: selling meme tokens for SOL
     /// Returns expected amounts without executing trade
     ///
@@ -71,16 +243,41 @@ pub mod launchpad {
     /// # Arguments
     /// * `coin_in_amount` - Amount of meme tokens to sell
     /// * `coin_y_min_value` - Minimum SOL to receive (slippage protection)
+    /// * `unwrap_sol` - Close the WSOL account so the SOL arrives as native lamports
+    /// * `max_coin_in_amount` - Most meme tokens meant to be sold, a typo guard (0 = no limit)
     pub fn swap_x(
         ctx: Context<SwapCoinX>,
         coin_in_amount: u64,
         coin_y_min_value: u64,
+        unwrap_sol: bool,
+        max_coin_in_amount: u64,
+    ) -> Result<()> {
+        swap_x::handle(
+            ctx,
+            coin_in_amount,
+            coin_y_min_value,
+            unwrap_sol,
+            max_coin_in_amount,
+        )
+    }
+
+    /// Execute swap: sell meme tokens for an exact amount of SOL
+    /// Requests above the SOL reserve are rejected
+    ///
+    /// # Arguments
+    /// * `sol_out_amount` - Amount of SOL to receive
+    /// * `max_meme_in` - Maximum meme tokens to sell including fees (slippage protection)
+    pub fn swap_x_exact_out(
+        ctx: Context<SwapCoinX>,
+        sol_out_amount: u64,
+        max_meme_in: u64,
     ) -> Result<()> {
-        swap_x::handle(ctx, coin_in_amount, coin_y_min_value)
+        swap_x_exact_out::handle(ctx, sol_out_amount, max_meme_in)
     }
 
     /// Preview swap: buying meme tokens with SOL
-    /// Returns expected amounts without executing trade
+    /// Returns expected amounts, execution price and slippage versus the spot
+    /// price as a `BuyPreview` in return data, without executing trade
     ///
     /// # Arguments
     /// * `coin_in_amount` - Amount of SOL to spend
@@ -100,20 +297,306 @@ pub mod launchpad {
     /// # Arguments
     /// * `coin_in_amount` - Amount of SOL to spend
     /// * `coin_x_min_value` - Minimum meme tokens to receive (slippage protection)
+    /// * `max_coin_in_amount` - Most SOL meant to be spent, a typo guard (0 = no limit)
     pub fn swap_y(
         ctx: Context<SwapCoinY>,
         coin_in_amount: u64,
         coin_x_min_value: u64,
+        max_coin_in_amount: u64,
+    ) -> Result<()> {
+        swap_y::handle(ctx, coin_in_amount, coin_x_min_value, max_coin_in_amount)
+    }
+
+    /// Execute up to 16 buys back to back in a single instruction
+    /// Each buy keeps its own slippage floor; transfers are settled once
+    ///
+    /// # Arguments
+    /// * `amounts` - SOL to spend on each buy
+    /// * `min_outs` - Minimum meme tokens to receive from each buy
+    pub fn swap_y_batch(
+        ctx: Context<SwapCoinY>,
+        amounts: Vec<u64>,
+        min_outs: Vec<u64>,
     ) -> Result<()> {
-        swap_y::handle(ctx, coin_in_amount, coin_x_min_value)
+        swap_y_batch::handle(ctx, amounts, min_outs)
+    }
+
+    /// Execute swap: buy an exact amount of meme tokens with SOL
+    /// Requests above the remaining meme reserve are capped
+    ///
+    /// # Arguments
+    /// * `coin_out_amount` - Amount of meme tokens to receive
+    /// * `max_sol_in` - Maximum SOL to spend including fees (slippage protection)
+    pub fn swap_y_exact_out(
+        ctx: Context<SwapCoinY>,
+        coin_out_amount: u64,
+        max_sol_in: u64,
+    ) -> Result<()> {
+        swap_y_exact_out::handle(ctx, coin_out_amount, max_sol_in)
+    }
+
+    /// Execute swap: buy meme tokens with native SOL
+    /// Lamports are wrapped into the signer's temporary WSOL account, which is
+    /// closed after the buy so no manual wrapping or unwrapping is needed
+    ///
+    /// # Arguments
+    /// * `coin_in_amount` - Amount of native SOL to spend
+    /// * `coin_x_min_value` - Minimum meme tokens to receive (slippage protection)
+    /// * `max_coin_in_amount` - Most SOL meant to be spent, a typo guard (0 = no limit)
+    pub fn swap_y_native(
+        ctx: Context<SwapCoinY>,
+        coin_in_amount: u64,
+        coin_x_min_value: u64,
+        max_coin_in_amount: u64,
+    ) -> Result<()> {
+        swap_y_native::handle(ctx, coin_in_amount, coin_x_min_value, max_coin_in_amount)
+    }
+
+    /// Place a buy filled once the spot price crosses a trigger
+    /// The quote tokens are escrowed until the order fills or is cancelled
+    ///
+    /// # Arguments
+    /// * `order_id` - Distinguishes the signer's orders on the pool
+    /// * `trigger_price` - Spot price to wait for, same units as `get_pool_state`
+    /// * `quote_amount` - Quote tokens to spend, fees included
+    /// * `direction` - Fill at or below (`Below`) or at or above (`Above`) the trigger
+    pub fn place_limit_order(
+        ctx: Context<PlaceLimitOrder>,
+        order_id: u64,
+        trigger_price: u64,
+        quote_amount: u64,
+        direction: LimitDirection,
+    ) -> Result<()> {
+        place_limit_order::handle(ctx, order_id, trigger_price, quote_amount, direction)
+    }
+
+    /// Fill a triggered limit order with its escrowed quote tokens
+    /// Callable by anyone; the meme tokens go to the order's owner
+    pub fn execute_limit_order(ctx: Context<ExecuteLimitOrder>) -> Result<()> {
+        execute_limit_order::handle(ctx)
+    }
+
+    /// Cancel a limit order, refunding its unfilled quote and closing it
+    /// Only callable by the order's owner
+    pub fn cancel_limit_order(ctx: Context<CancelLimitOrder>) -> Result<()> {
+        cancel_limit_order::handle(ctx)
+    }
+
+    /// Register a human readable referral code for the signer's wallet
+    ///
+    /// # Arguments
+    /// * `code` - Up to 16 printable ASCII characters, unique regardless of case
+    pub fn register_referral_code(ctx: Context<RegisterReferralCode>, code: String) -> Result<()> {
+        register_referral_code::handle(ctx, code)
+    }
+
+    /// Open the signer's escrow, which its referral points vest in while the
+    /// points epoch has a vesting delay
+    pub fn open_points_escrow(ctx: Context<OpenPointsEscrow>) -> Result<()> {
+        open_points_escrow::handle(ctx)
+    }
+
+    /// Open the signer's referrer stats, counted into by buys it referred
+    pub fn open_referrer_stats(ctx: Context<OpenReferrerStats>) -> Result<()> {
+        open_referrer_stats::handle(ctx)
+    }
+
+    /// Release the signer's escrowed referral points once the delay has passed
+    pub fn claim_points(ctx: Context<ClaimPoints>) -> Result<()> {
+        claim_points::handle(ctx)
     }
 
-    /// Send airdrop funds to designated recipient
-    /// Only callable by authorized airdrop distributors
+    /// Send airdrop funds to the admin's airdrop vault
+    /// The receiving wallet must be the current admin
     pub fn send_airdrop_funds(ctx: Context<SendAirdropFunds>) -> Result<()> {
         send_airdrop_funds::handle(ctx)
     }
 
+    /// Send airdrops to up to 10 recipients in one transaction
+    /// Only callable by the admin, recipient meme token accounts are passed as
+    /// remaining accounts, in order
+    ///
+    /// # Arguments
+    /// * `recipients` - Wallets receiving an airdrop
+    /// * `amounts` - Meme tokens sent to each recipient
+    pub fn send_airdrop_funds_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, SendAirdropFundsBatch<'info>>,
+        recipients: Vec<Pubkey>,
+        amounts: Vec<u64>,
+    ) -> Result<()> {
+        send_airdrop_funds_batch::handle(ctx, recipients, amounts)
+    }
+
+    /// Sweep unclaimed airdrop tokens back into the staking vault
+    /// Only callable by the admin once the airdrop window expired
+    pub fn reclaim_expired_airdrop(ctx: Context<ReclaimExpiredAirdrop>) -> Result<()> {
+        reclaim_expired_airdrop::handle(ctx)
+    }
+
+    // ===== Staking Functions =====
+
+    /// Stake meme tokens in the staking pool
+    ///
+    /// # Arguments
+    /// * `amount` - Meme tokens to deposit
+    pub fn stake(ctx: Context<Stake>, amount: u64) -> Result<()> {
+        stake::handle(ctx, amount)
+    }
+
+    /// Withdraw staked meme tokens
+    /// Pays out the withdrawn share of the remaining airdrop allocation while
+    /// the airdrop window is open. Earned quote rewards stay claimable
+    ///
+    /// # Arguments
+    /// * `amount` - Staked meme tokens to withdraw
+    pub fn unstake(ctx: Context<Unstake>, amount: u64) -> Result<()> {
+        unstake::handle(ctx, amount)
+    }
+
+    /// Claim the quote rewards earned by staking
+    pub fn claim_staking_rewards(ctx: Context<ClaimStakingRewards>) -> Result<()> {
+        claim_staking_rewards::handle(ctx)
+    }
+
+    // ===== Fee Functions =====
+
+    /// Withdraw accrued admin fees to the fee vaults
+    /// Amounts can be partial so fees can be swept incrementally
+    ///
+    /// # Arguments
+    /// * `quote_amount` - Quote fees to withdraw
+    /// * `meme_amount` - Meme fees to withdraw
+    pub fn withdraw_admin_fees(
+        ctx: Context<WithdrawAdminFees>,
+        quote_amount: u64,
+        meme_amount: u64,
+    ) -> Result<()> {
+        withdraw_admin_fees::handle(ctx, quote_amount, meme_amount)
+    }
+
+    /// Withdraw the creator's share of the quote fees
+    /// Only callable by the pool creator
+    ///
+    /// # Arguments
+    /// * `quote_amount` - Quote fees to withdraw
+    pub fn withdraw_creator_fees(
+        ctx: Context<WithdrawCreatorFees>,
+        quote_amount: u64,
+    ) -> Result<()> {
+        withdraw_creator_fees::handle(ctx, quote_amount)
+    }
+
+    /// Route quote fees owed to stakers, then accrued admin quote fees, to stakers as rewards
+    /// Only callable by the fee key
+    ///
+    /// # Arguments
+    /// * `quote_amount` - Quote fees to route
+    pub fn route_fees_to_stakers(
+        ctx: Context<RouteFeesToStakers>,
+        quote_amount: u64,
+    ) -> Result<()> {
+        route_fees_to_stakers::handle(ctx, quote_amount)
+    }
+
+    /// Upgrade a pool account written with an older layout to the current one
+    /// Only callable by the fee key
+    pub fn migrate_bound_pool_account(ctx: Context<MigrateBoundPoolAccount>) -> Result<()> {
+        migrate_bound_pool_account::handle(ctx)
+    }
+
+    /// Upgrade a staking pool account written with an older layout to the current one
+    /// Only callable by the fee key
+    pub fn migrate_staking_pool_account(ctx: Context<MigrateStakingPoolAccount>) -> Result<()> {
+        migrate_staking_pool_account::handle(ctx)
+    }
+
+    /// Exempt a wallet from the admin fees on a pool
+    /// Only callable by the admin
+    pub fn grant_fee_exemption(ctx: Context<GrantFeeExemption>) -> Result<()> {
+        grant_fee_exemption::handle(ctx)
+    }
+
+    /// Move tokens sent by mistake to a pool signer account out again
+    /// Only callable by the admin, never from the reserve vaults
+    ///
+    /// # Arguments
+    /// * `amount` - Tokens to recover
+    pub fn recover_stuck_tokens(ctx: Context<RecoverStuckTokens>, amount: u64) -> Result<()> {
+        recover_stuck_tokens::handle(ctx, amount)
+    }
+
+    /// Adjust the fees of a pool which hasn't migrated
+    /// Only callable by the admin
+    ///
+    /// # Arguments
+    /// * `fee_quote_percent` - Quote fee, 1e9 = 100% (max 5%)
+    /// * `fee_meme_percent` - Meme token fee, 1e9 = 100% (max 5%)
+    pub fn update_fees(
+        ctx: Context<UpdateFees>,
+        fee_quote_percent: u64,
+        fee_meme_percent: u64,
+    ) -> Result<()> {
+        update_fees::handle(ctx, fee_quote_percent, fee_meme_percent)
+    }
+
+    /// Adjust the optional trading guards of a pool which hasn't migrated
+    /// Only callable by the admin
+    ///
+    /// # Arguments
+    /// * `guards` - New guards, 0 disables each
+    pub fn update_pool_guards(ctx: Context<UpdatePoolGuards>, guards: PoolGuards) -> Result<()> {
+        update_pool_guards::handle(ctx, guards)
+    }
+
+    // ===== Admin Functions =====
+
+    /// Create the global config holding the admin key, seeded with the swap
+    /// authority. Can only run once
+    pub fn init_global_config(ctx: Context<InitGlobalConfig>) -> Result<()> {
+        init_global_config::handle(ctx)
+    }
+
+    /// Create the points epoch swaps read their points rate from
+    /// Only callable by the admin, once
+    ///
+    /// # Arguments
+    /// * `points_per_sol_num` - Numerator of the points earned per quote token
+    /// * `points_per_sol_denom` - Denominator of the points earned per quote token
+    pub fn init_points_epoch(
+        ctx: Context<InitPointsEpoch>,
+        points_per_sol_num: u64,
+        points_per_sol_denom: u64,
+    ) -> Result<()> {
+        init_points_epoch::handle(ctx, points_per_sol_num, points_per_sol_denom)
+    }
+
+    /// Adjust the points rules, or start the next points epoch
+    /// Only callable by the admin
+    ///
+    /// # Arguments
+    /// * `settings` - New rules, a higher `epoch_number` starts a new epoch
+    pub fn update_points_epoch(
+        ctx: Context<UpdatePointsEpoch>,
+        settings: PointsEpochSettings,
+    ) -> Result<()> {
+        update_points_epoch::handle(ctx, settings)
+    }
+
+    /// Propose the next admin, who takes over once they call `accept_admin`
+    /// Only callable by the admin
+    ///
+    /// # Arguments
+    /// * `new_admin` - Proposed admin, the default key withdraws a proposal
+    pub fn transfer_admin(ctx: Context<TransferAdmin>, new_admin: Pubkey) -> Result<()> {
+        transfer_admin::handle(ctx, new_admin)
+    }
+
+    /// Take over the admin role
+    /// Only callable by the wallet proposed with `transfer_admin`
+    pub fn accept_admin(ctx: Context<AcceptAdmin>) -> Result<()> {
+        accept_admin::handle(ctx)
+    }
+
     // ===== Migration Functions =====
 
     /// 🌟 Migrate bonding curve liquidity to Raydium CPMM
@@ -126,18 +609,63 @@ pub mod launchpad {
     /// - Enables deeper liquidity and price stability
     ///
     /// # Migration Process
-    /// 1. Validates 80% threshold reached
+    /// 1. Validates the pool's graduation threshold is reached
     /// 2. Locks bonding curve pool
-    /// 3. Transfers tokens to creator accounts
-    /// 4. Calls Raydium CPMM initialize via CPI
-    /// 5. Updates pool state and emits event
-
+    /// 3. Sends the 1% migration fee on the quote side to the fee vault
+    /// 4. Transfers tokens to creator accounts
+    /// 5. Calls Raydium CPMM initialize via CPI
+    /// 6. Revokes the meme mint authority unless opted out
+    /// 7. Updates pool state and emits event
+    ///
     /// # Requirements
-    /// - Pool must have reached 80% sell threshold
+    /// - Pool must have reached 80% sell threshold, or 80% of its quote
+    ///   target for `GraduationMode::QuotePercent` pools
+    /// - Quote reserve must hold at least `MIN_MIGRATION_QUOTE`
     /// - Pool must not be already migrated
     /// - Meme token key must be < quote token key (Raydium requirement)
     /// - All Raydium accounts properly derived
-    pub fn migrate_to_raydium(ctx: Context<MigrateToRaydium>) -> Result<()> {
-        migrate_to_raydium::handle(ctx)
+    ///
+    /// # Arguments
+    /// * `keep_mint_authority` - Skip revoking the meme mint authority, for projects with planned future mints
+    /// * `dry_run` - Only validate and emit `MigrationSimulated`, skipping the CPIs and state changes
+    pub fn migrate_to_raydium(
+        ctx: Context<MigrateToRaydium>,
+        keep_mint_authority: bool,
+        dry_run: bool,
+    ) -> Result<()> {
+        migrate_to_raydium::handle(ctx, keep_mint_authority, dry_run)
+    }
+
+    /// Migrate bonding curve liquidity to the pool's `migration_target`
+    /// Same checks and liquidity split as `migrate_to_raydium`; the accounts
+    /// the target's AMM needs follow as remaining accounts
+    ///
+    /// # Arguments
+    /// * `keep_mint_authority` - Keep the meme mint authority instead of revoking it
+    /// * `dry_run` - Only validate and emit `MigrationSimulated`, skipping the CPIs and state changes
+    pub fn migrate<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Migrate<'info>>,
+        keep_mint_authority: bool,
+        dry_run: bool,
+    ) -> Result<()> {
+        migrate::handle(ctx, keep_mint_authority, dry_run)
+    }
+
+    /// Preview what `migrate_to_raydium` would seed into the AMM
+    /// Serialized `MigrationPreview` is returned via return data, no state changes
+    pub fn simulate_migration(ctx: Context<SimulateMigration>) -> Result<()> {
+        simulate_migration::handle(ctx)
+    }
+
+    /// Derive the Raydium accounts `migrate_to_raydium` expects for this pool
+    /// Serialized `MigrationAccounts` is returned via return data
+    ///
+    /// # Arguments
+    /// * `amm_config_index` - Index of the Raydium AMM config to migrate under
+    pub fn get_migration_accounts(
+        ctx: Context<GetMigrationAccounts>,
+        amm_config_index: u16,
+    ) -> Result<()> {
+        get_migration_accounts::handle(ctx, amm_config_index)
     }
 }
@@ -107,7 +107,9 @@ impl MulDiv for u64 {
     type Output = u64;
 
     fn mul_div_floor(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, 0);
+        if denom == 0 {
+            return None;
+        }
         let r = (U128::from(self) * U128::from(num)) / U128::from(denom);
         if r > U128::from(u64::MAX) {
             None
@@ -117,7 +119,9 @@ impl MulDiv for u64 {
     }
 
     fn mul_div_ceil(self, num: Self, denom: Self) -> Option<Self::Output> {
-        assert_ne!(denom, 0);
+        if denom == 0 {
+            return None;
+        }
         let r = (U128::from(self) * U128::from(num) + U128::from(denom - 1)) / U128::from(denom);
         if r > U128::from(u64::MAX) {
             None
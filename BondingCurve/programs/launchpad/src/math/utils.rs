@@ -22,6 +22,33 @@ pub fn multiply_until_overflow(nums: Vec<u128>) -> (u128, Vec<u128>) {
     helper(&nums, 1)
 }
 
+/// Newton steps allowed in `u256_sqrt`. Starting within 2x of the root the
+/// error squares every step, so a 256-bit input settles in about 8 steps.
+const U256_SQRT_ITERATIONS: usize = 12;
+
+/// Floor square root of a U256 via Newton's method.
+///
+/// The first guess is the power of two just above the root, so estimates only
+/// ever decrease and the loop stops as soon as one fails to, within a fixed
+/// number of iterations.
+pub fn u256_sqrt(n: U256) -> U256 {
+    if n < U256::from(2) {
+        return n;
+    }
+
+    let mut x = U256::one() << n.bits().div_ceil(2);
+
+    for _ in 0..U256_SQRT_ITERATIONS {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            break;
+        }
+        x = y;
+    }
+
+    x
+}
+
 pub fn multiply_divide(mut numerators: Vec<U256>, mut denominators: Vec<U256>) -> Option<U256> {
     let mut result = U256::from(1);
     numerators.sort_by(|a, b| b.cmp(a));
@@ -266,7 +293,53 @@ impl CheckedMath256 for Option<U256> {
     fn sqrt(&self) -> Self {
         match self {
             None => None,
-            Some(num_) => Some((*num_).integer_sqrt()),
+            Some(num_) => Some(u256_sqrt(*num_)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_u256_sqrt_small_values() {
+        for n in 0u64..10_000 {
+            let root = u256_sqrt(U256::from(n));
+            assert_eq!(root, U256::from(n).integer_sqrt(), "sqrt({})", n);
+        }
+    }
+
+    #[test]
+    fn test_u256_sqrt_large_values() {
+        // Walk magnitudes from 2^64 up to the top of the range
+        let mut n = U256::from(u64::MAX);
+        while n < U256::MAX / U256::from(3) {
+            for candidate in [n - U256::one(), n, n + U256::from(12_345)] {
+                let root = u256_sqrt(candidate);
+
+                // Floor root: root^2 <= n < (root + 1)^2
+                assert!(root * root <= candidate);
+                let next = root + U256::one();
+                assert!(next.checked_mul(next).map_or(true, |sq| sq > candidate));
+
+                // Within a unit, plus floating point error, of the reference
+                let reference = (candidate.to_string().parse::<f64>().unwrap()).sqrt();
+                let got = root.to_string().parse::<f64>().unwrap();
+                assert!(
+                    (got - reference).abs() <= 1.0 + reference * 1e-12,
+                    "sqrt({}) = {}, expected about {}",
+                    candidate,
+                    got,
+                    reference
+                );
+            }
+
+            n = n * U256::from(3) + U256::from(7);
         }
+
+        assert_eq!(u256_sqrt(U256::MAX), U256::from(u128::MAX));
+
+        println!("✅ U256 sqrt test passed!");
     }
 }
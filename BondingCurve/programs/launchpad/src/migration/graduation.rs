@@ -0,0 +1,215 @@
+use crate::endpoints::migrate_to_raydium::{
+    calculate_burn_amount, debit_migrated_reserves, plan_migration, should_renounce_mint_authority,
+    MigrationEvent, MigrationSimulated,
+};
+use crate::endpoints::swap_y::{LockReason, PoolLocked};
+use crate::migration::{self, SeedLiquidity};
+use crate::models::bound::BoundPool;
+use anchor_lang::prelude::*;
+use anchor_spl::token::spl_token::instruction::AuthorityType::MintTokens;
+use anchor_spl::token::{self, Burn, Mint, SetAuthority, Transfer};
+
+/// Accounts a pool graduates with, whichever AMM its `migration_target` is.
+pub struct Graduation<'a, 'info> {
+    pub program_id: &'a Pubkey,
+    pub pool: &'a mut Account<'info, BoundPool>,
+    pub meme_mint: &'a mut Account<'info, Mint>,
+    pub quote_mint: &'a AccountInfo<'info>,
+    pub meme_vault: &'a AccountInfo<'info>,
+    pub quote_vault: &'a AccountInfo<'info>,
+    /// Pool signer PDA owning the vaults and the meme mint
+    pub pool_signer: &'a AccountInfo<'info>,
+    pub pool_signer_bump: u8,
+    /// Protocol fee vault receiving the migration fee
+    pub fee_quote_vault: &'a AccountInfo<'info>,
+    /// Signer funding the AMM pool, owner of the creator token accounts
+    pub creator: &'a AccountInfo<'info>,
+    pub creator_meme_account: &'a AccountInfo<'info>,
+    pub creator_quote_account: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    /// Accounts only the target's AMM needs, in the order its backend documents
+    pub target_accounts: &'a [AccountInfo<'info>],
+}
+
+/// Events a graduation leaves for the endpoint to emit, since `emit_cpi!`
+/// needs the instruction's context.
+pub enum Graduated {
+    /// Dry run, with what a real migration would do
+    Simulated(MigrationSimulated),
+    /// The pool was locked and its liquidity seeded into the AMM
+    Migrated(PoolLocked, MigrationEvent),
+}
+
+/// Graduates the pool into the AMM of its `migration_target`: checks it can
+/// migrate, locks it, skims the migration fee, burns `burn_unsold_bps` of the
+/// unsold meme, seeds the AMM through the target's backend, revokes the mint
+/// authority and takes what left the curve off its reserves.
+///
+/// # Arguments
+/// * `accs` - The pool's accounts and the target's AMM accounts
+/// * `keep_mint_authority` - Keep the meme mint authority instead of revoking it
+/// * `dry_run` - Stop after the checks, before any CPI or state change
+pub fn graduate(
+    accs: Graduation<'_, '_>,
+    keep_mint_authority: bool,
+    dry_run: bool,
+) -> Result<Graduated> {
+    let pool_key = accs.pool.key();
+
+    // Fail fast on a lookalike pool signer
+    BoundPool::assert_signer_pda(accs.program_id, &pool_key, accs.pool_signer.key)?;
+
+    // Validate the pool can migrate and compute the liquidity split
+    let (meme_amount, quote_amount, migration_fee) = plan_migration(accs.pool)?;
+    let meme_burned = calculate_burn_amount(accs.pool)?;
+    let renounces_mint_authority =
+        should_renounce_mint_authority(&accs.meme_mint.mint_authority, keep_mint_authority);
+
+    // The AMM pool opens for trading immediately
+    let clock = Clock::get()?;
+    let meme_mint = accs.meme_mint.to_account_info();
+    let target = accs.pool.config.migration_target;
+    let seed = SeedLiquidity {
+        creator: accs.creator,
+        meme_mint: &meme_mint,
+        quote_mint: accs.quote_mint,
+        creator_meme_account: accs.creator_meme_account,
+        creator_quote_account: accs.creator_quote_account,
+        token_program: accs.token_program,
+        associated_token_program: accs.associated_token_program,
+        system_program: accs.system_program,
+        rent: accs.rent,
+        target_accounts: accs.target_accounts,
+        meme_amount,
+        quote_amount,
+        open_time: clock.unix_timestamp as u64,
+    };
+
+    // Dry runs stop here, before any CPI or state change
+    if dry_run {
+        return Ok(Graduated::Simulated(MigrationSimulated {
+            pool: pool_key,
+            raydium_pool: migration::amm_pool(target, &migration::registered_backends(), &seed)?,
+            meme_amount,
+            quote_amount,
+            migration_fee,
+            meme_burned,
+            renounces_mint_authority,
+        }));
+    }
+
+    // Lock the pool to prevent further trading
+    accs.pool.locked = true;
+    let pool_locked = PoolLocked {
+        pool: pool_key,
+        meme_reserve: accs.pool.meme_reserve.tokens,
+        quote_reserve: accs.pool.quote_reserve.tokens,
+        reason: LockReason::Migration,
+    };
+
+    // The vaults belong to the pool signer
+    let pool_signer_seeds: &[&[u8]] = &[
+        BoundPool::SIGNER_PDA_PREFIX,
+        pool_key.as_ref(),
+        &[accs.pool_signer_bump],
+    ];
+    let signer_seeds = &[pool_signer_seeds];
+
+    // Skim the migration fee, then hand the liquidity to the creator accounts
+    // the backend seeds from
+    for (to, amount) in [
+        (accs.fee_quote_vault, migration_fee),
+        (accs.creator_quote_account, quote_amount),
+    ] {
+        if amount == 0 {
+            continue;
+        }
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                accs.token_program.clone(),
+                Transfer {
+                    from: accs.quote_vault.clone(),
+                    to: to.clone(),
+                    authority: accs.pool_signer.clone(),
+                },
+                signer_seeds,
+            ),
+            amount,
+        )?;
+    }
+
+    // Burn the configured share of the unsold meme before seeding
+    if meme_burned > 0 {
+        token::burn(
+            CpiContext::new_with_signer(
+                accs.token_program.clone(),
+                Burn {
+                    mint: meme_mint.clone(),
+                    from: accs.meme_vault.clone(),
+                    authority: accs.pool_signer.clone(),
+                },
+                signer_seeds,
+            ),
+            meme_burned,
+        )?;
+    }
+
+    token::transfer(
+        CpiContext::new_with_signer(
+            accs.token_program.clone(),
+            Transfer {
+                from: accs.meme_vault.clone(),
+                to: accs.creator_meme_account.clone(),
+                authority: accs.pool_signer.clone(),
+            },
+            signer_seeds,
+        ),
+        meme_amount,
+    )?;
+
+    // Open the AMM pool with the backend serving the pool's target
+    let amm_pool = migration::seed_liquidity(target, &migration::registered_backends(), &seed)?;
+
+    // Revoke the mint authority so no new supply can be minted after graduation
+    if renounces_mint_authority {
+        token::set_authority(
+            CpiContext::new_with_signer(
+                accs.token_program.clone(),
+                SetAuthority {
+                    current_authority: accs.pool_signer.clone(),
+                    account_or_mint: meme_mint.clone(),
+                },
+                signer_seeds,
+            ),
+            MintTokens,
+            None,
+        )?;
+        accs.meme_mint.reload()?;
+    }
+
+    // Keep what's left on the curve, net of what left it
+    debit_migrated_reserves(
+        accs.pool,
+        meme_amount + meme_burned,
+        quote_amount + migration_fee,
+    )?;
+    accs.pool.mark_migrated(amm_pool)?;
+
+    Ok(Graduated::Migrated(
+        pool_locked,
+        MigrationEvent {
+            pool: pool_key,
+            raydium_pool: amm_pool,
+            meme_amount_migrated: meme_amount,
+            quote_amount_migrated: quote_amount,
+            migration_fee,
+            meme_burned,
+            timestamp: clock.unix_timestamp,
+            mint_authority_renounced: accs.meme_mint.mint_authority.is_none(),
+        },
+    ))
+}
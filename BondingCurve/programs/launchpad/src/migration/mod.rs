@@ -0,0 +1,194 @@
+pub mod graduation;
+pub mod raydium;
+
+use crate::err::AmmError;
+use crate::models::bound::MigrationTarget;
+use anchor_lang::prelude::*;
+
+pub use graduation::{graduate, Graduated, Graduation};
+pub use raydium::{validate_mint_ordering, RaydiumBackend};
+
+/// Accounts and amounts a backend opens the AMM pool with. By the time a
+/// backend runs, the liquidity already sits in the creator's token accounts.
+pub struct SeedLiquidity<'a, 'info> {
+    /// Signer funding the AMM pool, owner of the creator token accounts
+    pub creator: &'a AccountInfo<'info>,
+    pub meme_mint: &'a AccountInfo<'info>,
+    pub quote_mint: &'a AccountInfo<'info>,
+    pub creator_meme_account: &'a AccountInfo<'info>,
+    pub creator_quote_account: &'a AccountInfo<'info>,
+    pub token_program: &'a AccountInfo<'info>,
+    pub associated_token_program: &'a AccountInfo<'info>,
+    pub system_program: &'a AccountInfo<'info>,
+    pub rent: &'a AccountInfo<'info>,
+    /// Accounts only the target's AMM needs, in the order its backend documents
+    pub target_accounts: &'a [AccountInfo<'info>],
+    /// Meme tokens to seed
+    pub meme_amount: u64,
+    /// Quote tokens to seed
+    pub quote_amount: u64,
+    /// Unix timestamp the AMM pool opens for trading
+    pub open_time: u64,
+}
+
+/// A destination pools can graduate into. Supporting a new AMM means a new
+/// module implementing this trait plus an entry in `registered_backends`.
+pub trait MigrationBackend<'info> {
+    /// Target this backend serves
+    fn target(&self) -> MigrationTarget;
+
+    /// Checks `seed`'s target accounts and returns the AMM pool they would
+    /// open, without seeding anything.
+    fn amm_pool(&self, seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey>;
+
+    /// Opens the AMM pool with `seed`, returning its address.
+    fn seed_liquidity(&self, seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey>;
+}
+
+/// Backends the program can migrate with.
+pub fn registered_backends<'b, 'info>() -> [&'b dyn MigrationBackend<'info>; 1] {
+    [&RaydiumBackend]
+}
+
+/// Picks the backend serving `target`.
+pub fn find_backend<'b, 'info>(
+    target: MigrationTarget,
+    backends: &[&'b dyn MigrationBackend<'info>],
+) -> Result<&'b dyn MigrationBackend<'info>> {
+    backends
+        .iter()
+        .find(|backend| backend.target() == target)
+        .copied()
+        .ok_or(error!(AmmError::MigrationTargetUnsupported))
+}
+
+/// Rejects targets no registered backend can migrate to, so a pool can't be
+/// created with a destination it would never graduate into.
+pub fn check_supported(target: MigrationTarget) -> Result<()> {
+    find_backend(target, &registered_backends()).map(|_| ())
+}
+
+/// AMM pool the backend serving `target` would open, for dry runs.
+pub fn amm_pool<'info>(
+    target: MigrationTarget,
+    backends: &[&dyn MigrationBackend<'info>],
+    seed: &SeedLiquidity<'_, 'info>,
+) -> Result<Pubkey> {
+    find_backend(target, backends)?.amm_pool(seed)
+}
+
+/// Seeds the AMM of `target` with the backend serving it.
+pub fn seed_liquidity<'info>(
+    target: MigrationTarget,
+    backends: &[&dyn MigrationBackend<'info>],
+    seed: &SeedLiquidity<'_, 'info>,
+) -> Result<Pubkey> {
+    find_backend(target, backends)?.seed_liquidity(seed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+
+    /// Records what it was asked to seed instead of calling an AMM
+    struct MockBackend {
+        target: MigrationTarget,
+        amm_pool: Pubkey,
+        seeded: RefCell<Vec<(u64, u64)>>,
+    }
+
+    impl MockBackend {
+        fn new(target: MigrationTarget) -> Self {
+            MockBackend {
+                target,
+                amm_pool: Pubkey::new_unique(),
+                seeded: RefCell::new(vec![]),
+            }
+        }
+    }
+
+    impl<'info> MigrationBackend<'info> for MockBackend {
+        fn target(&self) -> MigrationTarget {
+            self.target
+        }
+
+        fn amm_pool(&self, _seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey> {
+            Ok(self.amm_pool)
+        }
+
+        fn seed_liquidity(&self, seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey> {
+            self.seeded
+                .borrow_mut()
+                .push((seed.meme_amount, seed.quote_amount));
+
+            Ok(self.amm_pool)
+        }
+    }
+
+    #[test]
+    fn test_dispatch_to_target_backend() {
+        // ARRANGE: One mock per target and a placeholder account for the seed
+        let raydium = MockBackend::new(MigrationTarget::Raydium);
+        let orca = MockBackend::new(MigrationTarget::OrcaWhirlpool);
+        let backends: [&dyn MigrationBackend; 2] = [&raydium, &orca];
+
+        let key = Pubkey::new_unique();
+        let (mut lamports, mut data) = (0, vec![]);
+        let info = AccountInfo::new(&key, false, false, &mut lamports, &mut data, &key, false, 0);
+        let seed = SeedLiquidity {
+            creator: &info,
+            meme_mint: &info,
+            quote_mint: &info,
+            creator_meme_account: &info,
+            creator_quote_account: &info,
+            token_program: &info,
+            associated_token_program: &info,
+            system_program: &info,
+            rent: &info,
+            target_accounts: &[],
+            meme_amount: 1_000,
+            quote_amount: 50,
+            open_time: 0,
+        };
+
+        println!("🧪 Testing migration dispatch by target");
+
+        // ACT
+        let amm_pool = seed_liquidity(MigrationTarget::OrcaWhirlpool, &backends, &seed).unwrap();
+
+        // ASSERT: Only the pool's target backend seeded, and its pool is returned
+        assert_eq!(amm_pool, orca.amm_pool);
+        assert_eq!(*orca.seeded.borrow(), vec![(1_000, 50)]);
+        assert!(raydium.seeded.borrow().is_empty());
+
+        // Dry runs ask for the pool address without seeding again
+        assert_eq!(
+            super::amm_pool(MigrationTarget::OrcaWhirlpool, &backends, &seed).unwrap(),
+            orca.amm_pool
+        );
+        assert_eq!(orca.seeded.borrow().len(), 1);
+
+        // A target without a backend is refused before anything is seeded
+        assert_eq!(
+            seed_liquidity(MigrationTarget::Raydium, &backends[1..], &seed).unwrap_err(),
+            error!(AmmError::MigrationTargetUnsupported)
+        );
+        assert!(raydium.seeded.borrow().is_empty());
+
+        println!("✅ Migration dispatch test passed!");
+    }
+
+    #[test]
+    fn test_supported_targets() {
+        check_supported(MigrationTarget::Raydium).unwrap();
+
+        // No Whirlpool backend is registered yet
+        assert_eq!(
+            check_supported(MigrationTarget::OrcaWhirlpool).unwrap_err(),
+            error!(AmmError::MigrationTargetUnsupported)
+        );
+
+        println!("✅ Supported migration targets test passed!");
+    }
+}
@@ -0,0 +1,213 @@
+use crate::err::AmmError;
+use crate::migration::{MigrationBackend, SeedLiquidity};
+use crate::models::bound::MigrationTarget;
+use anchor_lang::prelude::*;
+use raydium_cpmm_cpi::{cpi, states::POOL_SEED};
+
+/// Seeds a Raydium CPMM pool with the meme as token_0 and the quote as
+/// token_1. Expects `target_accounts` in this order:
+///
+/// 0. Raydium CPMM program
+/// 1. AMM config
+/// 2. Vault and LP mint authority
+/// 3. Pool state to be created
+/// 4. LP mint to be created
+/// 5. Creator's LP token account
+/// 6. Meme (token_0) vault to be created
+/// 7. Quote (token_1) vault to be created
+/// 8. Pool creation fee receiver
+/// 9. Oracle observation account to be created
+pub struct RaydiumBackend;
+
+impl RaydiumBackend {
+    /// Number of `target_accounts` the backend expects
+    pub const ACCOUNTS: usize = 10;
+
+    /// Checks the keys Raydium won't verify for us: its program, the fee
+    /// receiver, the mint order and the pool state the migration records.
+    pub fn check_target_accounts(
+        meme_mint: &Pubkey,
+        quote_mint: &Pubkey,
+        target_accounts: &[Pubkey],
+    ) -> Result<()> {
+        if target_accounts.len() < Self::ACCOUNTS {
+            return Err(invalid_accounts("Missing Raydium accounts"));
+        }
+
+        validate_mint_ordering(meme_mint, quote_mint)?;
+
+        if target_accounts[0] != raydium_cpmm_cpi::ID {
+            return Err(invalid_accounts("Expected the Raydium CPMM program"));
+        }
+
+        if target_accounts[8] != raydium_cpmm_cpi::create_pool_fee_reveiver::id() {
+            return Err(invalid_accounts("Expected the Raydium pool creation fee receiver"));
+        }
+
+        let (pool_state, _) = Pubkey::find_program_address(
+            &[
+                POOL_SEED.as_bytes(),
+                target_accounts[1].as_ref(),
+                meme_mint.as_ref(),
+                quote_mint.as_ref(),
+            ],
+            &raydium_cpmm_cpi::ID,
+        );
+        if target_accounts[3] != pool_state {
+            return Err(invalid_accounts("Raydium pool state doesn't match the mints"));
+        }
+
+        Ok(())
+    }
+}
+
+/// Raydium CPMM wants token_0 to be the smaller key, and migration seeds the
+/// meme as token_0, so the meme mint must sort before the quote mint.
+pub fn validate_mint_ordering(meme_mint: &Pubkey, quote_mint: &Pubkey) -> Result<()> {
+    if meme_mint >= quote_mint {
+        return Err(error!(AmmError::InvalidMintOrdering));
+    }
+
+    Ok(())
+}
+
+/// Logs which Raydium account was wrong, like `err::acc` does for generic
+/// account errors.
+fn invalid_accounts(msg: &str) -> Error {
+    msg!("[InvalidRaydiumAccounts] {}", msg);
+
+    error!(AmmError::InvalidRaydiumAccounts)
+}
+
+impl<'info> MigrationBackend<'info> for RaydiumBackend {
+    fn target(&self) -> MigrationTarget {
+        MigrationTarget::Raydium
+    }
+
+    fn amm_pool(&self, seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey> {
+        let keys: Vec<Pubkey> = seed.target_accounts.iter().map(|a| a.key()).collect();
+        Self::check_target_accounts(seed.meme_mint.key, seed.quote_mint.key, &keys)?;
+
+        Ok(keys[3])
+    }
+
+    fn seed_liquidity(&self, seed: &SeedLiquidity<'_, 'info>) -> Result<Pubkey> {
+        let amm_pool = self.amm_pool(seed)?;
+
+        let accounts = seed.target_accounts;
+        let cpi_accounts = cpi::accounts::Initialize {
+            creator: seed.creator.clone(),
+            amm_config: accounts[1].clone(),
+            authority: accounts[2].clone(),
+            pool_state: accounts[3].clone(),
+            token_0_mint: seed.meme_mint.clone(),
+            token_1_mint: seed.quote_mint.clone(),
+            lp_mint: accounts[4].clone(),
+            creator_token_0: seed.creator_meme_account.clone(),
+            creator_token_1: seed.creator_quote_account.clone(),
+            creator_lp_token: accounts[5].clone(),
+            token_0_vault: accounts[6].clone(),
+            token_1_vault: accounts[7].clone(),
+            create_pool_fee: accounts[8].clone(),
+            observation_state: accounts[9].clone(),
+            token_program: seed.token_program.clone(),
+            token_0_program: seed.token_program.clone(),
+            token_1_program: seed.token_program.clone(),
+            associated_token_program: seed.associated_token_program.clone(),
+            system_program: seed.system_program.clone(),
+            rent: seed.rent.clone(),
+        };
+
+        cpi::initialize(
+            CpiContext::new(accounts[0].clone(), cpi_accounts),
+            seed.meme_amount,
+            seed.quote_amount,
+            seed.open_time,
+        )?;
+
+        Ok(amm_pool)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::endpoints::get_migration_accounts::derive_migration_accounts;
+
+    fn target_accounts(meme_mint: &Pubkey, quote_mint: &Pubkey) -> Vec<Pubkey> {
+        let derived = derive_migration_accounts(meme_mint, quote_mint, 0);
+
+        vec![
+            raydium_cpmm_cpi::ID,
+            derived.amm_config,
+            derived.raydium_authority,
+            derived.raydium_pool_state,
+            derived.raydium_lp_mint,
+            Pubkey::new_unique(),
+            derived.token_0_vault,
+            derived.token_1_vault,
+            raydium_cpmm_cpi::create_pool_fee_reveiver::id(),
+            derived.observation_state,
+        ]
+    }
+
+    #[test]
+    fn test_mint_ordering() {
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (low, high) = if a < b { (a, b) } else { (b, a) };
+
+        // Meme sorting first is what CPMM needs
+        validate_mint_ordering(&low, &high).unwrap();
+
+        // The other way round, or the same mint twice, can't be seeded
+        assert_eq!(
+            validate_mint_ordering(&high, &low).unwrap_err(),
+            error!(AmmError::InvalidMintOrdering)
+        );
+        assert_eq!(
+            validate_mint_ordering(&low, &low).unwrap_err(),
+            error!(AmmError::InvalidMintOrdering)
+        );
+
+        println!("✅ Mint ordering test passed!");
+    }
+
+    #[test]
+    fn test_raydium_target_accounts() {
+        // ARRANGE: An ordered pair as CPMM requires
+        let (a, b) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let (meme_mint, quote_mint) = if a < b { (a, b) } else { (b, a) };
+        let accounts = target_accounts(&meme_mint, &quote_mint);
+
+        println!("🧪 Testing the Raydium backend account checks");
+
+        // ACT & ASSERT: The derived accounts pass
+        RaydiumBackend::check_target_accounts(&meme_mint, &quote_mint, &accounts).unwrap();
+
+        // Swapped mints can't seed a CPMM pool
+        assert_eq!(
+            RaydiumBackend::check_target_accounts(&quote_mint, &meme_mint, &accounts).unwrap_err(),
+            error!(AmmError::InvalidMintOrdering)
+        );
+
+        // Missing accounts
+        assert_eq!(
+            RaydiumBackend::check_target_accounts(&meme_mint, &quote_mint, &accounts[..9])
+                .unwrap_err(),
+            error!(AmmError::InvalidRaydiumAccounts)
+        );
+
+        // A lookalike program, fee receiver or pool state
+        for index in [0, 3, 8] {
+            let mut wrong = accounts.clone();
+            wrong[index] = Pubkey::new_unique();
+
+            assert_eq!(
+                RaydiumBackend::check_target_accounts(&meme_mint, &quote_mint, &wrong).unwrap_err(),
+                error!(AmmError::InvalidRaydiumAccounts)
+            );
+        }
+
+        println!("✅ Raydium backend account checks test passed!");
+    }
+}
@@ -1,12 +1,17 @@
 /// Import necessary modules from crate
 use crate::{
-    consts::DECIMALS_S,
+    consts::{
+        ANCHOR_DISCRIMINATOR, BPS_DENOMINATOR, CURVE_TOLERANCE_BPS, DECIMALS_S, LINEAR_FAST_PATH_BPS, MAX_BATCH_BUYS, MAX_CURVE_DECIMALS,
+        MAX_PRICE_FACTOR, MAX_QUOTE_DECIMALS, MIN_PRICE_FACTOR,
+    },
     err::AmmError,
-    math::utils::{multiply_divide, CheckedMath, CheckedMath256},
+    libraries::MulDiv,
+    math::utils::{multiply_divide, u256_sqrt, CheckedMath, CheckedMath256},
 };
 
 /// Import Anchor lang prelude for Solana program development
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
 /// Import Solana program pubkey type
 use solana_program::pubkey::Pubkey;
@@ -18,7 +23,11 @@ use spl_math::uint::U256;
 use std::cmp::min;
 
 /// Import related models
-use super::{fees::Fees, Reserve, SwapAmount};
+use super::{
+    buyer_record::BuyerRecord,
+    fees::{get_fee_amount, get_gross_amount, Fees},
+    Reserve, SlippageDetail, SwapAmount,
+};
 
 /// Account struct representing a bonding curve pool
 #[account]
@@ -48,6 +57,28 @@ pub struct BoundPool {
     pub pool_migration: bool,
     /// Raydium pool public key (if migrated)
     pub pool_key: Pubkey,
+    /// Slot of the most recent swap against this pool
+    pub last_trade_slot: u64,
+    /// Lifetime SOL paid into the pool by buys, fees included
+    pub total_quote_volume: u128,
+    /// Lifetime meme tokens paid into the pool by sells, fees included
+    pub total_meme_volume: u128,
+    /// Number of distinct wallets which bought from the pool
+    pub unique_buyers: u32,
+    /// Creator's share of the quote fees, withdrawn via `withdraw_creator_fees`
+    pub creator_fees_quote: u64,
+    /// Sum of the spot price over every slot since launch, wrapping on overflow
+    pub price_cumulative: u128,
+    /// Slot `price_cumulative` was last brought up to date
+    pub last_twap_slot: u64,
+    /// Whether the first buy happened, and with it any first-buyer bonus
+    pub first_buy_done: bool,
+    /// Wallet proposed as the next creator, default key when none is pending
+    pub pending_creator: Pubkey,
+    /// Post-migration quote fees owed to stakers under `ResidualFeeMode::Stakers`
+    pub staker_fees_quote: u64,
+    /// Account layout version, `VERSION` for pools written by this program
+    pub version: u8,
 }
 
 impl BoundPool {
@@ -55,6 +86,113 @@ impl BoundPool {
     pub const POOL_PREFIX: &'static [u8; 10] = b"bound_pool";
     /// Prefix for signer PDA derivation
     pub const SIGNER_PDA_PREFIX: &'static [u8; 6] = b"signer";
+    /// Current account layout version. Bump it, and add an upgrade from the
+    /// previous layout, whenever a field is added to the pool or its config
+    pub const VERSION: u8 = 2;
+    /// Account size of a v1 pool, see `BoundPoolV1`
+    pub const V1_SPACE: usize = ANCHOR_DISCRIMINATOR + BoundPoolV1::INIT_SPACE;
+
+    /// Reads a v1 pool account, discriminator included, into the current
+    /// layout. Everything v1 didn't have starts as a v1 pool behaved: no
+    /// creator fee, guards off and a token target graduation. Anything else
+    /// is refused rather than guessed at.
+    pub fn upgrade_v1(data: &[u8]) -> Result<BoundPool> {
+        if data.len() != Self::V1_SPACE
+            || data[..ANCHOR_DISCRIMINATOR] != BoundPool::DISCRIMINATOR
+        {
+            return Err(error!(AmmError::UnsupportedPoolVersion));
+        }
+
+        let v1 = BoundPoolV1::deserialize(&mut &data[ANCHOR_DISCRIMINATOR..])?;
+
+        Ok(BoundPool {
+            meme_reserve: v1.meme_reserve,
+            quote_reserve: v1.quote_reserve,
+            admin_fees_meme: v1.admin_fees_meme,
+            admin_fees_quote: v1.admin_fees_quote,
+            fee_vault_quote: v1.fee_vault_quote,
+            creator_addr: v1.creator_addr,
+            fees: v1.fees,
+            config: Config {
+                alpha_abs: v1.config.alpha_abs,
+                beta: v1.config.beta,
+                price_factor_num: v1.config.price_factor_num,
+                price_factor_denom: v1.config.price_factor_denom,
+                gamma_s: v1.config.gamma_s,
+                gamma_m: v1.config.gamma_m,
+                omega_m: v1.config.omega_m,
+                decimals: v1.config.decimals,
+                creator_fee_bps: 0,
+                graduation_mode: GraduationMode::TokenTarget,
+                ..Config::default()
+            },
+            airdropped_tokens: v1.airdropped_tokens,
+            locked: v1.locked,
+            pool_migration: v1.pool_migration,
+            pool_key: v1.pool_key,
+            // A bonus for whoever trades next wasn't part of the v1 deal
+            first_buy_done: true,
+            version: Self::VERSION,
+            last_trade_slot: 0,
+            total_quote_volume: 0,
+            total_meme_volume: 0,
+            unique_buyers: 0,
+            creator_fees_quote: 0,
+            price_cumulative: 0,
+            last_twap_slot: 0,
+            pending_creator: Pubkey::default(),
+            staker_fees_quote: 0,
+        })
+    }
+
+    /// Re-derives the signer PDA of `pool_key` and checks `provided` is it.
+    pub fn assert_signer_pda(
+        program_id: &Pubkey,
+        pool_key: &Pubkey,
+        provided: &Pubkey,
+    ) -> Result<()> {
+        let (signer, _) = Pubkey::find_program_address(
+            &[Self::SIGNER_PDA_PREFIX, pool_key.as_ref()],
+            program_id,
+        );
+
+        if signer != *provided {
+            return Err(error!(AmmError::InvalidPoolSigner));
+        }
+
+        Ok(())
+    }
+}
+
+/// A pool as the first release wrote it, before `version` and everything
+/// after `pool_key` existed. Only read by `BoundPool::upgrade_v1`
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace)]
+struct BoundPoolV1 {
+    meme_reserve: Reserve,
+    quote_reserve: Reserve,
+    admin_fees_meme: u64,
+    admin_fees_quote: u64,
+    fee_vault_quote: Pubkey,
+    creator_addr: Pubkey,
+    fees: Fees,
+    config: ConfigV1,
+    airdropped_tokens: u64,
+    locked: bool,
+    pool_migration: bool,
+    pool_key: Pubkey,
+}
+
+/// The v1 `Config`, which ended at `decimals`
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace)]
+struct ConfigV1 {
+    alpha_abs: u128,
+    beta: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+    gamma_s: u64,
+    gamma_m: u64,
+    omega_m: u64,
+    decimals: Decimals,
 }
 
 /// Struct holding decimal configuration values
@@ -70,6 +208,85 @@ pub struct Decimals {
     pub quote: u64,
 }
 
+impl Decimals {
+    /// Checks every precision is a power of ten the curve math can work with.
+    pub fn validate(&self) -> Result<()> {
+        let curve_ok = |d: u128| is_power_of_ten(d) && d <= MAX_CURVE_DECIMALS;
+        let quote_ok = is_power_of_ten(self.quote as u128)
+            && self.quote <= 10u64.pow(MAX_QUOTE_DECIMALS as u32);
+
+        if !curve_ok(self.alpha) || !curve_ok(self.beta) || !quote_ok {
+            return Err(error!(AmmError::InvalidDecimalsConfig));
+        }
+
+        Ok(())
+    }
+
+    /// Checks that `quote` is the precision of a mint with `mint_decimals`.
+    pub fn check_quote_mint(&self, mint_decimals: u8) -> Result<()> {
+        if 10u64.checked_pow(mint_decimals as u32) != Some(self.quote) {
+            return Err(error!(AmmError::DecimalsMismatch));
+        }
+
+        Ok(())
+    }
+}
+
+/// Rule deciding when a pool may migrate to the AMM
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum GraduationMode {
+    /// Quote reserve reaches `MIGRATION_QUOTE_BPS` of the target (`gamma_s`)
+    QuotePercent,
+    /// 80% of the trading tokens have been sold
+    #[default]
+    TokenTarget,
+}
+
+/// AMM a pool's liquidity graduates into, each served by a
+/// `MigrationBackend` in `crate::migration`
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum MigrationTarget {
+    /// Raydium CPMM
+    #[default]
+    Raydium,
+    /// Orca Whirlpools
+    OrcaWhirlpool,
+}
+
+/// Where the fees of trades against the residual curve liquidity go once the
+/// pool migrated. Before migration they always accrue as admin fees.
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum ResidualFeeMode {
+    /// Accrue as admin fees, like before migration
+    #[default]
+    Treasury,
+    /// Meme fees are burned. Quote tokens can't be, so quote fees are added
+    /// to the residual quote reserve instead
+    Burn,
+    /// Quote fees are set aside for `route_fees_to_stakers`. Staking rewards
+    /// are paid in quote, so meme fees are burned
+    Stakers,
+}
+
+/// Shape of the rate `alpha_abs * supply + beta` the curve sells meme at
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum CurveType {
+    /// Slope and intercept derived from the price factor
+    #[default]
+    PositiveIntercept,
+    /// No intercept, `alpha_abs * supply` alone, priced in closed form. The
+    /// price at zero supply is undefined, so the pool needs virtual SOL
+    Linear,
+}
+
 /// Struct holding pool configuration parameters
 #[derive(
     AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
@@ -91,6 +308,75 @@ pub struct Config {
     pub omega_m: u64,
     /// Decimal configuration values
     pub decimals: Decimals,
+    /// Minimum number of slots between two trades on the pool (0 disables)
+    pub min_slot_between_trades: u64,
+    /// Share of every quote fee credited to the creator, in basis points
+    pub creator_fee_bps: u16,
+    /// Which threshold allows the pool to migrate
+    pub graduation_mode: GraduationMode,
+    /// Largest price move a single swap may cause, in basis points (0 disables)
+    pub max_price_impact_bps: u16,
+    /// Unbacked SOL the curve starts from, raising the opening price
+    pub virtual_sol_reserves: u64,
+    /// Meme the curve counts as already sold at the virtual SOL supply. It is
+    /// held back from buyers and never priced by the curve
+    pub virtual_meme_reserves: u64,
+    /// Smallest quote amount a buy may pay in (0 disables)
+    pub min_quote_in: u64,
+    /// Smallest meme amount a sell may pay in (0 disables)
+    pub min_meme_in: u64,
+    /// Seconds a wallet has to wait between two swaps on the pool (0 disables)
+    pub wallet_cooldown_secs: u64,
+    /// AMM the pool migrates into
+    pub migration_target: MigrationTarget,
+    /// Largest gap between the seeded AMM price and the curve's spot price,
+    /// in basis points (0 disables)
+    pub migration_price_tolerance_bps: u16,
+    /// Extra meme the very first buyer gets, in basis points of their buy
+    /// (0 disables)
+    pub first_buyer_bonus_bps: u16,
+    /// Meme that stays sold: sells are capped so the curve's quote reserve
+    /// keeps a sliver of liquidity instead of draining to zero (0 disables)
+    pub min_meme_reserve: u64,
+    /// Share of the unsold curve meme burned at migration instead of seeding
+    /// the AMM, in basis points (0 disables)
+    pub burn_unsold_bps: u16,
+    /// Distinct buyers a pool needs before it may migrate, so a single whale
+    /// can't graduate it alone (0 disables)
+    pub min_unique_buyers: u32,
+    /// Where post-migration trading fees go
+    pub residual_fee_mode: ResidualFeeMode,
+    /// Shape of the curve, `beta` is 0 on a linear one
+    pub curve_type: CurveType,
+}
+
+/// The optional trading guards of a pool, which launch disabled and the admin
+/// may tune through `update_pool_guards`. See `Config` for each field.
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct PoolGuards {
+    pub min_slot_between_trades: u64,
+    pub max_price_impact_bps: u16,
+    pub min_quote_in: u64,
+    pub min_meme_in: u64,
+    pub wallet_cooldown_secs: u64,
+    pub migration_price_tolerance_bps: u16,
+    pub min_meme_reserve: u64,
+    pub min_unique_buyers: u32,
+}
+
+impl PoolGuards {
+    /// Rejects basis point guards above 100% and a cooldown that doesn't fit
+    /// a unix timestamp.
+    pub fn check(&self) -> Result<()> {
+        if self.max_price_impact_bps as u64 > BPS_DENOMINATOR
+            || self.migration_price_tolerance_bps as u64 > BPS_DENOMINATOR
+            || self.wallet_cooldown_secs > i64::MAX as u64
+        {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        Ok(())
+    }
 }
 
 impl BoundPool {
@@ -99,253 +385,1227 @@ impl BoundPool {
         coin_in_amount: u64,
         coin_out_min_value: u64,
         buy_meme: bool,
-    ) -> SwapAmount {
-        if buy_meme {
+    ) -> Result<SwapAmount> {
+        if self.pool_migration {
+            self.residual_swap_amounts(coin_in_amount, coin_out_min_value, buy_meme)
+        } else if buy_meme {
             self.buy_meme_swap_amounts(coin_in_amount, coin_out_min_value)
-                .unwrap()
         } else {
             self.sell_meme_swap_amounts(coin_in_amount, coin_out_min_value)
-                .unwrap()
         }
     }
 
-    fn buy_meme_swap_amounts(&self, delta_s: u64, min_delta_m: u64) -> Result<SwapAmount> {
-        let (m_t0, s_t0) = self.balances();
+    /// The pool as quoted for a fee exempt wallet: same curve and reserves,
+    /// no admin fees in either direction.
+    pub fn without_fees(&self) -> BoundPool {
+        let mut pool = self.clone();
+        pool.fees = Fees::default();
 
-        let p = &self.config;
+        pool
+    }
 
-        let max_delta_s = p.gamma_s - s_t0;
+    /// Whether the pool may be quoted or traded against. The one place
+    /// deciding it, so every swap and preview honours the same flags. Only a
+    /// pool locked awaiting migration refuses trades; once migrated it trades
+    /// the reserves it left on the curve, locked or not.
+    pub fn is_tradeable(&self) -> bool {
+        !self.locked || self.pool_migration
+    }
 
-        let admin_fee_in = self.fees.get_fee_quote_amount(delta_s).unwrap();
-        let is_max = delta_s - admin_fee_in >= max_delta_s;
+    /// Whether `signer` is the creator recorded at `new_pool`. Gates every
+    /// creator-only endpoint.
+    pub fn is_creator(&self, signer: &Pubkey) -> bool {
+        *signer == self.creator_addr
+    }
 
-        let net_delta_s = min(delta_s - admin_fee_in, max_delta_s);
+    /// First step of a creator handoff. The creator only changes once
+    /// `new_creator` accepts, so a mistyped key can't take over the pool.
+    /// Proposing the default key withdraws a pending proposal.
+    pub fn propose_creator(&mut self, new_creator: Pubkey) {
+        self.pending_creator = new_creator;
+    }
 
-        let delta_m = if is_max {
-            m_t0
-        } else {
-            self.compute_delta_m(s_t0, s_t0 + net_delta_s)?
-        };
+    /// Second step of a creator handoff, signed by the proposed wallet.
+    pub fn accept_creator(&mut self, signer: &Pubkey) -> Result<()> {
+        if self.pending_creator == Pubkey::default() || self.pending_creator != *signer {
+            return Err(error!(AmmError::NotPendingCreator));
+        }
 
-        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m).unwrap();
-        let net_delta_m = delta_m - admin_fee_out;
+        self.creator_addr = std::mem::take(&mut self.pending_creator);
 
-        if net_delta_m < min_delta_m {
-            return Err(error!(AmmError::SlippageExceeded));
+        Ok(())
+    }
+
+    /// Rejects quoting or trading against a pool which isn't `is_tradeable`.
+    pub fn check_tradeable(&self) -> Result<()> {
+        if !self.is_tradeable() {
+            return Err(error!(AmmError::PoolIsLocked));
         }
 
-        Ok(SwapAmount {
-            amount_in: net_delta_s,
-            amount_out: net_delta_m,
-            admin_fee_in,
-            admin_fee_out,
-        })
+        Ok(())
     }
 
-    fn sell_meme_swap_amounts(&self, delta_m: u64, min_delta_s: u64) -> Result<SwapAmount> {
-        let (m_b, s_b) = self.balances();
+    /// Rejects a swap paying in less than the configured minimum for its
+    /// direction, `min_quote_in` for buys and `min_meme_in` for sells.
+    pub fn check_min_trade(&self, amount_in: u64, buy_meme: bool) -> Result<()> {
+        let min_in = if buy_meme {
+            self.config.min_quote_in
+        } else {
+            self.config.min_meme_in
+        };
 
-        let p = &self.config;
+        if amount_in < min_in {
+            return Err(error!(AmmError::BelowMinimumTrade));
+        }
 
-        let max_delta_m = p.gamma_m - m_b;
+        Ok(())
+    }
 
-        let admin_fee_in = self.fees.get_fee_meme_amount(delta_m).unwrap() * 2;
-        let is_max = delta_m - admin_fee_in >= max_delta_m;
+    /// Rejects a trade landing fewer than `min_slot_between_trades` slots
+    /// after the previous one, then records `current_slot` as the last trade.
+    pub fn check_trade_slot(&mut self, current_slot: u64) -> Result<()> {
+        let min_slots = self.config.min_slot_between_trades;
 
-        let net_delta_m = min(delta_m - admin_fee_in, max_delta_m);
+        if min_slots > 0 && current_slot.saturating_sub(self.last_trade_slot) < min_slots {
+            return Err(error!(AmmError::TradeTooSoon));
+        }
 
-        let delta_s = if is_max {
-            s_b
-        } else {
-            self.compute_delta_s(s_b, net_delta_m)?
-        };
+        self.last_trade_slot = current_slot;
 
-        let admin_fee_out = self.fees.get_fee_quote_amount(delta_s).unwrap() * 2;
-        let net_delta_s = delta_s - admin_fee_out;
+        Ok(())
+    }
 
-        if net_delta_s < min_delta_s {
-            return Err(error!(AmmError::SlippageExceeded));
+    /// Brings `price_cumulative` up to `current_slot` with the price held
+    /// since the last update. Called before a swap moves the price.
+    pub fn update_twap(&mut self, current_slot: u64) -> Result<()> {
+        if current_slot > self.last_twap_slot {
+            self.price_cumulative = self.cumulative_price_at(current_slot)?;
+            self.last_twap_slot = current_slot;
         }
 
-        Ok(SwapAmount {
-            amount_in: net_delta_m,
-            amount_out: net_delta_s,
-            admin_fee_in,
-            admin_fee_out,
-        })
+        Ok(())
     }
 
-    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
-    /// Formula: price = +alpha_abs * supply + beta (positive intercept)
-    pub fn compute_delta_m(&self, s_a: u64, s_b: u64) -> Result<u64> {
-        let s_a = s_a as u128;
-        let s_b = s_b as u128;
-
-        let alpha_abs = self.config.alpha_abs;
-        let beta = self.config.beta;
-        let alpha_decimals = self.config.decimals.alpha;
-        let beta_decimals = self.config.decimals.beta;
+    /// `price_cumulative` as it would read at `slot`, the current spot price
+    /// having held since the last update.
+    pub fn cumulative_price_at(&self, slot: u64) -> Result<u128> {
+        let elapsed = slot.saturating_sub(self.last_twap_slot) as u128;
 
-        return match delta_m1_positive_strategy(
-            alpha_abs,
-            beta,
-            alpha_decimals,
-            beta_decimals,
-            s_a,
-            s_b,
-        ) {
-            Some(delta_m) => Ok(delta_m as u64),
-            None => {
-                match delta_m2_positive_strategy(
-                    alpha_abs,
-                    beta,
-                    alpha_decimals,
-                    beta_decimals,
-                    s_a,
-                    s_b,
-                ) {
-                    Some(delta_m) => Ok(delta_m as u64),
-                    None => Err(error!(AmmError::MathOverflow)),
-                }
-            }
-        };
+        Ok(self
+            .price_cumulative
+            .wrapping_add(self.spot_price()? as u128 * elapsed))
     }
 
-    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
-    pub fn compute_delta_s(&self, s_b: u64, delta_m: u64) -> Result<u64> {
-        let s_b = s_b as u128;
-        let delta_m = delta_m as u128;
+    /// Average spot price between `start_slot`, when the accumulator read
+    /// `start_cumulative`, and `current_slot`. Differences are taken wrapping,
+    /// so a window spanning an accumulator overflow still averages right.
+    pub fn read_twap(
+        &self,
+        start_cumulative: u128,
+        start_slot: u64,
+        current_slot: u64,
+    ) -> Result<u64> {
+        if current_slot <= start_slot {
+            return Err(error!(AmmError::InvalidArg));
+        }
 
-        let alpha_abs = self.config.alpha_abs;
-        let beta = self.config.beta;
-        let alpha_decimals = self.config.decimals.alpha;
-        let beta_decimals = self.config.decimals.beta;
+        let window = (current_slot - start_slot) as u128;
+        let average = self
+            .cumulative_price_at(current_slot)?
+            .wrapping_sub(start_cumulative)
+            / window;
 
-        match delta_s_positive_strategy(
-            alpha_abs,
-            beta,
-            alpha_decimals,
-            beta_decimals,
-            s_b,
-            delta_m,
-        ) {
-            Some(delta_s) => Ok(delta_s as u64),
-            None => Err(error!(AmmError::MathOverflow)),
-        }
+        u64::try_from(average).map_err(|_| error!(AmmError::MathOverflow))
     }
 
-    fn balances(&self) -> (u64, u64) {
-        (self.meme_reserve.tokens, self.quote_reserve.tokens)
+    /// Registers `buyer` on its record and bumps `unique_buyers` when this is
+    /// the wallet's first buy from the pool.
+    pub fn record_buyer(
+        &mut self,
+        record: &mut BuyerRecord,
+        pool: Pubkey,
+        buyer: Pubkey,
+        slot: u64,
+    ) -> Result<()> {
+        if record.register(pool, buyer, slot) {
+            self.unique_buyers = self
+                .unique_buyers
+                .checked_add(1)
+                .ok_or(error!(AmmError::MathOverflow))?;
+        }
+
+        Ok(())
     }
-}
 
-/// CHANGED: Updated for positive slope calculation with POSITIVE intercept
-pub fn compute_alpha_abs(
-    gamma_s: u128,
-    gamma_s_denom: u128,
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-) -> Result<(u128, u128)> {
-    check_slope(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+    /// Locks the pool once its meme reserve is depleted. Returns `true` only
+    /// on the swap that sells it out, so the lock is reported once.
+    pub fn lock_if_sold_out(&mut self) -> bool {
+        if self.locked || self.meme_reserve.tokens != 0 {
+            return false;
+        }
 
-    let left = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        self.locked = true;
 
-    // For positive slope: price increases with supply
-    // Formula: alpha = 2 * (left - gamma_m) * gamma_s_denom^2 / gamma_s^2
-    let num = U256::from(2 * (left - gamma_m)) * U256::from(gamma_s_denom * gamma_s_denom);
-    let denom = U256::from(gamma_s * gamma_s);
+        true
+    }
 
-    if num <= denom {
-        return Err(error!(AmmError::EGammaSAboveRelativeLimit));
+    /// Rejects a pool which already migrated, or started to.
+    pub fn check_not_migrated(&self) -> Result<()> {
+        if self.pool_migration || self.pool_key != Pubkey::default() {
+            return Err(error!(AmmError::AlreadyMigrated));
+        }
+
+        Ok(())
     }
 
-    // Calculate the scale (order of magnitude) of numerator and denominator
-    let num_scale = compute_scale(num.as_u128());
-    let denom_scale = compute_scale(denom.as_u128());
+    /// Rejects aborting a launch once anything was traded on it. Volume is
+    /// checked too, since a round trip can leave the quote reserve empty.
+    pub fn check_abortable(&self) -> Result<()> {
+        self.check_not_migrated()?;
 
-    // Get the difference in scales to determine required decimal precision
-    let net_scale = num_scale - denom_scale;
+        if self.quote_reserve.tokens != 0
+            || self.total_quote_volume != 0
+            || self.total_meme_volume != 0
+        {
+            return Err(error!(AmmError::LaunchAlreadyTraded));
+        }
 
-    // Convert net_scale to appropriate decimal precision for alpha
-    let alpha_decimals = U256::from(compute_decimals(net_scale)?);
+        Ok(())
+    }
 
-    // For positive slope, we keep the sign positive
-    Ok((
-        ((num * alpha_decimals) / denom).as_u128(),
-        alpha_decimals.as_u128(),
-    ))
-}
+    /// Checks that buying the whole quote target off an empty curve hands out
+    /// the trading supply, within `CURVE_TOLERANCE_BPS`.
+    pub fn check_curve_consistency(&self) -> Result<()> {
+        let gamma_m = self.config.gamma_m;
+        let sold = self
+            .compute_delta_m(0, self.config.gamma_s)
+            .map_err(|_| error!(AmmError::CurveParameterMismatch))?;
+
+        let drift = sold.abs_diff(gamma_m) as u128 * BPS_DENOMINATOR as u128;
+        if drift > gamma_m as u128 * CURVE_TOLERANCE_BPS as u128 {
+            return Err(error!(AmmError::CurveParameterMismatch));
+        }
 
-pub fn compute_decimals(scale: u64) -> Result<u128> {
-    match scale {
-        0..=4 => return Err(error!(AmmError::EScaleTooLow)),
-        5 => Ok(100_000_000),
-        6 => Ok(10_000_000),
-        7 => Ok(1_000_000),
-        8 => Ok(100_000),
-        9 => Ok(10_000),
-        10 => Ok(1_000),
-        11 => Ok(100),
-        12 => Ok(10),
-        _ => Ok(1), // If scale is above 12
+        Ok(())
     }
-}
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept
-pub fn compute_beta(
-    gamma_s: u128,
-    gamma_s_denom: u128,
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-    beta_decimals: u128,
-) -> Result<u128> {
-    check_intercept(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+    /// Checks the virtual offsets leave a curve to trade on: the virtual SOL
+    /// must stay below the target so `max_delta_s` is positive, and the meme
+    /// held back can't exceed the real reserve. A linear curve also needs
+    /// some virtual SOL to have a spot price at launch.
+    pub fn check_virtual_reserves(&self) -> Result<()> {
+        let p = &self.config;
 
-    let left = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        if p.virtual_sol_reserves >= p.gamma_s
+            || p.virtual_meme_reserves > self.meme_reserve.tokens
+            || (p.curve_type == CurveType::Linear && p.virtual_sol_reserves == 0)
+        {
+            return Err(error!(AmmError::InvalidVirtualReserves));
+        }
 
-    // For positive slope with positive intercept:
-    // beta = (2 * gamma_m - left) * gamma_s_denom / gamma_s
-    let right = 2 * gamma_m;
+        Ok(())
+    }
 
-    // Now we want a positive beta, so we calculate (right - left)
-    let num = (right - left) * gamma_s_denom;
-    let denom = gamma_s;
+    /// Whether `vault` holds the pool's meme or quote reserve.
+    pub fn is_reserve_vault(&self, vault: &Pubkey) -> bool {
+        *vault == self.meme_reserve.vault || *vault == self.quote_reserve.vault
+    }
 
-    Ok((num * beta_decimals) / denom)
-}
+    /// Replaces the pool's fees, returning the previous ones. Refused once
+    /// the pool migrated or with either rate above its cap.
+    pub fn update_fees(&mut self, fees: Fees) -> Result<Fees> {
+        self.check_not_migrated()?;
+        fees.check_quote_fee()?;
+        fees.check_meme_fee()?;
 
-/// CHANGED: For positive slope bonding curve - price increases as supply increases
-pub fn check_slope(
-    gamma_m: u128,
-    omega_m: u128,
-    price_factor_num: u64,
-    price_factor_denom: u64,
-) -> Result<()> {
-    let pfo = omega_m
-        .checked_mul(price_factor_num as u128)
-        .checked_div(price_factor_denom as u128)
-        .unwrap();
+        Ok(std::mem::replace(&mut self.fees, fees))
+    }
 
-    // For positive slope: omega_m * price_factor must be GREATER than gamma_m
-    // This ensures positive slope (price increases with supply)
-    if pfo <= gamma_m {
-        return Err(error!(AmmError::BondingCurveMustBePositivelySloped));
+    /// The pool's current trading guards.
+    pub fn guards(&self) -> PoolGuards {
+        PoolGuards {
+            min_slot_between_trades: self.config.min_slot_between_trades,
+            max_price_impact_bps: self.config.max_price_impact_bps,
+            min_quote_in: self.config.min_quote_in,
+            min_meme_in: self.config.min_meme_in,
+            wallet_cooldown_secs: self.config.wallet_cooldown_secs,
+            migration_price_tolerance_bps: self.config.migration_price_tolerance_bps,
+            min_meme_reserve: self.config.min_meme_reserve,
+            min_unique_buyers: self.config.min_unique_buyers,
+        }
     }
 
-    Ok(())
-}
+    /// Replaces the pool's trading guards, returning the previous ones.
+    /// Refused once the pool migrated, with a guard out of range or with a
+    /// reserve floor above the trading supply.
+    pub fn update_guards(&mut self, guards: PoolGuards) -> Result<PoolGuards> {
+        self.check_not_migrated()?;
+        guards.check()?;
 
-/// CHANGED: For positive slope bonding curve with POSITIVE intercept
+        if guards.min_meme_reserve > self.config.gamma_m {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        let previous = self.guards();
+        self.config.min_slot_between_trades = guards.min_slot_between_trades;
+        self.config.max_price_impact_bps = guards.max_price_impact_bps;
+        self.config.min_quote_in = guards.min_quote_in;
+        self.config.min_meme_in = guards.min_meme_in;
+        self.config.wallet_cooldown_secs = guards.wallet_cooldown_secs;
+        self.config.migration_price_tolerance_bps = guards.migration_price_tolerance_bps;
+        self.config.min_meme_reserve = guards.min_meme_reserve;
+        self.config.min_unique_buyers = guards.min_unique_buyers;
+
+        Ok(previous)
+    }
+
+    /// Checks the meme reserve fits in the trading supply, so `meme_sold`
+    /// can't underflow.
+    pub fn check_meme_reserve(&self) -> Result<()> {
+        if self.meme_reserve.tokens > self.config.gamma_m {
+            return Err(error!(AmmError::MemeReserveExceedsSupply));
+        }
+
+        Ok(())
+    }
+
+    /// Checks a freshly created pool starts empty of quote, with the whole
+    /// trading supply on the curve, and that the meme vault actually holds
+    /// the trading supply the reserve accounts for.
+    pub fn check_funded(&self, meme_vault_amount: u64) -> Result<()> {
+        if self.quote_reserve.tokens != 0
+            || self.meme_reserve.tokens != self.config.gamma_m
+            || meme_vault_amount < self.config.gamma_m
+        {
+            return Err(error!(AmmError::PoolNotProperlyFunded));
+        }
+
+        Ok(())
+    }
+
+    /// Marks the first buy done, returning the bonus meme it earns on top of
+    /// `amount_out`. The bonus comes out of the vault's supply held off the
+    /// curve, so reserves are untouched. Every later buy gets 0.
+    pub fn claim_first_buyer_bonus(&mut self, amount_out: u64) -> Result<u64> {
+        if self.first_buy_done {
+            return Ok(0);
+        }
+
+        self.first_buy_done = true;
+
+        amount_out
+            .mul_div_floor(self.config.first_buyer_bonus_bps as u64, BPS_DENOMINATOR)
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Meme tokens bought off the curve so far, i.e. the most a sell can
+    /// return to it.
+    pub fn meme_sold(&self) -> Result<u64> {
+        self.config
+            .gamma_m
+            .checked_sub(self.meme_reserve.tokens)
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Meme a sell may return to the curve, i.e. what was sold less the
+    /// `min_meme_reserve` floor.
+    pub fn sellable_meme(&self) -> Result<u64> {
+        Ok(self.meme_sold()?.saturating_sub(self.config.min_meme_reserve))
+    }
+
+    /// Share of the trading supply sold so far, in basis points.
+    pub fn percent_sold(&self) -> Result<u64> {
+        self.meme_sold()?
+            .mul_div_floor(BPS_DENOMINATOR, self.config.gamma_m)
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Records the migration to `amm_pool`. Both markers are set together so
+    /// `check_not_migrated` trips on either.
+    pub fn mark_migrated(&mut self, amm_pool: Pubkey) -> Result<()> {
+        self.check_not_migrated()?;
+
+        self.pool_migration = true;
+        self.pool_key = amm_pool;
+
+        Ok(())
+    }
+
+    /// Deducts a withdrawal from the accrued admin fees, rejecting amounts
+    /// above what has accrued.
+    pub fn take_admin_fees(&mut self, quote_amount: u64, meme_amount: u64) -> Result<()> {
+        if quote_amount > self.admin_fees_quote || meme_amount > self.admin_fees_meme {
+            return Err(error!(AmmError::InsufficientAccruedFees));
+        }
+
+        self.admin_fees_quote -= quote_amount;
+        self.admin_fees_meme -= meme_amount;
+
+        Ok(())
+    }
+
+    /// Credits a quote fee, splitting off the creator's `creator_fee_bps`
+    /// share. The protocol keeps the rounding remainder.
+    pub fn accrue_quote_fees(&mut self, fee: u64) -> Result<()> {
+        let creator_fee = fee
+            .mul_div_floor(self.config.creator_fee_bps as u64, BPS_DENOMINATOR)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        self.creator_fees_quote = self
+            .creator_fees_quote
+            .checked_add(creator_fee)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        self.admin_fees_quote = self
+            .admin_fees_quote
+            .checked_add(fee - creator_fee)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Where the fees of a trade go at this point of the pool's life.
+    pub fn fee_destination(&self) -> ResidualFeeMode {
+        if self.pool_migration {
+            self.config.residual_fee_mode
+        } else {
+            ResidualFeeMode::Treasury
+        }
+    }
+
+    /// Books the fees of a trade at its `fee_destination`. Returns the meme
+    /// fee nobody is credited with, which the caller burns out of the vault.
+    pub fn accrue_swap_fees(&mut self, quote_fee: u64, meme_fee: u64) -> Result<u64> {
+        match self.fee_destination() {
+            ResidualFeeMode::Treasury => {
+                self.accrue_quote_fees(quote_fee)?;
+                self.admin_fees_meme = self
+                    .admin_fees_meme
+                    .checked_add(meme_fee)
+                    .ok_or(error!(AmmError::MathOverflow))?;
+
+                return Ok(0);
+            }
+            ResidualFeeMode::Burn => {
+                self.quote_reserve.tokens = self
+                    .quote_reserve
+                    .tokens
+                    .checked_add(quote_fee)
+                    .ok_or(error!(AmmError::MathOverflow))?;
+            }
+            ResidualFeeMode::Stakers => {
+                self.staker_fees_quote = self
+                    .staker_fees_quote
+                    .checked_add(quote_fee)
+                    .ok_or(error!(AmmError::MathOverflow))?;
+            }
+        }
+
+        Ok(meme_fee)
+    }
+
+    /// Deducts quote fees routed to the staking pool, drawing on the fees
+    /// owed to stakers before the admin fees. Nothing is deducted unless the
+    /// two together cover `quote_amount`.
+    pub fn take_fees_for_stakers(&mut self, quote_amount: u64) -> Result<()> {
+        let from_stakers = min(quote_amount, self.staker_fees_quote);
+
+        self.take_admin_fees(quote_amount - from_stakers, 0)?;
+        self.staker_fees_quote -= from_stakers;
+
+        Ok(())
+    }
+
+    /// Checks the vault balances still back the books: every reserve plus the
+    /// fees accrued on it. Vaults may hold more (the LP and creator
+    /// allocations, donations) but never less.
+    pub fn check_reserve_invariant(&self, meme_vault: u64, quote_vault: u64) -> Result<()> {
+        let meme_owed = self.meme_reserve.tokens as u128 + self.admin_fees_meme as u128;
+        let quote_owed = self.quote_reserve.tokens as u128
+            + self.admin_fees_quote as u128
+            + self.creator_fees_quote as u128
+            + self.staker_fees_quote as u128;
+
+        if (meme_vault as u128) < meme_owed || (quote_vault as u128) < quote_owed {
+            return Err(error!(AmmError::ReserveInvariantViolated));
+        }
+
+        Ok(())
+    }
+
+    /// Deducts a withdrawal from the creator's accrued quote fees.
+    pub fn take_creator_fees(&mut self, quote_amount: u64) -> Result<()> {
+        if quote_amount > self.creator_fees_quote {
+            return Err(error!(AmmError::InsufficientAccruedFees));
+        }
+
+        self.creator_fees_quote -= quote_amount;
+
+        Ok(())
+    }
+
+    /// Decimal places of `spot_price`: dividing it by `10^price_decimals`
+    /// gives whole quote tokens per meme base unit. Follows the pool's quote
+    /// decimals, so consumers needn't assume them.
+    pub fn price_decimals(&self) -> u8 {
+        (DECIMALS_S.ilog10() + self.config.decimals.quote.ilog10()) as u8
+    }
+
+    /// Current spot price in quote units per meme unit, scaled by `DECIMALS_S`.
+    pub fn spot_price(&self) -> Result<u64> {
+        if self.pool_migration {
+            return residual_price(self.meme_reserve.tokens, self.quote_reserve.tokens);
+        }
+
+        let (_, s) = self.balances();
+
+        self.spot_price_at(s)
+    }
+
+    fn spot_price_at(&self, s: u64) -> Result<u64> {
+        let p = &self.config;
+
+        let price = curve_rate(
+            p.alpha_abs,
+            p.beta,
+            p.decimals.alpha,
+            p.decimals.beta,
+            s as u128,
+        )
+        .and_then(|(num, denom)| {
+            U256::from(DECIMALS_S)
+                .checked_mul(denom)
+                .checked_div(num)
+        })
+        .filter(|price| *price <= U256::from(u64::MAX))
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(price.as_u64())
+    }
+
+    /// Rejects a swap moving the spot price by more than
+    /// `max_price_impact_bps` in either direction.
+    pub fn check_price_impact(&self, swap_amount: &SwapAmount, buy_meme: bool) -> Result<()> {
+        let max_impact_bps = self.config.max_price_impact_bps as u64;
+
+        if max_impact_bps == 0 {
+            return Ok(());
+        }
+
+        if self.price_impact_bps(swap_amount, buy_meme)? > max_impact_bps {
+            return Err(error!(AmmError::PriceImpactTooHigh));
+        }
+
+        Ok(())
+    }
+
+    /// How far a swap moves the spot price, in basis points rounded up.
+    pub fn price_impact_bps(&self, swap_amount: &SwapAmount, buy_meme: bool) -> Result<u64> {
+        let (price_before, price_after) = if self.pool_migration {
+            let (m, s) = (self.meme_reserve.tokens, self.quote_reserve.tokens);
+            let gross_out = swap_amount.amount_out + swap_amount.admin_fee_out;
+            let (m_after, s_after) = if buy_meme {
+                (m.checked_sub(gross_out), s.checked_add(swap_amount.amount_in))
+            } else {
+                (m.checked_add(swap_amount.amount_in), s.checked_sub(gross_out))
+            };
+            let (m_after, s_after) = m_after
+                .zip(s_after)
+                .ok_or(error!(AmmError::MathOverflow))?;
+
+            (residual_price(m, s)?, residual_price(m_after, s_after)?)
+        } else {
+            let (_, s) = self.balances();
+            let s_after = if buy_meme {
+                s.checked_add(swap_amount.amount_in)
+            } else {
+                s.checked_sub(swap_amount.amount_out + swap_amount.admin_fee_out)
+            }
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+            (self.spot_price_at(s)?, self.spot_price_at(s_after)?)
+        };
+
+        price_before
+            .abs_diff(price_after)
+            .mul_div_ceil(BPS_DENOMINATOR, price_before)
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Adds the gross input of a swap to the lifetime volume counters.
+    pub fn record_volume(&mut self, swap_amount: &SwapAmount, buy_meme: bool) -> Result<()> {
+        let gross_in = swap_amount.amount_in as u128 + swap_amount.admin_fee_in as u128;
+
+        let total = if buy_meme {
+            &mut self.total_quote_volume
+        } else {
+            &mut self.total_meme_volume
+        };
+
+        *total = total
+            .checked_add(gross_in)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    fn buy_meme_swap_amounts(&self, delta_s: u64, min_delta_m: u64) -> Result<SwapAmount> {
+        let (m_t0, s_t0) = self.balances();
+
+        let p = &self.config;
+
+        let max_delta_s = p.gamma_s - s_t0;
+
+        let mut admin_fee_in = self.fees.get_fee_quote_amount(delta_s)?;
+
+        // Dust eaten whole by the rounded up fee would buy nothing
+        if admin_fee_in == delta_s {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        let is_max = delta_s - admin_fee_in >= max_delta_s;
+
+        let net_delta_s = min(delta_s - admin_fee_in, max_delta_s);
+
+        // The final buy only pays for what fills the curve, the rest stays with the user
+        if is_max {
+            let gross_delta_s = min(
+                get_gross_amount(net_delta_s, self.fees.fee_quote_percent)?,
+                delta_s,
+            );
+            admin_fee_in = gross_delta_s - net_delta_s;
+        }
+
+        // Rounding near the end of the curve can price more meme than is left
+        let delta_m = if is_max {
+            m_t0
+        } else {
+            min(self.compute_delta_m(s_t0, s_t0 + net_delta_s)?, m_t0)
+        };
+
+        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m)?;
+        let net_delta_m = delta_m - admin_fee_out;
+
+        if net_delta_m + admin_fee_out > m_t0 {
+            return Err(error!(AmmError::ExceedsPoolReserve));
+        }
+
+        if net_delta_m < min_delta_m {
+            return Err(SlippageDetail::exceeded(net_delta_m, min_delta_m));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_s,
+            amount_out: net_delta_m,
+            admin_fee_in,
+            admin_fee_out,
+        })
+    }
+
+    /// Quotes a swap against the reserves a migrated pool left on the curve.
+    /// The curve's position no longer matches them, so they trade as a
+    /// constant product pool, opening at the ratio the AMM was seeded with.
+    fn residual_swap_amounts(
+        &self,
+        amount_in: u64,
+        min_amount_out: u64,
+        buy_meme: bool,
+    ) -> Result<SwapAmount> {
+        let (reserve_in, reserve_out) = if buy_meme {
+            (self.quote_reserve.tokens, self.meme_reserve.tokens)
+        } else {
+            (self.meme_reserve.tokens, self.quote_reserve.tokens)
+        };
+        let fee = |amount: u64, quote: bool| {
+            if quote {
+                self.fees.get_fee_quote_amount(amount)
+            } else {
+                self.fees.get_fee_meme_amount(amount)
+            }
+        };
+
+        let admin_fee_in = fee(amount_in, buy_meme)?;
+        let net_in = amount_in - admin_fee_in;
+
+        let gross_out = reserve_out
+            .mul_div_floor(
+                net_in,
+                reserve_in
+                    .checked_add(net_in)
+                    .ok_or(error!(AmmError::MathOverflow))?,
+            )
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        // Dust, or a drained side, buys nothing
+        if gross_out == 0 {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        let admin_fee_out = fee(gross_out, !buy_meme)?;
+        let amount_out = gross_out - admin_fee_out;
+
+        if amount_out < min_amount_out {
+            return Err(SlippageDetail::exceeded(amount_out, min_amount_out));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_in,
+            amount_out,
+            admin_fee_in,
+            admin_fee_out,
+        })
+    }
+
+    fn sell_meme_swap_amounts(&self, delta_m: u64, min_delta_s: u64) -> Result<SwapAmount> {
+        let (_, s_b) = self.balances();
+        let real_s = self.quote_reserve.tokens;
+
+        // Nothing to pay the seller with
+        if real_s == 0 {
+            return Err(error!(AmmError::ExceedsPoolReserve));
+        }
+
+        // Only meme actually bought off the curve can come back, down to the floor
+        let max_delta_m = self.sellable_meme()?;
+
+        // Nothing above the floor to sell back
+        if max_delta_m == 0 {
+            return Err(error!(AmmError::ExceedsPoolReserve));
+        }
+
+        // Doubled fees are capped so a dust sell can't be charged more than it moves
+        let mut admin_fee_in = min(self.fees.get_fee_meme_amount(delta_m)? * 2, delta_m);
+
+        let is_max = delta_m - admin_fee_in >= max_delta_m;
+
+        let net_delta_m = min(delta_m - admin_fee_in, max_delta_m);
+
+        // Like the final buy, a sell refilling the curve only pays for what fits
+        if is_max {
+            let gross_delta_m = min(
+                gross_up_sell_fee(net_delta_m, self.fees.fee_meme_percent)?,
+                delta_m,
+            );
+            admin_fee_in = gross_delta_m - net_delta_m;
+        }
+
+        // Virtual SOL is never paid out, and with a floor the curve isn't
+        // refilled, so a capped sell is priced like any other
+        let delta_s = if is_max && self.config.min_meme_reserve == 0 {
+            real_s
+        } else {
+            min(self.compute_delta_s(s_b, net_delta_m)?, real_s)
+        };
+
+        let admin_fee_out = min(self.fees.get_fee_quote_amount(delta_s)? * 2, delta_s);
+        let net_delta_s = delta_s - admin_fee_out;
+
+        if net_delta_s < min_delta_s {
+            return Err(SlippageDetail::exceeded(net_delta_s, min_delta_s));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_m,
+            amount_out: net_delta_s,
+            admin_fee_in,
+            admin_fee_out,
+        })
+    }
+
+    /// Quotes a buy of exactly `delta_m_out` meme tokens. The required SOL is
+    /// found by inverting the curve with `compute_delta_s`, then grossed up by
+    /// the quote fee. Requests above the remaining meme reserve are capped to
+    /// what is left on the curve. Migrated pools only take exact-in swaps.
+    pub fn buy_meme_exact_out_amounts(
+        &self,
+        delta_m_out: u64,
+        max_delta_s: u64,
+    ) -> Result<SwapAmount> {
+        self.check_not_migrated()?;
+
+        let (m_t0, s_t0) = self.balances();
+
+        let p = &self.config;
+
+        let max_net_delta_s = p.gamma_s - s_t0;
+
+        // Meme leaving the pool so that the user nets `delta_m_out`
+        let delta_m = get_gross_amount(delta_m_out, self.fees.fee_meme_percent)?;
+
+        let (delta_m, net_delta_s) = if delta_m >= m_t0 {
+            (m_t0, max_net_delta_s)
+        } else {
+            (
+                delta_m,
+                self.delta_s_for_delta_m(s_t0, delta_m, max_net_delta_s)?,
+            )
+        };
+
+        let admin_fee_out = self.fees.get_fee_meme_amount(delta_m)?;
+        let net_delta_m = delta_m - admin_fee_out;
+
+        let gross_delta_s = get_gross_amount(net_delta_s, self.fees.fee_quote_percent)?;
+
+        if gross_delta_s > max_delta_s {
+            return Err(SlippageDetail::exceeded(gross_delta_s, max_delta_s));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_s,
+            amount_out: net_delta_m,
+            admin_fee_in: gross_delta_s - net_delta_s,
+            admin_fee_out,
+        })
+    }
+
+    /// Quotes a series of buys executed back to back, each against the curve
+    /// left by the previous one and each with its own slippage floor. Returns
+    /// the sum of the individual buys.
+    pub fn buy_meme_batch_amounts(&self, amounts: &[u64], min_outs: &[u64]) -> Result<SwapAmount> {
+        if amounts.is_empty() || amounts.len() != min_outs.len() {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        if amounts.len() > MAX_BATCH_BUYS {
+            return Err(error!(AmmError::BatchTooLarge));
+        }
+
+        let mut pool = self.clone();
+        let mut total = SwapAmount {
+            amount_in: 0,
+            amount_out: 0,
+            admin_fee_in: 0,
+            admin_fee_out: 0,
+        };
+
+        for (&amount, &min_out) in amounts.iter().zip(min_outs) {
+            if amount == 0 {
+                return Err(error!(AmmError::NoZeroTokens));
+            }
+
+            let swap = pool.buy_meme_swap_amounts(amount, min_out)?;
+
+            pool.quote_reserve.tokens += swap.amount_in;
+            pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+
+            total.amount_in += swap.amount_in;
+            total.amount_out += swap.amount_out;
+            total.admin_fee_in += swap.admin_fee_in;
+            total.admin_fee_out += swap.admin_fee_out;
+        }
+
+        Ok(total)
+    }
+
+    /// Quotes a sell which pays out exactly `delta_s_out` SOL. The meme tokens
+    /// required are found by inverting the curve and grossed up by the sell
+    /// fee. Requests above the quote reserve are rejected. Migrated pools
+    /// only take exact-in swaps.
+    pub fn sell_meme_exact_out_amounts(
+        &self,
+        delta_s_out: u64,
+        max_delta_m: u64,
+    ) -> Result<SwapAmount> {
+        self.check_not_migrated()?;
+
+        let (_, s_b) = self.balances();
+
+        let max_net_delta_m = self.sellable_meme()?;
+
+        // SOL leaving the curve so that the user nets `delta_s_out`
+        let delta_s = gross_up_sell_fee(delta_s_out, self.fees.fee_quote_percent)?;
+
+        if delta_s > self.quote_reserve.tokens {
+            return Err(error!(AmmError::ExceedsPoolReserve));
+        }
+
+        let net_delta_m = self.delta_m_for_delta_s(s_b, delta_s, max_net_delta_m)?;
+
+        // A capped search means the sellable meme isn't worth the payout
+        if self.compute_delta_s(s_b, net_delta_m)? < delta_s {
+            return Err(error!(AmmError::ExceedsPoolReserve));
+        }
+
+        let delta_m = gross_up_sell_fee(net_delta_m, self.fees.fee_meme_percent)?;
+
+        if delta_m > max_delta_m {
+            return Err(SlippageDetail::exceeded(delta_m, max_delta_m));
+        }
+
+        Ok(SwapAmount {
+            amount_in: net_delta_m,
+            amount_out: delta_s_out,
+            admin_fee_in: delta_m - net_delta_m,
+            admin_fee_out: delta_s - delta_s_out,
+        })
+    }
+
+    /// Inverts the cumulative curve: the SOL supply at which `meme_sold` meme
+    /// tokens have left the curve, rounded up to the first supply reaching it.
+    pub fn supply_at_meme_sold(&self, meme_sold: u64) -> Result<u64> {
+        if meme_sold > self.config.gamma_m {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        if meme_sold == 0 {
+            return Ok(0);
+        }
+
+        self.delta_s_for_delta_m(0, meme_sold, self.config.gamma_s)
+    }
+
+    /// Smallest SOL amount which buys at least `delta_m` starting at `s_a`,
+    /// capped at `max_delta_s`. `compute_delta_s` gives the starting guess and
+    /// its rounding is corrected against `compute_delta_m`, so the pool is
+    /// never short-changed by the inversion.
+    fn delta_s_for_delta_m(&self, s_a: u64, delta_m: u64, max_delta_s: u64) -> Result<u64> {
+        let guess = self.compute_delta_s(s_a, delta_m)?;
+
+        smallest_amount(guess, max_delta_s, |delta_s| {
+            Ok(self.compute_delta_m(s_a, s_a + delta_s)? >= delta_m)
+        })
+    }
+
+    /// Smallest meme amount whose sell is worth at least `delta_s` at `s_b`,
+    /// capped at `max_delta_m`, mirroring how `sell_meme_swap_amounts` prices
+    /// a sell with `compute_delta_s`.
+    fn delta_m_for_delta_s(&self, s_b: u64, delta_s: u64, max_delta_m: u64) -> Result<u64> {
+        let guess = self.compute_delta_m(s_b, s_b + delta_s)?;
+
+        smallest_amount(guess, max_delta_m, |delta_m| {
+            Ok(self.compute_delta_s(s_b, delta_m)? >= delta_s)
+        })
+    }
+
+    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
+    /// Formula: price = +alpha_abs * supply + beta (positive intercept)
+    ///
+    /// `delta_m1_positive_strategy` is authoritative. It floors the beta and
+    /// alpha terms separately, so it never hands out more than the exact
+    /// integral. `delta_m2_positive_strategy` is only used when method 1
+    /// overflows; it floors once over the combined fraction and therefore
+    /// returns the same value or at most 1 unit more.
+    pub fn compute_delta_m(&self, s_a: u64, s_b: u64) -> Result<u64> {
+        let s_a = s_a as u128;
+        let s_b = s_b as u128;
+
+        let alpha_abs = self.config.alpha_abs;
+        let beta = self.config.beta;
+        let alpha_decimals = self.config.decimals.alpha;
+        let beta_decimals = self.config.decimals.beta;
+
+        if self.config.curve_type == CurveType::Linear {
+            let delta_m = delta_m_linear_curve(alpha_abs, alpha_decimals, s_a, s_b)
+                .ok_or(error!(AmmError::MathOverflow))?;
+
+            return u64::try_from(delta_m).map_err(|_| error!(AmmError::MathOverflow));
+        }
+
+        return match delta_m1_positive_strategy(
+            alpha_abs,
+            beta,
+            alpha_decimals,
+            beta_decimals,
+            s_a,
+            s_b,
+        ) {
+            Some(delta_m) => u64::try_from(delta_m).map_err(|_| error!(AmmError::MathOverflow)),
+            None => {
+                match delta_m2_positive_strategy(
+                    alpha_abs,
+                    beta,
+                    alpha_decimals,
+                    beta_decimals,
+                    s_a,
+                    s_b,
+                ) {
+                    Some(delta_m) => {
+                        u64::try_from(delta_m).map_err(|_| error!(AmmError::MathOverflow))
+                    }
+                    None => Err(error!(AmmError::MathOverflow)),
+                }
+            }
+        };
+    }
+
+    /// CHANGED: Updated for positive slope bonding curve with POSITIVE intercept
+    ///
+    /// Small sells, returning at most `LINEAR_FAST_PATH_BPS` of `s_b`, are
+    /// priced with `delta_s_linear_strategy` instead of the U256 quadratic.
+    pub fn compute_delta_s(&self, s_b: u64, delta_m: u64) -> Result<u64> {
+        let s_b = s_b as u128;
+        let delta_m = delta_m as u128;
+
+        let alpha_abs = self.config.alpha_abs;
+        let beta = self.config.beta;
+        let alpha_decimals = self.config.decimals.alpha;
+        let beta_decimals = self.config.decimals.beta;
+
+        if self.config.curve_type == CurveType::Linear {
+            let delta_s = delta_s_linear_curve(alpha_abs, alpha_decimals, s_b, delta_m)
+                .ok_or(error!(AmmError::MathOverflow))?;
+
+            return u64::try_from(delta_s).map_err(|_| error!(AmmError::MathOverflow));
+        }
+
+        if let Some(delta_s) = delta_s_linear_strategy(
+            alpha_abs,
+            beta,
+            alpha_decimals,
+            beta_decimals,
+            s_b,
+            delta_m,
+        ) {
+            return u64::try_from(delta_s).map_err(|_| error!(AmmError::MathOverflow));
+        }
+
+        match delta_s_positive_strategy(
+            alpha_abs,
+            beta,
+            alpha_decimals,
+            beta_decimals,
+            s_b,
+            delta_m,
+        ) {
+            Some(delta_s) => u64::try_from(delta_s).map_err(|_| error!(AmmError::MathOverflow)),
+            None => Err(error!(AmmError::MathOverflow)),
+        }
+    }
+
+    /// Reserves as the curve sees them, with the virtual offsets applied.
+    pub(crate) fn balances(&self) -> (u64, u64) {
+        let p = &self.config;
+
+        (
+            self.meme_reserve.tokens.saturating_sub(p.virtual_meme_reserves),
+            self.quote_reserve.tokens + p.virtual_sol_reserves,
+        )
+    }
+}
+
+/// Price of the reserves a migrated pool trades, in quote units per meme unit
+/// scaled by `DECIMALS_S`.
+fn residual_price(meme: u64, quote: u64) -> Result<u64> {
+    quote
+        .mul_div_floor(DECIMALS_S as u64, meme)
+        .ok_or(error!(AmmError::MathOverflow))
+}
+
+/// Smallest amount in `[0, max]` for which `enough` holds, starting the search
+/// from `guess`. Returns `max` if no amount in range is enough. `enough` must be
+/// monotonic in the amount.
+fn smallest_amount(
+    guess: u64,
+    max: u64,
+    enough: impl Fn(u64) -> Result<bool>,
+) -> Result<u64> {
+    let guess = min(guess, max);
+
+    // Gallop away from the guess until [lo, hi] brackets the answer
+    let (mut lo, mut hi) = if enough(guess)? {
+        let mut hi = guess;
+        let mut step = 1;
+        loop {
+            let lo = hi.saturating_sub(step);
+            if lo == 0 || !enough(lo)? {
+                break (lo, hi);
+            }
+            hi = lo;
+            step *= 2;
+        }
+    } else {
+        let mut lo = guess;
+        let mut step = 1;
+        loop {
+            let hi = min(lo.saturating_add(step), max);
+            if enough(hi)? {
+                break (lo, hi);
+            }
+            if hi == max {
+                return Ok(max);
+            }
+            lo = hi;
+            step *= 2;
+        }
+    };
+
+    // Bisect down to the smallest amount which is still enough
+    while hi - lo > 1 {
+        let mid = lo + (hi - lo) / 2;
+        if enough(mid)? {
+            hi = mid;
+        } else {
+            lo = mid;
+        }
+    }
+
+    Ok(hi)
+}
+
+/// Smallest gross amount which still leaves `net` after the doubled sell fee
+/// charged by `sell_meme_swap_amounts`.
+fn gross_up_sell_fee(net: u64, percent: u64) -> Result<u64> {
+    let mut gross = get_gross_amount(net, percent * 2)?;
+
+    // Two separately rounded fees can take one unit more than a single one
+    while gross - get_fee_amount(gross, percent)? * 2 < net {
+        gross += 1;
+    }
+
+    Ok(gross)
+}
+
+/// CHANGED: Updated for positive slope calculation with POSITIVE intercept
+pub fn compute_alpha_abs(
+    gamma_s: u128,
+    gamma_s_denom: u128,
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<(u128, u128)> {
+    check_slope(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+
+    let left = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope: price increases with supply
+    // Formula: alpha = 2 * (left - gamma_m) * gamma_s_denom^2 / gamma_s^2
+    let num = U256::from(2 * (left - gamma_m)) * U256::from(gamma_s_denom * gamma_s_denom);
+    let denom = U256::from(gamma_s * gamma_s);
+
+    if num <= denom {
+        return Err(error!(AmmError::EGammaSAboveRelativeLimit));
+    }
+
+    // Calculate the scale (order of magnitude) of numerator and denominator
+    let num_scale = compute_scale(num.as_u128());
+    let denom_scale = compute_scale(denom.as_u128());
+
+    // Get the difference in scales to determine required decimal precision
+    let net_scale = num_scale - denom_scale;
+
+    // Convert net_scale to appropriate decimal precision for alpha
+    let alpha_decimals = U256::from(compute_decimals(net_scale)?);
+
+    // For positive slope, we keep the sign positive
+    Ok((
+        ((num * alpha_decimals) / denom).as_u128(),
+        alpha_decimals.as_u128(),
+    ))
+}
+
+/// Slope of a `CurveType::Linear` curve selling `gamma_m` over `gamma_s`.
+/// It is the positive intercept curve whose price factor zeroes `beta`,
+/// i.e. `omega_m * price_factor = 2 * gamma_m`.
+pub fn compute_linear_alpha_abs(
+    gamma_s: u128,
+    gamma_s_denom: u128,
+    gamma_m: u128,
+) -> Result<(u128, u128)> {
+    let omega_m = gamma_m
+        .checked_mul(2)
+        .ok_or(error!(AmmError::MathOverflow))?;
+
+    compute_alpha_abs(gamma_s, gamma_s_denom, gamma_m, omega_m, 1, 1)
+}
+
+pub fn is_power_of_ten(mut value: u128) -> bool {
+    if value == 0 {
+        return false;
+    }
+
+    while value.is_multiple_of(10) {
+        value /= 10;
+    }
+
+    value == 1
+}
+
+pub fn compute_decimals(scale: u64) -> Result<u128> {
+    match scale {
+        0..=4 => return Err(error!(AmmError::EScaleTooLow)),
+        5 => Ok(100_000_000),
+        6 => Ok(10_000_000),
+        7 => Ok(1_000_000),
+        8 => Ok(100_000),
+        9 => Ok(10_000),
+        10 => Ok(1_000),
+        11 => Ok(100),
+        12 => Ok(10),
+        _ => Ok(1), // If scale is above 12
+    }
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept
+pub fn compute_beta(
+    gamma_s: u128,
+    gamma_s_denom: u128,
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+    beta_decimals: u128,
+) -> Result<u128> {
+    check_intercept(gamma_m, omega_m, price_factor_num, price_factor_denom)?;
+
+    let left = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope with positive intercept:
+    // beta = (2 * gamma_m - left) * gamma_s_denom / gamma_s
+    let right = 2 * gamma_m;
+
+    // Now we want a positive beta, so we calculate (right - left)
+    let num = (right - left) * gamma_s_denom;
+    let denom = gamma_s;
+
+    Ok((num * beta_decimals) / denom)
+}
+
+/// Bounds the price factor ratio so extreme values can't blow up alpha and
+/// overflow the curve math intermediates.
+pub fn check_price_factor(price_factor_num: u64, price_factor_denom: u64) -> Result<()> {
+    if price_factor_denom == 0 {
+        return Err(error!(AmmError::PriceFactorOutOfRange));
+    }
+
+    let denom = price_factor_denom as u128;
+    let num = price_factor_num as u128;
+
+    // MIN_PRICE_FACTOR <= num / denom <= MAX_PRICE_FACTOR
+    if num < denom * MIN_PRICE_FACTOR as u128 || num > denom * MAX_PRICE_FACTOR as u128 {
+        return Err(error!(AmmError::PriceFactorOutOfRange));
+    }
+
+    Ok(())
+}
+
+/// CHANGED: For positive slope bonding curve - price increases as supply increases
+pub fn check_slope(
+    gamma_m: u128,
+    omega_m: u128,
+    price_factor_num: u64,
+    price_factor_denom: u64,
+) -> Result<()> {
+    let pfo = omega_m
+        .checked_mul(price_factor_num as u128)
+        .checked_div(price_factor_denom as u128)
+        .unwrap();
+
+    // For positive slope: omega_m * price_factor must be GREATER than gamma_m
+    // This ensures positive slope (price increases with supply)
+    if pfo <= gamma_m {
+        return Err(error!(AmmError::BondingCurveMustBePositivelySloped));
+    }
+
+    Ok(())
+}
+
+/// CHANGED: For positive slope bonding curve with POSITIVE intercept
 pub fn check_intercept(
     gamma_m: u128,
     omega_m: u128,
@@ -357,535 +1617,3424 @@ pub fn check_intercept(
         .checked_div(price_factor_denom as u128)
         .unwrap();
 
-    // For positive slope with positive intercept:
-    // This means 2 * gamma_m > omega_m * price_factor
-    if 2 * gamma_m <= omp {
-        return Err(error!(AmmError::BondingCurveInterceptMustBePositive));
+    // For positive slope with positive intercept:
+    // This means 2 * gamma_m > omega_m * price_factor
+    if 2 * gamma_m <= omp {
+        return Err(error!(AmmError::BondingCurveInterceptMustBePositive));
+    }
+
+    Ok(())
+}
+
+fn compute_scale(num_: u128) -> u64 {
+    let mut num = num_;
+
+    return if num == 0 {
+        1
+    } else {
+        let mut scale = 1;
+
+        while num >= 10 {
+            num = num / 10;
+            scale += 1;
+        }
+
+        scale
+    };
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_s calculation
+fn delta_s_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let alpha_abs = U256::from(alpha_abs);
+    let beta = U256::from(beta);
+    let alpha_decimals = U256::from(alpha_decimals);
+    let beta_decimals = U256::from(beta_decimals);
+    let s_b = U256::from(s_b);
+    let delta_m = U256::from(delta_m);
+    let decimals_s = U256::from(DECIMALS_S);
+
+    // For positive slope: price = +alpha_abs * supply + beta
+    // The u term now ADDS beta
+    let u = U256::from(2)
+        .checked_mul(alpha_abs)
+        .checked_mul(s_b)
+        .checked_mul(beta_decimals)
+        .checked_add_(
+            U256::from(2)
+                .checked_mul(beta)
+                .checked_mul(alpha_decimals)
+                .checked_mul(decimals_s),
+        )?;
+
+    let v = alpha_decimals
+        .checked_mul(beta_decimals)
+        .checked_mul(decimals_s)?;
+
+    let w = U256::from(8).checked_mul(delta_m).checked_mul(alpha_abs)?;
+
+    let a = compute_a_positive(u, alpha_decimals, w, v)?;
+
+    let b = v
+        .checked_pow(U256::from(2))
+        .checked_mul(alpha_decimals)
+        .sqrt()?;
+
+    let num_1 = vec![decimals_s, alpha_decimals, a, v];
+    let num_2 = vec![decimals_s, alpha_decimals, u, b];
+    let denom_ = vec![U256::from(2), alpha_abs, b, v];
+
+    let left = multiply_divide(num_1, denom_.clone());
+    let right = multiply_divide(num_2, denom_);
+
+    // We subtract here because of the quadratic formula structure
+    left.checked_sub_(right).map(|value| value.as_u128())
+}
+
+/// Linear approximation of `delta_s_positive_strategy` for small sells, or
+/// `None` when the sell is too large for it. The curve is linear in supply, so
+/// `delta_m` equals `delta_s` times the rate at the midpoint of the sold range.
+/// The midpoint is estimated from a first tangent step at `s_b`; the estimate
+/// sits above the true midpoint, so the result never exceeds the true integral
+/// and stays monotonic in `delta_m`.
+fn delta_s_linear_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let rate = |s: u128| curve_rate(alpha_abs, beta, alpha_decimals, beta_decimals, s);
+
+    let (rate_num, denom) = rate(s_b)?;
+    let tangent = U256::from(delta_m).checked_mul(denom).checked_div(rate_num)?;
+
+    if tangent.checked_mul(U256::from(BPS_DENOMINATOR))?
+        > U256::from(s_b).checked_mul(U256::from(LINEAR_FAST_PATH_BPS))?
+    {
+        return None;
+    }
+
+    let midpoint = s_b.checked_sub(tangent.as_u128() / 2)?;
+    let (rate_num, denom) = rate(midpoint)?;
+
+    U256::from(delta_m)
+        .checked_mul(denom)
+        .checked_div(rate_num)
+        .map(|delta_s| delta_s.as_u128())
+}
+
+/// `CurveType::Linear` meme out as the supply moves from `s_a` to `s_b`:
+/// `alpha_abs * (s_b^2 - s_a^2) / (2 * alpha_decimals * D_S^2)`, floored.
+fn delta_m_linear_curve(
+    alpha_abs: u128,
+    alpha_decimals: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    let decimals_s = U256::from(DECIMALS_S);
+
+    let delta_m = (U256::from(s_b) * U256::from(s_b))
+        .checked_sub(U256::from(s_a) * U256::from(s_a))
+        .checked_mul(U256::from(alpha_abs))
+        .checked_div(U256::from(2) * U256::from(alpha_decimals) * decimals_s * decimals_s)?;
+
+    (delta_m <= U256::from(u128::MAX)).then(|| delta_m.as_u128())
+}
+
+/// `CurveType::Linear` quote out for `delta_m` meme sold back from supply
+/// `s_b`: the `delta_m_linear_curve` integral solved for the lower supply,
+/// `s_a = sqrt(s_b^2 - 2 * delta_m * alpha_decimals * D_S^2 / alpha_abs)`.
+/// `s_a` is rounded up so the meme is never paid more than it's worth.
+fn delta_s_linear_curve(
+    alpha_abs: u128,
+    alpha_decimals: u128,
+    s_b: u128,
+    delta_m: u128,
+) -> Option<u128> {
+    let decimals_s = U256::from(DECIMALS_S);
+
+    let area = U256::from(2)
+        .checked_mul(U256::from(delta_m))
+        .checked_mul(U256::from(alpha_decimals))
+        .checked_mul(decimals_s)
+        .checked_mul(decimals_s)
+        .checked_div(U256::from(alpha_abs))?;
+    let s_a_squared = (U256::from(s_b) * U256::from(s_b)).saturating_sub(area);
+
+    let mut s_a = u256_sqrt(s_a_squared);
+    if s_a * s_a < s_a_squared {
+        s_a += U256::one();
+    }
+
+    s_b.checked_sub(s_a.as_u128())
+}
+
+/// Meme tokens paid out per quote unit at supply `s`, as a `(num, denom)`
+/// fraction: `(beta * alpha_decimals * D_S + alpha_abs * beta_decimals * s)
+/// / (alpha_decimals * beta_decimals * D_S^2)`.
+fn curve_rate(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    s: u128,
+) -> Option<(U256, U256)> {
+    let decimals_s = U256::from(DECIMALS_S);
+
+    let num = U256::from(beta)
+        .checked_mul(U256::from(alpha_decimals))
+        .checked_mul(decimals_s)
+        .checked_add_(
+            U256::from(alpha_abs)
+                .checked_mul(U256::from(beta_decimals))
+                .checked_mul(U256::from(s)),
+        )?;
+    let denom = U256::from(alpha_decimals)
+        .checked_mul(U256::from(beta_decimals))
+        .checked_mul(decimals_s)
+        .checked_mul(decimals_s)?;
+
+    Some((num, denom))
+}
+
+/// Computes `sqrt(u^2 * alpha_decimals + v^2 * w)`. When the radicand
+/// overflows, the squares are taken over a growing `scale` whose root is
+/// multiplied back in. `scale` overflows after at most 39 rescales, which
+/// bounds the loop.
+fn compute_a_positive(u: U256, alpha_decimals: U256, w: U256, v: U256) -> Option<U256> {
+    let mut scale = U256::from(1);
+
+    loop {
+        let left = u
+            .checked_div(scale)
+            .checked_mul(u)
+            .checked_mul(alpha_decimals);
+
+        let right = v.checked_div(scale).checked_mul(v).checked_mul(w);
+
+        let result = left
+            .checked_add_(right)
+            .map(u256_sqrt)
+            .checked_mul(u256_sqrt(scale));
+
+        if result.is_some() {
+            return result;
+        }
+
+        scale = scale.checked_mul(U256::from(100))?;
+    }
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 1)
+fn delta_m1_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    // For positive intercept, we ADD the beta term
+    let left_num = s_b.checked_sub(s_a)?.checked_mul(beta)?;
+    let left_denom = beta_decimals.checked_mul(DECIMALS_S)?;
+    let left = Some(left_num).checked_div_(Some(left_denom))?;
+
+    let s_b_squared = s_b.checked_pow(2)?;
+    let s_a_squared = s_a.checked_pow(2)?;
+    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+    let decimals_s_squared = DECIMALS_S.checked_pow(2)?;
+
+    let right = power_diff
+        .checked_mul(alpha_abs)
+        .checked_div(decimals_s_squared)?
+        .checked_div(2 * alpha_decimals)?;
+
+    // For positive slope with positive intercept, we ADD beta term
+    Some(left).checked_add_(Some(right))
+}
+
+/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 2)
+fn delta_m2_positive_strategy(
+    alpha_abs: u128,
+    beta: u128,
+    alpha_decimals: u128,
+    beta_decimals: u128,
+    s_a: u128,
+    s_b: u128,
+) -> Option<u128> {
+    // For positive slope: price = +alpha_abs * supply + beta
+    // We ADD beta term
+    let left = (beta * 2)
+        .checked_mul(DECIMALS_S)
+        .checked_mul(alpha_decimals)
+        .checked_mul(s_b - s_a)?;
+
+    let s_b_squared = s_b.checked_pow(2)?;
+    let s_a_squared = s_a.checked_pow(2)?;
+    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+
+    let right = alpha_abs
+        .checked_mul(beta_decimals)
+        .checked_mul(power_diff)?;
+
+    let denom = (2 * alpha_decimals)
+        .checked_mul(beta_decimals)
+        .checked_mul(DECIMALS_S.checked_pow(2)?)?;
+
+    // For positive slope with positive intercept, we ADD both terms
+    left.checked_add(right)?.checked_div(denom)
+}
+
+/// Empty pool on the default launch curve (85 SOL target, price factor 3)
+/// with the 1% quote fee and every optional guard off. Tests adjust it with
+/// struct update syntax.
+#[cfg(test)]
+pub(crate) fn test_pool() -> BoundPool {
+    use crate::consts::{DEFAULT_MAX_M, DEFAULT_MAX_M_LP};
+    use crate::models::fees::FEE;
+
+    let (gamma_s, gamma_m, omega_m) = (85_000_000_000, DEFAULT_MAX_M, DEFAULT_MAX_M_LP);
+    let (alpha_abs, decimals) =
+        compute_alpha_abs(gamma_s, 1_000_000_000, gamma_m, omega_m, 3, 1).unwrap();
+    let beta = compute_beta(gamma_s, 1_000_000_000, gamma_m, omega_m, 3, 1, decimals).unwrap();
+
+    BoundPool {
+        meme_reserve: Reserve {
+            tokens: gamma_m as u64,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+        },
+        quote_reserve: Reserve {
+            tokens: 0,
+            mint: Pubkey::new_unique(),
+            vault: Pubkey::new_unique(),
+        },
+        admin_fees_meme: 0,
+        admin_fees_quote: 0,
+        fee_vault_quote: Pubkey::new_unique(),
+        creator_addr: Pubkey::new_unique(),
+        fees: Fees {
+            fee_meme_percent: 0,
+            fee_quote_percent: FEE,
+        },
+        config: Config {
+            alpha_abs,
+            beta,
+            price_factor_num: 3,
+            price_factor_denom: 1,
+            gamma_s: gamma_s as u64,
+            gamma_m: gamma_m as u64,
+            omega_m: omega_m as u64,
+            decimals: Decimals {
+                alpha: decimals,
+                beta: decimals,
+                quote: 1_000_000_000,
+            },
+            min_slot_between_trades: 0,
+            creator_fee_bps: 0,
+            graduation_mode: GraduationMode::TokenTarget,
+            max_price_impact_bps: 0,
+            virtual_sol_reserves: 0,
+            virtual_meme_reserves: 0,
+            min_quote_in: 0,
+            min_meme_in: 0,
+            wallet_cooldown_secs: 0,
+            migration_target: MigrationTarget::Raydium,
+            migration_price_tolerance_bps: 0,
+            first_buyer_bonus_bps: 0,
+            min_meme_reserve: 0,
+            burn_unsold_bps: 0,
+            min_unique_buyers: 0,
+            residual_fee_mode: ResidualFeeMode::Treasury,
+            curve_type: CurveType::PositiveIntercept,
+        },
+        airdropped_tokens: 0,
+        locked: false,
+        pool_migration: false,
+        pool_key: Pubkey::default(),
+        last_trade_slot: 0,
+        total_quote_volume: 0,
+        total_meme_volume: 0,
+        unique_buyers: 0,
+        creator_fees_quote: 0,
+        price_cumulative: 0,
+        last_twap_slot: 0,
+        first_buy_done: false,
+        pending_creator: Pubkey::default(),
+        staker_fees_quote: 0,
+        version: BoundPool::VERSION,
+    }
+}
+
+/// `test_pool` raised to 80 SOL and migrated, trading what it left on the
+/// curve with its fees going to `mode`.
+#[cfg(test)]
+pub(crate) fn migrated_test_pool(mode: ResidualFeeMode) -> BoundPool {
+    use crate::endpoints::migrate_to_raydium::{
+        calculate_burn_amount, debit_migrated_reserves, plan_migration,
+    };
+
+    let mut pool = test_pool();
+    pool.config.residual_fee_mode = mode;
+    pool.meme_reserve.tokens -= pool.compute_delta_m(0, 80_000_000_000).unwrap();
+    pool.quote_reserve.tokens = 80_000_000_000;
+
+    let (meme_amount, quote_amount, migration_fee) = plan_migration(&pool).unwrap();
+    let meme_burned = calculate_burn_amount(&pool).unwrap();
+    debit_migrated_reserves(
+        &mut pool,
+        meme_amount + meme_burned,
+        quote_amount + migration_fee,
+    )
+    .unwrap();
+    pool.locked = true;
+    pool.mark_migrated(Pubkey::new_unique()).unwrap();
+
+    pool
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Reserve;
+    use super::*;
+    use crate::models::fees::{get_fee_amount, FEE, FEE_PRECISION, MAX_MEME_FEE, MAX_QUOTE_FEE};
+
+    // Helper function to create a test pool configuration
+    fn create_test_config() -> Config {
+        Config {
+            alpha_abs: 1_000_000,       // Smaller alpha for gentler slope
+            beta: 1_000_000_000,        // 1.0 with 9 decimals (positive intercept)
+            price_factor_num: 1,        // Simple 1:1 ratio
+            price_factor_denom: 10, // This gives omega_m * 1/10 = 300, clearly satisfying 2*gamma_m > omega_m*price_factor (6000 > 300)
+            gamma_s: 1_000_000_000_000, // 1000 SOL
+            gamma_m: 3_000_000_000_000, // 3000 tokens (increased to satisfy constraint better)
+            omega_m: 3_000_000_000_000, // 3000 tokens (same as gamma_m)
+            decimals: Decimals {
+                alpha: 1_000_000,     // 6 decimals for alpha
+                beta: 1_000_000_000,  // 9 decimals for beta
+                quote: 1_000_000_000, // 9 decimals (SOL)
+            },
+            min_slot_between_trades: 0,
+            creator_fee_bps: 0,
+            graduation_mode: GraduationMode::TokenTarget,
+            max_price_impact_bps: 0,
+            virtual_sol_reserves: 0,
+            virtual_meme_reserves: 0,
+            min_quote_in: 0,
+            min_meme_in: 0,
+            wallet_cooldown_secs: 0,
+            migration_target: MigrationTarget::Raydium,
+            migration_price_tolerance_bps: 0,
+            first_buyer_bonus_bps: 0,
+            min_meme_reserve: 0,
+            burn_unsold_bps: 0,
+            min_unique_buyers: 0,
+            residual_fee_mode: ResidualFeeMode::Treasury,
+            curve_type: CurveType::PositiveIntercept,
+        }
+    }
+
+    // Helper function to create a test pool
+    fn create_test_pool() -> BoundPool {
+        BoundPool {
+            meme_reserve: Reserve {
+                tokens: 500_000_000_000, // 500 tokens
+                mint: Pubkey::default(),
+                vault: Pubkey::default(),
+            },
+            quote_reserve: Reserve {
+                tokens: 250_000_000_000, // 250 SOL
+                mint: Pubkey::default(),
+                vault: Pubkey::default(),
+            },
+            admin_fees_meme: 0,
+            admin_fees_quote: 0,
+            fee_vault_quote: Pubkey::default(),
+            creator_addr: Pubkey::default(),
+            fees: Fees {
+                fee_meme_percent: 0,
+                fee_quote_percent: FEE, // 1%
+            },
+            config: create_test_config(),
+            airdropped_tokens: 0,
+            locked: false,
+            pool_migration: false,
+            pool_key: Pubkey::default(),
+            last_trade_slot: 0,
+            total_quote_volume: 0,
+            total_meme_volume: 0,
+            unique_buyers: 0,
+            creator_fees_quote: 0,
+            price_cumulative: 0,
+            last_twap_slot: 0,
+            first_buy_done: false,
+            pending_creator: Pubkey::default(),
+            staker_fees_quote: 0,
+            version: BoundPool::VERSION,
+        }
+    }
+
+    // Helper building an empty pool on a curve derived like new_pool does
+    fn create_curve_pool(
+        gamma_s: u128,
+        quote_decimals: u128,
+        omega_m: u128,
+        price_factor_num: u64,
+        price_factor_denom: u64,
+    ) -> BoundPool {
+        create_locked_curve_pool(
+            0,
+            gamma_s,
+            quote_decimals,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+        )
+    }
+
+    // Same, with `creator_locked` carved out of the trading supply
+    fn create_locked_curve_pool(
+        creator_locked: u64,
+        gamma_s: u128,
+        quote_decimals: u128,
+        omega_m: u128,
+        price_factor_num: u64,
+        price_factor_denom: u64,
+    ) -> BoundPool {
+        let gamma_m = crate::consts::DEFAULT_MAX_M - creator_locked as u128;
+
+        let (alpha_abs, decimals) = compute_alpha_abs(
+            gamma_s,
+            quote_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+        )
+        .unwrap();
+        let beta = compute_beta(
+            gamma_s,
+            quote_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+            decimals,
+        )
+        .unwrap();
+
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = gamma_m as u64;
+        pool.quote_reserve.tokens = 0;
+        pool.config = Config {
+            alpha_abs,
+            beta,
+            price_factor_num,
+            price_factor_denom,
+            gamma_s: gamma_s as u64,
+            gamma_m: gamma_m as u64,
+            omega_m: omega_m as u64,
+            decimals: Decimals {
+                alpha: decimals,
+                beta: decimals,
+                quote: quote_decimals as u64,
+            },
+            min_slot_between_trades: 0,
+            creator_fee_bps: 0,
+            graduation_mode: GraduationMode::TokenTarget,
+            max_price_impact_bps: 0,
+            virtual_sol_reserves: 0,
+            virtual_meme_reserves: 0,
+            min_quote_in: 0,
+            min_meme_in: 0,
+            wallet_cooldown_secs: 0,
+            migration_target: MigrationTarget::Raydium,
+            migration_price_tolerance_bps: 0,
+            first_buyer_bonus_bps: 0,
+            min_meme_reserve: 0,
+            burn_unsold_bps: 0,
+            min_unique_buyers: 0,
+            residual_fee_mode: ResidualFeeMode::Treasury,
+            curve_type: CurveType::PositiveIntercept,
+        };
+
+        pool
+    }
+
+    #[test]
+    fn test_compute_delta_m_basic() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let s_a = 100_000_000_000; // 100 SOL
+        let s_b = 200_000_000_000; // 200 SOL
+
+        println!("🧪 Testing compute_delta_m with:");
+        println!("   s_a (start): {} SOL", s_a / 1_000_000_000);
+        println!("   s_b (end): {} SOL", s_b / 1_000_000_000);
+
+        // ACT: Calculate delta_m
+        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+
+        // ASSERT: Check result is reasonable
+        assert!(delta_m > 0, "Delta_m should be positive");
+        assert!(
+            delta_m < pool.config.gamma_m,
+            "Delta_m should be less than max meme tokens"
+        );
+
+        println!(
+            "✅ Test passed! Delta_m = {} tokens",
+            delta_m / 1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_compute_delta_s_basic() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let s_b = 100_000_000_000; // 100 SOL
+        let delta_m = 1_000_000_000; // 1 token to sell (much smaller amount)
+
+        println!("🧪 Testing compute_delta_s with:");
+        println!("   s_b (current): {} SOL", s_b / 1_000_000_000);
+        println!("   delta_m: {} tokens", delta_m / 1_000_000_000);
+
+        // ACT: Calculate delta_s
+        let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+
+        // ASSERT: Check result is reasonable
+        assert!(delta_s > 0, "Delta_s should be positive");
+        // Use a more reasonable upper bound check
+        assert!(
+            delta_s < 100_000_000_000_000, // 100k SOL as reasonable upper bound
+            "Delta_s should be reasonable: got {} SOL",
+            delta_s / 1_000_000_000
+        );
+
+        println!("✅ Test passed! Delta_s = {} SOL", delta_s / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_positive_intercept_at_zero_supply() {
+        // ARRANGE: Test that price = beta when supply = 0
+        let pool = create_test_pool();
+        let s_a = 0; // Start from 0 supply
+        let s_b = 1_000_000_000; // Small amount of SOL
+
+        println!("🧪 Testing positive intercept (beta) at zero supply");
+
+        // ACT: Calculate delta_m
+        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+
+        // The price at zero should be beta, so initial tokens should be higher
+        // because we're starting from a positive price
+        assert!(
+            delta_m > 0,
+            "Should get tokens even at zero supply due to positive intercept"
+        );
+
+        println!(
+            "✅ Positive intercept confirmed! Got {} tokens for {} SOL at zero supply",
+            delta_m / 1_000_000_000,
+            s_b / 1_000_000_000
+        );
+    }
+
+    #[test]
+    fn test_buy_meme_swap_amounts() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL to buy with
+        let min_meme_out = 0; // No slippage protection for test
+
+        println!(
+            "🧪 Testing buy_meme_swap_amounts with {} SOL",
+            sol_amount / 1_000_000_000
+        );
+
+        // ACT: Calculate swap amounts
+        let swap = pool
+            .buy_meme_swap_amounts(sol_amount, min_meme_out)
+            .unwrap();
+
+        // ASSERT: Check all values are correct
+        assert!(swap.amount_out > 0, "Should receive meme tokens");
+        assert_eq!(
+            swap.admin_fee_in,
+            sol_amount / 100, // 1% fee
+            "Admin fee should be 1% of input"
+        );
+        assert!(
+            swap.amount_in <= sol_amount - swap.admin_fee_in,
+            "Net amount in should be less than input minus fees"
+        );
+
+        println!("✅ Buy swap test passed!");
+        println!("   Input: {} SOL", sol_amount / 1_000_000_000);
+        println!("   Output: {} MEME", swap.amount_out / 1_000_000_000);
+        println!("   Fee: {} SOL", swap.admin_fee_in / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_sell_meme_swap_amounts() {
+        // ARRANGE: Set up test data
+        let pool = create_test_pool();
+        let meme_amount = 10_000_000_000; // 10 MEME to sell
+        let min_sol_out = 0; // No slippage protection for test
+
+        println!(
+            "🧪 Testing sell_meme_swap_amounts with {} MEME",
+            meme_amount / 1_000_000_000
+        );
+
+        // ACT: Calculate swap amounts
+        let swap = pool
+            .sell_meme_swap_amounts(meme_amount, min_sol_out)
+            .unwrap();
+
+        // ASSERT: Check all values are correct
+        assert!(swap.amount_out > 0, "Should receive SOL");
+        assert_eq!(
+            swap.admin_fee_in,
+            0, // 0% fee on meme, the default meme fee
+            "Admin fee on meme should be 0%"
+        );
+
+        println!("✅ Sell swap test passed!");
+        println!("   Input: {} MEME", meme_amount / 1_000_000_000);
+        println!("   Output: {} SOL", swap.amount_out / 1_000_000_000);
+        println!("   Fee: {} SOL", swap.admin_fee_out / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_round_trip_swap() {
+        // ARRANGE: Buy then sell to test round trip
+        let mut pool = create_test_pool();
+        let initial_sol = 100_000_000_000; // 100 SOL
+
+        println!("🧪 Testing round trip swap (buy then sell)");
+
+        // ACT: First buy meme with SOL
+        let buy_swap = pool.buy_meme_swap_amounts(initial_sol, 0).unwrap();
+
+        // Update pool reserves (simulate the buy)
+        pool.quote_reserve.tokens += buy_swap.amount_in;
+        pool.meme_reserve.tokens -= buy_swap.amount_out;
+
+        // Now sell the meme back
+        let sell_swap = pool.sell_meme_swap_amounts(buy_swap.amount_out, 0).unwrap();
+
+        // ASSERT: We should get less SOL back due to fees
+        assert!(
+            sell_swap.amount_out < initial_sol,
+            "Should get less SOL back due to fees"
+        );
+
+        let total_fees = initial_sol - sell_swap.amount_out;
+        println!("✅ Round trip test passed!");
+        println!("   Started with: {} SOL", initial_sol / 1_000_000_000);
+        println!(
+            "   Ended with: {} SOL",
+            sell_swap.amount_out / 1_000_000_000
+        );
+        println!("   Total fees: {} SOL", total_fees / 1_000_000_000);
+    }
+
+    #[test]
+    fn test_price_increases_with_supply() {
+        // ARRANGE: Test that price increases as supply increases (positive slope)
+        let pool = create_test_pool();
+
+        // Use more dramatic supply differences to see the effect
+        let supply1 = 10_000_000_000; // 10 SOL (low supply)
+        let supply2 = 500_000_000_000; // 500 SOL (high supply)
+        let delta = 1_000_000_000; // 1 SOL increment (smaller to be more precise)
+
+        println!("🧪 Testing that price increases with supply (positive slope)");
+
+        // ACT: Get tokens at lower supply
+        let tokens_at_low_supply = pool.compute_delta_m(supply1, supply1 + delta).unwrap();
+
+        // Get tokens at higher supply
+        let tokens_at_high_supply = pool.compute_delta_m(supply2, supply2 + delta).unwrap();
+
+        println!(
+            "   At supply {}: {} tokens for {} SOL",
+            supply1 / 1_000_000_000,
+            tokens_at_low_supply / 1_000_000_000,
+            delta / 1_000_000_000
+        );
+        println!(
+            "   At supply {}: {} tokens for {} SOL",
+            supply2 / 1_000_000_000,
+            tokens_at_high_supply / 1_000_000_000,
+            delta / 1_000_000_000
+        );
+
+        // ASSERT: At higher supply, same SOL amount should buy fewer tokens (higher price)
+        // If this still fails, the bonding curve math might need adjustment
+        if tokens_at_low_supply <= tokens_at_high_supply {
+            println!("⚠️  Bonding curve behavior: getting more tokens at higher supply");
+            println!("   This suggests the math implementation may need review");
+            // For now, just check that both values are positive and different
+            assert!(
+                tokens_at_low_supply > 0 && tokens_at_high_supply > 0,
+                "Both token amounts should be positive"
+            );
+        } else {
+            println!("✅ Positive slope confirmed!");
+        }
+    }
+
+    #[test]
+    fn test_max_supply_limits() {
+        // ARRANGE: Test behavior at max supply limits
+        let pool = create_test_pool();
+        let near_max_sol = pool.config.gamma_s - 1_000_000_000; // 1 SOL from max
+
+        println!("🧪 Testing behavior near max supply limits");
+
+        // ACT: Try to buy with amount that would exceed max
+        let large_amount = 100_000_000_000; // 100 SOL
+        let swap = pool.buy_meme_swap_amounts(large_amount, 0).unwrap();
+
+        // ASSERT: Should cap at remaining amount
+        assert!(
+            swap.amount_in <= pool.config.gamma_s - pool.quote_reserve.tokens,
+            "Should not exceed max SOL supply"
+        );
+
+        println!("✅ Max supply limit test passed!");
+        println!("   Attempted: {} SOL", large_amount / 1_000_000_000);
+        println!("   Actually used: {} SOL", swap.amount_in / 1_000_000_000);
+    }
+
+    #[test]
+    #[should_panic(expected = "SlippageExceeded")]
+    fn test_slippage_protection() {
+        // ARRANGE: Test slippage protection
+        let pool = create_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL
+        let unrealistic_min_out = 1_000_000_000_000; // Expect 1000 MEME (unrealistic)
+
+        println!("🧪 Testing slippage protection (should fail)");
+
+        // ACT & ASSERT: This should panic with SlippageExceeded
+        pool.buy_meme_swap_amounts(sol_amount, unrealistic_min_out)
+            .unwrap();
+    }
+
+    #[test]
+    fn test_alpha_and_beta_calculation() {
+        // ARRANGE: Test the compute_alpha_abs and compute_beta functions
+        let gamma_s = 1_000_000_000_000_u128; // 1000 SOL
+        let gamma_s_denom = 1_000_000_000_u128; // SOL decimals
+        let gamma_m = 3_000_000_000_000_u128; // 3000 tokens
+        let omega_m = 3_000_000_000_000_u128; // 3000 tokens
+        let price_factor_num = 3; // Adjusted to ensure positive slope
+        let price_factor_denom = 2; // Adjusted to ensure positive slope and intercept
+        let beta_decimals = 1_000_000_000_u128;
+
+        println!("🧪 Testing alpha and beta calculations");
+
+        // ACT: Calculate alpha
+        let (alpha, alpha_decimals_result) = compute_alpha_abs(
+            gamma_s,
+            gamma_s_denom,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+        )
+        .unwrap();
+
+        // Calculate beta
+        let beta = compute_beta(
+            gamma_s,
+            gamma_s_denom,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+            beta_decimals,
+        )
+        .unwrap();
+
+        // ASSERT: Both should be positive
+        assert!(alpha > 0, "Alpha should be positive for positive slope");
+        assert!(beta > 0, "Beta should be positive for positive intercept");
+
+        println!("✅ Alpha and beta calculation passed!");
+        println!("   Alpha: {} (decimals: {})", alpha, alpha_decimals_result);
+        println!("   Beta: {} (decimals: {})", beta, beta_decimals);
+    }
+
+    #[test]
+    fn test_trade_slot_guard_consecutive_slots() {
+        // ARRANGE: Require at least 2 slots between trades
+        let mut pool = create_test_pool();
+        pool.config.min_slot_between_trades = 2;
+
+        println!("🧪 Testing trade slot guard with two trades in consecutive slots");
+
+        // ACT: First trade at slot 100 goes through
+        pool.check_trade_slot(100).unwrap();
+        assert_eq!(pool.last_trade_slot, 100);
+
+        // Second trade in the very next slot is rejected
+        let err = pool.check_trade_slot(101).unwrap_err();
+        assert_eq!(err, error!(AmmError::TradeTooSoon));
+        assert_eq!(pool.last_trade_slot, 100, "Rejected trade must not move the slot");
+
+        // ASSERT: Once enough slots passed the trade is accepted again
+        pool.check_trade_slot(102).unwrap();
+        assert_eq!(pool.last_trade_slot, 102);
+
+        println!("✅ Trade slot guard test passed!");
+    }
+
+    #[test]
+    fn test_trade_slot_guard_set_by_update() {
+        // ARRANGE: A launched pool, guard off by default
+        let mut pool = create_test_pool();
+        pool.check_trade_slot(100).unwrap();
+
+        println!("🧪 Testing the trade slot guard switched on after launch");
+
+        // ACT: The admin requires 5 slots between trades
+        let mut guards = pool.guards();
+        guards.min_slot_between_trades = 5;
+        let previous = pool.update_guards(guards).unwrap();
+
+        // ASSERT: The next trade within the window is refused
+        assert_eq!(previous.min_slot_between_trades, 0);
+        assert_eq!(
+            pool.check_trade_slot(104).unwrap_err(),
+            error!(AmmError::TradeTooSoon)
+        );
+        pool.check_trade_slot(105).unwrap();
+
+        // Guards are frozen once the pool migrated
+        pool.pool_migration = true;
+        assert_eq!(
+            pool.update_guards(PoolGuards::default()).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Updated trade slot guard test passed!");
+    }
+
+    #[test]
+    fn test_wallet_cooldown_set_by_update() {
+        use crate::models::wallet_activity::WalletActivity;
+
+        // ARRANGE: A launched pool lets a wallet swap back to back
+        let mut pool = create_test_pool();
+        let (pool_key, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut activity = WalletActivity {
+            pool: pool_key,
+            wallet,
+            last_trade_ts: 0,
+        };
+        activity
+            .record_trade(pool_key, wallet, 1_000, pool.config.wallet_cooldown_secs)
+            .unwrap();
+        activity
+            .record_trade(pool_key, wallet, 1_000, pool.config.wallet_cooldown_secs)
+            .unwrap();
+
+        println!("🧪 Testing the wallet cooldown switched on after launch");
+
+        // ACT: The admin spaces a wallet's swaps 30 seconds apart
+        let mut guards = pool.guards();
+        guards.wallet_cooldown_secs = 30;
+        pool.update_guards(guards).unwrap();
+
+        // ASSERT: A swap inside the cooldown is refused, one after it isn't
+        assert_eq!(
+            activity
+                .record_trade(pool_key, wallet, 1_029, pool.config.wallet_cooldown_secs)
+                .unwrap_err(),
+            error!(AmmError::WalletCooldown)
+        );
+        activity
+            .record_trade(pool_key, wallet, 1_030, pool.config.wallet_cooldown_secs)
+            .unwrap();
+
+        // A cooldown past what a timestamp can hold is refused
+        guards.wallet_cooldown_secs = u64::MAX;
+        assert_eq!(
+            pool.update_guards(guards).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(pool.config.wallet_cooldown_secs, 30);
+
+        println!("✅ Updated wallet cooldown test passed!");
+    }
+
+    #[test]
+    fn test_trade_slot_guard_disabled() {
+        // ARRANGE: Zero disables the guard
+        let mut pool = create_test_pool();
+
+        // ACT & ASSERT: Consecutive and same-slot trades are all allowed
+        pool.check_trade_slot(100).unwrap();
+        pool.check_trade_slot(101).unwrap();
+        pool.check_trade_slot(101).unwrap();
+        assert_eq!(pool.last_trade_slot, 101);
+
+        println!("✅ Disabled trade slot guard test passed!");
+    }
+
+    #[test]
+    fn test_price_factor_bounds() {
+        assert!(check_price_factor(1, 1).is_ok());
+        assert!(check_price_factor(100, 1).is_ok());
+        assert!(check_price_factor(300, 3).is_ok());
+
+        assert_eq!(
+            check_price_factor(101, 1).unwrap_err(),
+            error!(AmmError::PriceFactorOutOfRange)
+        );
+        assert_eq!(
+            check_price_factor(1, 2).unwrap_err(),
+            error!(AmmError::PriceFactorOutOfRange)
+        );
+        assert_eq!(
+            check_price_factor(1, 0).unwrap_err(),
+            error!(AmmError::PriceFactorOutOfRange)
+        );
+
+        println!("✅ Price factor bounds test passed!");
+    }
+
+    #[test]
+    fn test_delta_m_fits_u64_at_max_price_factor() {
+        // ARRANGE: Curve built with the largest allowed price factor. omega_m is
+        // picked so that the slope and intercept checks still pass at 100x.
+        let gamma_s = 1_000_000_000_000_u128; // 1000 SOL
+        let gamma_s_denom = 1_000_000_000_u128; // SOL decimals
+        let gamma_m = crate::consts::DEFAULT_MAX_M; // 690M tokens
+        let omega_m = gamma_m / 60;
+        let price_factor_num = MAX_PRICE_FACTOR;
+        let price_factor_denom = 1;
+
+        check_price_factor(price_factor_num, price_factor_denom).unwrap();
+
+        let (alpha_abs, decimals) = compute_alpha_abs(
+            gamma_s,
+            gamma_s_denom,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+        )
+        .unwrap();
+        let beta = compute_beta(
+            gamma_s,
+            gamma_s_denom,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+            decimals,
+        )
+        .unwrap();
+
+        let mut pool = create_test_pool();
+        pool.config = Config {
+            alpha_abs,
+            beta,
+            price_factor_num,
+            price_factor_denom,
+            gamma_s: gamma_s as u64,
+            gamma_m: gamma_m as u64,
+            omega_m: omega_m as u64,
+            decimals: Decimals {
+                alpha: decimals,
+                beta: decimals,
+                quote: gamma_s_denom as u64,
+            },
+            min_slot_between_trades: 0,
+            creator_fee_bps: 0,
+            graduation_mode: GraduationMode::TokenTarget,
+            max_price_impact_bps: 0,
+            virtual_sol_reserves: 0,
+            virtual_meme_reserves: 0,
+            min_quote_in: 0,
+            min_meme_in: 0,
+            wallet_cooldown_secs: 0,
+            migration_target: MigrationTarget::Raydium,
+            migration_price_tolerance_bps: 0,
+            first_buyer_bonus_bps: 0,
+            min_meme_reserve: 0,
+            burn_unsold_bps: 0,
+            min_unique_buyers: 0,
+            residual_fee_mode: ResidualFeeMode::Treasury,
+            curve_type: CurveType::PositiveIntercept,
+        };
+
+        println!("🧪 Sweeping the full supply range at price factor {}", MAX_PRICE_FACTOR);
+
+        // ACT & ASSERT: Every point of the curve stays inside u64
+        let steps = 100;
+        for i in 1..=steps {
+            let s_b = gamma_s * i / steps;
+
+            let raw = delta_m1_positive_strategy(alpha_abs, beta, decimals, decimals, 0, s_b)
+                .or_else(|| delta_m2_positive_strategy(alpha_abs, beta, decimals, decimals, 0, s_b))
+                .expect("curve math must not overflow");
+
+            assert!(
+                raw <= u64::MAX as u128,
+                "delta_m {} at s_b {} doesn't fit u64",
+                raw,
+                s_b
+            );
+            assert_eq!(pool.compute_delta_m(0, s_b as u64).unwrap() as u128, raw);
+        }
+
+        println!("✅ compute_delta_m fits u64 across the full supply range!");
+    }
+
+    #[test]
+    fn test_delta_overflowing_u64_is_an_error() {
+        // ARRANGE: A flat curve priced so that amounts on the other side of
+        // the trade come out far beyond u64, but still inside u128
+        let mut pool = create_test_pool();
+        pool.config.alpha_abs = 0;
+        pool.config.beta = 1_000_000_000_000_000_000_000_000_000_000;
+        pool.config.decimals.beta = 1;
+
+        println!("🧪 Testing that a delta beyond u64 is refused, not truncated");
+
+        // ACT & ASSERT: The buy's intermediate doesn't fit u64
+        let raw = delta_m1_positive_strategy(0, pool.config.beta, 1, 1, 0, 1_000_000).unwrap();
+        assert!(raw > u64::MAX as u128);
+        assert_eq!(
+            pool.compute_delta_m(0, 1_000_000).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        // Same for a large sell down a shallow curve
+        pool.config.alpha_abs = 1;
+        pool.config.beta = 1;
+        pool.config.decimals.alpha = 1_000;
+        let (s_b, delta_m) = (1_000_000_000_000_000_000, 1_000_000_000_000_000_000);
+        let raw = delta_s_positive_strategy(1, 1, 1_000, 1, s_b, delta_m).unwrap();
+        assert!(raw > u64::MAX as u128);
+        assert_eq!(
+            pool.compute_delta_s(s_b as u64, delta_m as u64).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        println!("✅ Overflowing delta rejection test passed!");
+    }
+
+    // Helper building a linear pool on rate `2 * supply / D_S^2`, so that
+    // selling between supplies `a` and `b` pays out `(b^2 - a^2) / D_S^2`
+    fn create_linear_pool() -> BoundPool {
+        let mut pool = create_test_pool();
+        pool.config.curve_type = CurveType::Linear;
+        pool.config.alpha_abs = 2_000_000;
+        pool.config.beta = 0;
+        pool.config.decimals.alpha = 1_000_000;
+
+        pool
+    }
+
+    #[test]
+    fn test_linear_curve_hand_computed() {
+        // ARRANGE
+        let pool = create_linear_pool();
+        let sol = 1_000_000_000_000; // 1e12, so sol^2 / D_S^2 = 1e6
+
+        println!("🧪 Testing the linear curve against hand computed values");
+
+        // ACT & ASSERT: Buys pay out (b^2 - a^2) / 1e18
+        assert_eq!(pool.compute_delta_m(0, sol).unwrap(), 1_000_000);
+        assert_eq!(pool.compute_delta_m(sol, 3 * sol).unwrap(), 8_000_000);
+        assert_eq!(pool.compute_delta_m(2 * sol, 3 * sol).unwrap(), 5_000_000);
+
+        // Just short of a whole meme floors to nothing
+        assert_eq!(pool.compute_delta_m(0, 999_999_999).unwrap(), 0);
+
+        // Sells solve a = sqrt(b^2 - meme * 1e18)
+        assert_eq!(pool.compute_delta_s(3 * sol, 8_000_000).unwrap(), 2 * sol);
+        assert_eq!(pool.compute_delta_s(3 * sol, 5_000_000).unwrap(), sol);
+
+        // 1 meme off 3e12: a = ceil(sqrt(9e24 - 1e18)) = 2_999_999_833_334
+        assert_eq!(pool.compute_delta_s(3 * sol, 1).unwrap(), 166_666);
+
+        println!("✅ Linear curve hand computation test passed!");
+    }
+
+    #[test]
+    fn test_linear_curve_matches_general_math() {
+        // ARRANGE: The same curve through the positive intercept math
+        let linear = create_linear_pool();
+        let mut general = linear.clone();
+        general.config.curve_type = CurveType::PositiveIntercept;
+
+        println!("🧪 Testing the linear branch against the general curve math");
+
+        for (s_a, s_b) in [
+            (0, 1_000_000_007),
+            (123_456_789_012, 123_456_789_013),
+            (5_000_000_000_000, 7_777_777_777_777),
+        ] {
+            // ACT & ASSERT: Buys agree exactly
+            let delta_m = linear.compute_delta_m(s_a, s_b).unwrap();
+            assert_eq!(delta_m, general.compute_delta_m(s_a, s_b).unwrap());
+
+            // Selling the meme back never pays more than was paid in
+            let delta_s = linear.compute_delta_s(s_b, delta_m).unwrap();
+            assert!(delta_s <= s_b - s_a, "{:?}", (s_a, s_b));
+            assert!(linear.compute_delta_m(s_b - delta_s, s_b).unwrap() <= delta_m);
+        }
+
+        println!("✅ Linear branch agreement test passed!");
+    }
+
+    #[test]
+    fn test_linear_curve_derivation() {
+        // ARRANGE: A linear pool derived the way new_pool does, 85 SOL target
+        let gamma_s = 85_000_000_000;
+        let gamma_m = crate::consts::DEFAULT_MAX_M;
+        let (alpha_abs, decimals) =
+            compute_linear_alpha_abs(gamma_s, 1_000_000_000, gamma_m).unwrap();
+
+        let mut pool = create_curve_pool(gamma_s, 1_000_000_000, 310_000_000_000_000, 3, 1);
+        pool.config.curve_type = CurveType::Linear;
+        pool.config.alpha_abs = alpha_abs;
+        pool.config.beta = 0;
+        pool.config.decimals.alpha = decimals;
+        pool.config.decimals.beta = decimals;
+
+        // ACT & ASSERT: It sells the trading supply at the target
+        pool.check_curve_consistency().unwrap();
+
+        // And can't open without virtual SOL to price the first buy
+        assert_eq!(
+            pool.check_virtual_reserves().unwrap_err(),
+            error!(AmmError::InvalidVirtualReserves)
+        );
+        pool.config.virtual_sol_reserves = 1_000_000_000;
+        pool.check_virtual_reserves().unwrap();
+        assert!(pool.spot_price().unwrap() > 0);
+
+        println!("✅ Linear curve derivation test passed!");
+    }
+
+    #[test]
+    fn test_delta_m_strategies_agree() {
+        // ARRANGE: Sample a grid over the whole supply range
+        let pool = create_test_pool();
+        let p = &pool.config;
+        let steps = 40;
+
+        println!("🧪 Comparing delta_m strategies over a {}x{} grid", steps, steps);
+
+        // ACT & ASSERT: Both strategies agree within 1 unit, method 2 never lower
+        let mut compared = 0;
+        for i in 0..steps {
+            for j in (i + 1)..=steps {
+                let s_a = p.gamma_s as u128 * i / steps;
+                let s_b = p.gamma_s as u128 * j / steps + j; // offset off round numbers
+
+                let m1 = delta_m1_positive_strategy(
+                    p.alpha_abs,
+                    p.beta,
+                    p.decimals.alpha,
+                    p.decimals.beta,
+                    s_a,
+                    s_b,
+                );
+                let m2 = delta_m2_positive_strategy(
+                    p.alpha_abs,
+                    p.beta,
+                    p.decimals.alpha,
+                    p.decimals.beta,
+                    s_a,
+                    s_b,
+                );
+
+                if let (Some(m1), Some(m2)) = (m1, m2) {
+                    assert!(
+                        m2 >= m1 && m2 - m1 <= 1,
+                        "strategies diverge at [{}, {}]: {} vs {}",
+                        s_a,
+                        s_b,
+                        m1,
+                        m2
+                    );
+                    compared += 1;
+                }
+            }
+        }
+
+        assert!(compared > 0, "grid must exercise both strategies");
+
+        println!("✅ Strategies agree on {} sampled ranges!", compared);
+    }
+
+    #[test]
+    fn test_buy_exact_out_round_trip() {
+        // ARRANGE: Quote a regular buy first
+        let pool = create_test_pool();
+        let sol_amount = 10_000_000_000; // 10 SOL
+        let buy = pool.buy_meme_swap_amounts(sol_amount, 0).unwrap();
+
+        println!(
+            "🧪 Testing exact-out buy for {} MEME bought with {} SOL",
+            buy.amount_out,
+            sol_amount
+        );
+
+        // ACT: Ask for exactly the same amount of meme out
+        let exact = pool
+            .buy_meme_exact_out_amounts(buy.amount_out, u64::MAX)
+            .unwrap();
+
+        // ASSERT: Same output, never charging more than the exact-in buy
+        assert_eq!(exact.amount_out, buy.amount_out);
+        let charged = exact.amount_in + exact.admin_fee_in;
+        assert!(
+            charged <= sol_amount,
+            "exact-out charged {} vs {} for the same output",
+            charged,
+            sol_amount
+        );
+
+        // Spending the charged SOL as an exact-in buy lands on the same output
+        let replay = pool.buy_meme_swap_amounts(charged, 0).unwrap();
+        assert_eq!(replay.amount_out, exact.amount_out);
+
+        // The SOL charged buys at least the requested amount on the curve
+        let (_, s_t0) = pool.balances();
+        let bought = pool
+            .compute_delta_m(s_t0, s_t0 + exact.amount_in)
+            .unwrap();
+        assert!(bought >= exact.amount_out);
+
+        println!("✅ Exact-out round trip test passed! Charged {} SOL", charged);
+    }
+
+    #[test]
+    fn test_buy_exact_out_slippage() {
+        let pool = create_test_pool();
+        let buy = pool.buy_meme_swap_amounts(10_000_000_000, 0).unwrap();
+
+        // Allow way less SOL than required
+        let err = pool
+            .buy_meme_exact_out_amounts(buy.amount_out, 1_000_000_000)
+            .unwrap_err();
+
+        assert_eq!(err, error!(AmmError::SlippageExceeded));
+
+        println!("✅ Exact-out slippage test passed!");
+    }
+
+    #[test]
+    fn test_buy_exact_out_capped_at_reserve() {
+        // ARRANGE: Request more meme than the pool holds
+        let pool = create_test_pool();
+        let requested = pool.meme_reserve.tokens + 1_000_000_000;
+
+        // ACT
+        let exact = pool.buy_meme_exact_out_amounts(requested, u64::MAX).unwrap();
+
+        // ASSERT: Output capped to the reserve, input capped to the curve limit
+        assert_eq!(exact.amount_out, pool.meme_reserve.tokens);
+        assert_eq!(
+            exact.amount_in,
+            pool.config.gamma_s - pool.quote_reserve.tokens
+        );
+
+        println!("✅ Exact-out reserve cap test passed!");
+    }
+
+    #[test]
+    fn test_sell_exact_out_monotonic() {
+        // ARRANGE: Pool with plenty of meme sold
+        let pool = create_test_pool();
+
+        println!("🧪 Testing exact-out sell input grows with requested output");
+
+        // ACT & ASSERT: Required meme in never decreases as requested SOL grows
+        let mut last_required = 0;
+        for i in 1..=20 {
+            let sol_out = 1_000_000_000 * i; // i SOL
+            let swap = pool.sell_meme_exact_out_amounts(sol_out, u64::MAX).unwrap();
+            let required = swap.amount_in + swap.admin_fee_in;
+
+            assert_eq!(swap.amount_out, sol_out);
+            assert!(
+                required >= last_required,
+                "required {} dropped below {} at {} SOL",
+                required,
+                last_required,
+                i
+            );
+
+            // Selling the required meme really is worth the requested SOL
+            let sell = pool.sell_meme_swap_amounts(required, 0).unwrap();
+            assert!(sell.amount_out >= sol_out);
+
+            last_required = required;
+        }
+
+        println!("✅ Exact-out sell monotonicity test passed!");
+    }
+
+    #[test]
+    fn test_sell_exact_out_rejections() {
+        let pool = create_test_pool();
+
+        // More SOL than the pool holds
+        let err = pool
+            .sell_meme_exact_out_amounts(pool.quote_reserve.tokens + 1, u64::MAX)
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::ExceedsPoolReserve));
+
+        // Not allowing enough meme in
+        let err = pool
+            .sell_meme_exact_out_amounts(1_000_000_000, 1)
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::SlippageExceeded));
+
+        println!("✅ Exact-out sell rejection test passed!");
+    }
+
+    #[test]
+    fn test_volume_counters_accumulate() {
+        // ARRANGE: Fresh pool with no recorded volume
+        let mut pool = create_test_pool();
+
+        println!("🧪 Testing cumulative volume counters");
+
+        // ACT: Two buys followed by one sell
+        let mut expected_quote = 0u128;
+        for sol_in in [1_000_000_000, 3_000_000_000] {
+            let swap = pool.swap_amounts(sol_in, 0, true).unwrap();
+            pool.record_volume(&swap, true).unwrap();
+            expected_quote += (swap.amount_in + swap.admin_fee_in) as u128;
+        }
+
+        let sell = pool.swap_amounts(1_000_000, 0, false).unwrap();
+        pool.record_volume(&sell, false).unwrap();
+
+        // ASSERT: Each side counts the gross amount paid in
+        assert_eq!(expected_quote, 4_000_000_000);
+        assert_eq!(pool.total_quote_volume, expected_quote);
+        assert_eq!(
+            pool.total_meme_volume,
+            (sell.amount_in + sell.admin_fee_in) as u128
+        );
+
+        println!("✅ Volume counter test passed!");
+    }
+
+    #[test]
+    fn test_volume_counter_overflow() {
+        let mut pool = create_test_pool();
+        pool.total_quote_volume = u128::MAX;
+
+        let swap = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        let err = pool.record_volume(&swap, true).unwrap_err();
+
+        assert_eq!(err, error!(AmmError::MathOverflow));
+    }
+
+    #[test]
+    fn test_unique_buyers_counted_once() {
+        // ARRANGE: Fresh pool and an empty record, as `init_if_needed` creates it
+        let mut pool = create_test_pool();
+        let pool_key = Pubkey::new_unique();
+        let buyer = Pubkey::new_unique();
+        let mut record = BuyerRecord {
+            pool: Pubkey::default(),
+            buyer: Pubkey::default(),
+            first_buy_slot: 0,
+            initialized: false,
+        };
+
+        println!("🧪 Testing unique buyer counter");
+
+        // ACT: Same wallet buys twice
+        pool.record_buyer(&mut record, pool_key, buyer, 10).unwrap();
+        pool.record_buyer(&mut record, pool_key, buyer, 20).unwrap();
+
+        // ASSERT: Only the first buy counts and keeps its slot
+        assert_eq!(pool.unique_buyers, 1);
+        assert!(record.initialized);
+        assert_eq!(record.buyer, buyer);
+        assert_eq!(record.first_buy_slot, 10);
+
+        // A second wallet has its own record
+        let mut other = BuyerRecord {
+            pool: Pubkey::default(),
+            buyer: Pubkey::default(),
+            first_buy_slot: 0,
+            initialized: false,
+        };
+        pool.record_buyer(&mut other, pool_key, Pubkey::new_unique(), 30)
+            .unwrap();
+        assert_eq!(pool.unique_buyers, 2);
+
+        println!("✅ Unique buyer counter test passed!");
+    }
+
+    #[test]
+    fn test_non_sol_quote_pool() {
+        // ARRANGE: USDC-style quote with 6 decimals and a 50k target
+        let usdc_decimals = 1_000_000u128;
+        let gamma_s = 50_000 * usdc_decimals;
+        let gamma_m = 690_000_000_000_000u128;
+        let omega_m = 310_000_000_000_000u128;
+        let (price_factor_num, price_factor_denom) = (3, 1);
+
+        println!("🧪 Testing a pool quoted in a 6 decimal mint");
+
+        let (alpha_abs, decimals) = compute_alpha_abs(
+            gamma_s,
+            usdc_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+        )
+        .unwrap();
+        let beta = compute_beta(
+            gamma_s,
+            usdc_decimals,
+            gamma_m,
+            omega_m,
+            price_factor_num,
+            price_factor_denom,
+            decimals,
+        )
+        .unwrap();
+
+        let quote_mint = Pubkey::new_unique();
+        let mut pool = create_test_pool();
+        pool.quote_reserve = Reserve {
+            tokens: 0,
+            mint: quote_mint,
+            vault: Pubkey::new_unique(),
+        };
+        pool.meme_reserve.tokens = gamma_m as u64;
+        pool.config = Config {
+            alpha_abs,
+            beta,
+            price_factor_num,
+            price_factor_denom,
+            gamma_s: gamma_s as u64,
+            gamma_m: gamma_m as u64,
+            omega_m: omega_m as u64,
+            decimals: Decimals {
+                alpha: decimals,
+                beta: decimals,
+                quote: usdc_decimals as u64,
+            },
+            min_slot_between_trades: 0,
+            creator_fee_bps: 0,
+            graduation_mode: GraduationMode::TokenTarget,
+            max_price_impact_bps: 0,
+            virtual_sol_reserves: 0,
+            virtual_meme_reserves: 0,
+            min_quote_in: 0,
+            min_meme_in: 0,
+            wallet_cooldown_secs: 0,
+            migration_target: MigrationTarget::Raydium,
+            migration_price_tolerance_bps: 0,
+            first_buyer_bonus_bps: 0,
+            min_meme_reserve: 0,
+            burn_unsold_bps: 0,
+            min_unique_buyers: 0,
+            residual_fee_mode: ResidualFeeMode::Treasury,
+            curve_type: CurveType::PositiveIntercept,
+        };
+
+        // ACT: Buy with 100 USDC, then sell the tokens back
+        let buy = pool.swap_amounts(100 * usdc_decimals as u64, 1, true).unwrap();
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out;
+
+        let sell = pool.swap_amounts(buy.amount_out, 0, false).unwrap();
+
+        // ASSERT: Both directions work in the quote mint's units
+        assert_eq!(pool.quote_reserve.mint, quote_mint);
+        assert!(buy.amount_out > 0);
+        assert!(sell.amount_out > 0);
+        assert!(sell.amount_out + sell.admin_fee_out <= pool.quote_reserve.tokens);
+
+        println!("✅ Non-SOL quote pool test passed!");
+    }
+
+    #[test]
+    fn test_capped_buy_charges_only_fill() {
+        // ARRANGE: Pool 1 SOL short of its target
+        let mut pool = create_test_pool();
+        let max_delta_s = 1_000_000_000;
+        pool.quote_reserve.tokens = pool.config.gamma_s - max_delta_s;
+
+        println!("🧪 Testing the final buy is charged only for the fill");
+
+        // ACT: Offer far more SOL than the curve can take
+        let offered = 50_000_000_000; // 50 SOL
+        let swap = pool.swap_amounts(offered, 0, true).unwrap();
+        let charged = swap.amount_in + swap.admin_fee_in;
+
+        // ASSERT: User receives the whole reserve and pays for 1 SOL plus fee
+        assert_eq!(swap.amount_in, max_delta_s);
+        assert_eq!(swap.amount_out, pool.meme_reserve.tokens);
+        assert!(charged < offered);
+        assert_eq!(
+            charged,
+            get_gross_amount(max_delta_s, pool.fees.fee_quote_percent).unwrap()
+        );
+        assert!(charged - pool.fees.get_fee_quote_amount(charged).unwrap() >= max_delta_s);
+
+        println!("✅ Capped buy charge test passed!");
+    }
+
+    #[test]
+    fn test_sells_capped_at_reserve_floor() {
+        // ARRANGE: 40 SOL bought off an 85 SOL curve, half of it held as the floor
+        let mut pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        let buy = pool.buy_meme_swap_amounts(40_000_000_000, 0).unwrap();
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        let sold = pool.meme_sold().unwrap();
+        pool.config.min_meme_reserve = sold / 2;
+
+        println!("🧪 Testing sells near the reserve floor");
+
+        // ACT: Try to sell back everything that was bought
+        let sell = pool.swap_amounts(sold, 0, false).unwrap();
+
+        // ASSERT: Only what sits above the floor comes back, and quote stays
+        assert_eq!(sell.amount_in, sold - sold / 2);
+        assert!(sell.amount_out + sell.admin_fee_out < pool.quote_reserve.tokens);
+
+        pool.meme_reserve.tokens += sell.amount_in;
+        pool.quote_reserve.tokens -= sell.amount_out + sell.admin_fee_out;
+        assert_eq!(pool.sellable_meme().unwrap(), 0);
+        assert!(pool.quote_reserve.tokens > 0);
+
+        // At the floor there's nothing left to sell, either way of quoting
+        assert_eq!(
+            pool.swap_amounts(1_000_000, 0, false).unwrap_err(),
+            error!(AmmError::ExceedsPoolReserve)
+        );
+        assert_eq!(
+            pool.sell_meme_exact_out_amounts(1_000, u64::MAX).unwrap_err(),
+            error!(AmmError::ExceedsPoolReserve)
+        );
+
+        println!("✅ Reserve floor sell cap test passed!");
+    }
+
+    #[test]
+    fn test_reserve_floor_set_by_update() {
+        // ARRANGE: 40 SOL bought off an 85 SOL curve, all of it sellable
+        let mut pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        let buy = pool.buy_meme_swap_amounts(40_000_000_000, 0).unwrap();
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        let sold = pool.meme_sold().unwrap();
+        assert_eq!(pool.sellable_meme().unwrap(), sold);
+        let unfloored = pool.swap_amounts(sold, 0, false).unwrap();
+
+        println!("🧪 Testing the reserve floor switched on after launch");
+
+        // ACT: The admin holds half of what was sold as the floor
+        let mut guards = pool.guards();
+        guards.min_meme_reserve = sold / 2;
+        pool.update_guards(guards).unwrap();
+
+        // ASSERT: Selling everything back only returns what's above the floor
+        let sell = pool.swap_amounts(sold, 0, false).unwrap();
+        assert_eq!(unfloored.amount_in, sold);
+        assert_eq!(sell.amount_in, sold - sold / 2);
+        assert!(sell.amount_out < unfloored.amount_out);
+        assert!(sell.amount_out + sell.admin_fee_out < pool.quote_reserve.tokens);
+
+        // A floor above the trading supply is refused
+        guards.min_meme_reserve = pool.config.gamma_m + 1;
+        assert_eq!(
+            pool.update_guards(guards).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(pool.config.min_meme_reserve, sold / 2);
+
+        println!("✅ Updated reserve floor test passed!");
+    }
+
+    #[test]
+    fn test_sell_on_empty_pool_errors() {
+        // ARRANGE: Pool nobody has bought from yet
+        let mut pool = create_test_pool();
+        pool.quote_reserve.tokens = 0;
+
+        // ACT: Try to sell into it
+        let result = pool.swap_amounts(1_000_000, 0, false);
+
+        // ASSERT: A clean error instead of a panic or a free sell
+        assert_eq!(result.unwrap_err(), error!(AmmError::ExceedsPoolReserve));
+
+        println!("✅ Empty pool sell test passed!");
+    }
+
+    #[test]
+    fn test_partial_admin_fee_withdrawal() {
+        let mut pool = create_test_pool();
+        pool.admin_fees_quote = 1_000;
+        pool.admin_fees_meme = 500;
+
+        // Two partial sweeps
+        pool.take_admin_fees(400, 0).unwrap();
+        pool.take_admin_fees(100, 500).unwrap();
+
+        assert_eq!(pool.admin_fees_quote, 500);
+        assert_eq!(pool.admin_fees_meme, 0);
+
+        println!("✅ Partial admin fee withdrawal test passed!");
+    }
+
+    #[test]
+    fn test_admin_fee_over_withdrawal() {
+        let mut pool = create_test_pool();
+        pool.admin_fees_quote = 1_000;
+        pool.admin_fees_meme = 500;
+
+        let err = pool.take_admin_fees(1_001, 0).unwrap_err();
+        assert_eq!(err, error!(AmmError::InsufficientAccruedFees));
+
+        let err = pool.take_admin_fees(0, 501).unwrap_err();
+        assert_eq!(err, error!(AmmError::InsufficientAccruedFees));
+
+        // Nothing is deducted on failure
+        assert_eq!(pool.admin_fees_quote, 1_000);
+        assert_eq!(pool.admin_fees_meme, 500);
+    }
+
+    #[test]
+    fn test_creator_fee_split_sums_to_fee() {
+        // ARRANGE: Creator takes 20% of every quote fee
+        let mut pool = create_test_pool();
+        pool.config.creator_fee_bps = 2_000;
+
+        println!("🧪 Testing the creator/protocol fee split");
+
+        let mut total = 0;
+        for fee in [0_u64, 1, 4, 5, 9, 10_000, 12_345_679, u32::MAX as u64] {
+            // ACT
+            pool.accrue_quote_fees(fee).unwrap();
+            total += fee;
+
+            // ASSERT: Nothing is lost or created by the split
+            assert_eq!(pool.admin_fees_quote + pool.creator_fees_quote, total);
+        }
+
+        // Dust fees round in the protocol's favour
+        assert!(pool.creator_fees_quote <= total * 2_000 / 10_000);
+
+        // 100% and 0% send everything one way
+        let mut pool = create_test_pool();
+        pool.config.creator_fee_bps = 10_000;
+        pool.accrue_quote_fees(777).unwrap();
+        assert_eq!((pool.admin_fees_quote, pool.creator_fees_quote), (0, 777));
+
+        pool.config.creator_fee_bps = 0;
+        pool.accrue_quote_fees(777).unwrap();
+        assert_eq!((pool.admin_fees_quote, pool.creator_fees_quote), (777, 777));
+
+        println!("✅ Creator fee split test passed!");
+    }
+
+    #[test]
+    fn test_creator_fee_over_withdrawal() {
+        let mut pool = create_test_pool();
+        pool.creator_fees_quote = 1_000;
+
+        let err = pool.take_creator_fees(1_001).unwrap_err();
+        assert_eq!(err, error!(AmmError::InsufficientAccruedFees));
+        assert_eq!(pool.creator_fees_quote, 1_000);
+
+        pool.take_creator_fees(400).unwrap();
+        assert_eq!(pool.creator_fees_quote, 600);
+    }
+
+    #[test]
+    fn test_residual_fee_destinations() {
+        // ARRANGE: The fee ledgers of a pool after one trade in each mode
+        let books = |mode: ResidualFeeMode, migrated: bool| {
+            let mut pool = create_test_pool();
+            pool.config.creator_fee_bps = 2_000;
+            pool.config.residual_fee_mode = mode;
+            pool.pool_migration = migrated;
+            let quote_reserve = pool.quote_reserve.tokens;
+
+            let burned = pool.accrue_swap_fees(1_000, 300).unwrap();
+
+            (
+                pool.admin_fees_quote,
+                pool.creator_fees_quote,
+                pool.admin_fees_meme,
+                pool.staker_fees_quote,
+                burned,
+                pool.quote_reserve.tokens - quote_reserve,
+            )
+        };
+
+        println!("🧪 Testing where each residual fee mode sends the fees");
+
+        // ACT & ASSERT: Before migration every mode pays the treasury
+        for mode in [
+            ResidualFeeMode::Treasury,
+            ResidualFeeMode::Burn,
+            ResidualFeeMode::Stakers,
+        ] {
+            assert_eq!(books(mode, false), (800, 200, 300, 0, 0, 0), "{:?}", mode);
+        }
+
+        // Treasury keeps the pre-migration split
+        assert_eq!(books(ResidualFeeMode::Treasury, true), (800, 200, 300, 0, 0, 0));
+
+        // The meme fee is burned, the quote fee stays in the reserve
+        assert_eq!(books(ResidualFeeMode::Burn, true), (0, 0, 0, 0, 300, 1_000));
+
+        // Stakers get the whole quote fee, the meme fee is burned
+        assert_eq!(books(ResidualFeeMode::Stakers, true), (0, 0, 0, 1_000, 300, 0));
+
+        println!("✅ Residual fee destinations test passed!");
+    }
+
+    #[test]
+    fn test_take_fees_for_stakers() {
+        let mut pool = create_test_pool();
+        pool.staker_fees_quote = 300;
+        pool.admin_fees_quote = 500;
+
+        // The stakers' share goes first
+        pool.take_fees_for_stakers(200).unwrap();
+        assert_eq!((pool.staker_fees_quote, pool.admin_fees_quote), (100, 500));
+
+        // Then the admin fees top it up
+        pool.take_fees_for_stakers(400).unwrap();
+        assert_eq!((pool.staker_fees_quote, pool.admin_fees_quote), (0, 200));
+
+        // More than both together is refused and takes nothing
+        pool.staker_fees_quote = 100;
+        assert_eq!(
+            pool.take_fees_for_stakers(301).unwrap_err(),
+            error!(AmmError::InsufficientAccruedFees)
+        );
+        assert_eq!((pool.staker_fees_quote, pool.admin_fees_quote), (100, 200));
+
+        // Fees owed to stakers are backed by the quote vault
+        assert_eq!(
+            pool.check_reserve_invariant(u64::MAX, pool.quote_reserve.tokens + 299)
+                .unwrap_err(),
+            error!(AmmError::ReserveInvariantViolated)
+        );
+    }
+
+    #[test]
+    fn test_double_migration_rejected() {
+        let mut pool = create_test_pool();
+        let amm_pool = Pubkey::new_unique();
+
+        // First migration goes through and sets both markers
+        pool.check_not_migrated().unwrap();
+        pool.mark_migrated(amm_pool).unwrap();
+        assert!(pool.pool_migration);
+        assert_eq!(pool.pool_key, amm_pool);
+
+        // Second one is refused and leaves the recorded pool alone
+        assert_eq!(
+            pool.check_not_migrated().unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+        assert_eq!(
+            pool.mark_migrated(Pubkey::new_unique()).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+        assert_eq!(pool.pool_key, amm_pool);
+
+        // A pool_key alone is enough to block
+        let mut half_migrated = create_test_pool();
+        half_migrated.pool_key = amm_pool;
+        assert_eq!(
+            half_migrated.check_not_migrated().unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Double migration guard test passed!");
+    }
+
+    #[test]
+    fn test_sold_out_lock_reported_once() {
+        // ARRANGE: Pool one buy away from selling out
+        let mut pool = create_test_pool();
+        pool.quote_reserve.tokens = pool.config.gamma_s - 1_000_000_000;
+
+        println!("🧪 Testing the sold out lock fires once");
+
+        // ACT: Buy out the reserve, applying it like swap_y does
+        let swap = pool.swap_amounts(50_000_000_000, 0, true).unwrap();
+        pool.quote_reserve.tokens += swap.amount_in;
+        pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+
+        // ASSERT: The depleting swap locks, later checks don't report again
+        assert_eq!(pool.meme_reserve.tokens, 0);
+        assert!(pool.lock_if_sold_out());
+        assert!(pool.locked);
+        assert!(!pool.lock_if_sold_out());
+
+        // A pool with tokens left is never locked
+        let mut live = create_test_pool();
+        assert!(!live.lock_if_sold_out());
+        assert!(!live.locked);
+
+        println!("✅ Sold out lock test passed!");
+    }
+
+    #[test]
+    fn test_quote_decimals_match_mint() {
+        let sol = Decimals {
+            alpha: 1_000_000,
+            beta: 1_000_000,
+            quote: 1_000_000_000,
+        };
+        let usdc = Decimals {
+            quote: 1_000_000,
+            ..sol
+        };
+
+        // Matching mints
+        sol.check_quote_mint(9).unwrap();
+        usdc.check_quote_mint(6).unwrap();
+
+        // Swapped or impossible mints
+        let mismatch = error!(AmmError::DecimalsMismatch);
+        assert_eq!(sol.check_quote_mint(6).unwrap_err(), mismatch);
+        assert_eq!(usdc.check_quote_mint(9).unwrap_err(), mismatch);
+        assert_eq!(sol.check_quote_mint(20).unwrap_err(), mismatch);
+
+        println!("✅ Quote decimals validation test passed!");
+    }
+
+    #[test]
+    fn test_supply_at_meme_sold_inverts_delta_m() {
+        // 85 SOL target, 310M LP tokens, price factor 3
+        let pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+
+        println!("🧪 Testing supply_at_meme_sold against compute_delta_m");
+
+        let mut last_supply = 0;
+        for step in 0..=20u64 {
+            let meme_sold = pool.config.gamma_m / 40 * step;
+            let supply = pool.supply_at_meme_sold(meme_sold).unwrap();
+
+            // The curve up to `supply` has sold at least `meme_sold`...
+            assert!(pool.compute_delta_m(0, supply).unwrap() >= meme_sold);
+            // ...and one lamport less has not
+            if supply > 0 {
+                assert!(pool.compute_delta_m(0, supply - 1).unwrap() < meme_sold);
+            }
+            // More tokens sold means a higher supply
+            assert!(supply >= last_supply);
+
+            last_supply = supply;
+        }
+
+        assert_eq!(
+            pool.supply_at_meme_sold(pool.config.gamma_m + 1)
+                .unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+
+        println!("✅ supply_at_meme_sold test passed!");
+    }
+
+    #[test]
+    fn test_batch_buy_matches_individual_buys() {
+        // ARRANGE: Three buys of growing size
+        let pool = create_test_pool();
+        let amounts = [1_000_000_000, 2_000_000_000, 5_000_000_000];
+        let min_outs = [1, 1, 1];
+
+        println!("🧪 Testing batched buys against sequential buys");
+
+        // ACT: Batch quote vs. executing one after another
+        let batch = pool.buy_meme_batch_amounts(&amounts, &min_outs).unwrap();
+
+        let mut sequential = create_test_pool();
+        let (mut amount_in, mut amount_out, mut fee_in, mut fee_out) = (0, 0, 0, 0);
+        for amount in amounts {
+            let swap = sequential.swap_amounts(amount, 1, true).unwrap();
+            sequential.quote_reserve.tokens += swap.amount_in;
+            sequential.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+            amount_in += swap.amount_in;
+            amount_out += swap.amount_out;
+            fee_in += swap.admin_fee_in;
+            fee_out += swap.admin_fee_out;
+        }
+
+        // ASSERT: Aggregate is the sum, and the quoting pool is untouched
+        assert_eq!(batch.amount_in, amount_in);
+        assert_eq!(batch.amount_out, amount_out);
+        assert_eq!(batch.admin_fee_in, fee_in);
+        assert_eq!(batch.admin_fee_out, fee_out);
+        assert_eq!(batch.amount_in + batch.admin_fee_in, 8_000_000_000);
+        assert_eq!(pool.quote_reserve.tokens, create_test_pool().quote_reserve.tokens);
+
+        println!("✅ Batched buy test passed!");
+    }
+
+    #[test]
+    fn test_batch_buy_rejections() {
+        let pool = create_test_pool();
+
+        // Every sub-buy keeps its own slippage floor
+        let err = pool
+            .buy_meme_batch_amounts(&[1_000_000_000, 1_000_000_000], &[1, u64::MAX])
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::SlippageExceeded));
+
+        // Mismatched or empty vectors
+        let err = pool.buy_meme_batch_amounts(&[1, 2], &[0]).unwrap_err();
+        assert_eq!(err, error!(AmmError::InvalidArg));
+        let err = pool.buy_meme_batch_amounts(&[], &[]).unwrap_err();
+        assert_eq!(err, error!(AmmError::InvalidArg));
+
+        // At most MAX_BATCH_BUYS entries
+        pool.buy_meme_batch_amounts(&[1_000_000; MAX_BATCH_BUYS], &[0; MAX_BATCH_BUYS])
+            .unwrap();
+        let err = pool
+            .buy_meme_batch_amounts(&[1_000_000; MAX_BATCH_BUYS + 1], &[0; MAX_BATCH_BUYS + 1])
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::BatchTooLarge));
+    }
+
+    #[test]
+    fn test_fee_rounding_conserves_reserves() {
+        // ARRANGE: Both fee legs active so every rounding path is exercised
+        let mut pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        pool.fees.fee_meme_percent = FEE;
+
+        let mut vault_quote = pool.quote_reserve.tokens;
+        let mut vault_meme = pool.meme_reserve.tokens;
+
+        // Small deterministic xorshift stream, no rand dependency needed
+        let mut seed: u64 = 0x9E37_79B9_7F4A_7C15;
+        let mut next = move |max: u64| {
+            seed ^= seed << 13;
+            seed ^= seed >> 7;
+            seed ^= seed << 17;
+            1 + seed % max
+        };
+
+        println!("🧪 Testing fee rounding never lets reserves drift from the vaults");
+
+        for i in 0..2_000 {
+            let buy_meme = i % 2 == 0;
+            // Open with a 10 SOL buy so sells have a reserve to draw from
+            let amount = match i {
+                0 => 10_000_000_000,
+                _ if i % 10 == 0 => next(10),
+                _ => next(100_000),
+            };
+            let (m_b, s_b) = pool.balances();
+
+            // ACT: Quote and settle the trade the way the swap handlers do
+            let swap = match pool.swap_amounts(amount, 0, buy_meme) {
+                // A single lamport is all fee, so the buy is refused
+                Err(err) if buy_meme && amount == 1 => {
+                    assert_eq!(err, error!(AmmError::NoZeroTokens));
+                    continue;
+                }
+                swap => swap.unwrap(),
+            };
+
+            if buy_meme {
+                assert_eq!(swap.amount_in + swap.admin_fee_in, amount);
+                assert_eq!(
+                    swap.amount_out + swap.admin_fee_out,
+                    pool.compute_delta_m(s_b, s_b + swap.amount_in).unwrap()
+                );
+
+                vault_quote += swap.amount_in + swap.admin_fee_in;
+                vault_meme -= swap.amount_out;
+                pool.admin_fees_quote += swap.admin_fee_in;
+                pool.admin_fees_meme += swap.admin_fee_out;
+                pool.quote_reserve.tokens += swap.amount_in;
+                pool.meme_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+            } else {
+                assert_eq!(swap.amount_in + swap.admin_fee_in, amount);
+                assert_eq!(
+                    swap.amount_out + swap.admin_fee_out,
+                    pool.compute_delta_s(s_b, swap.amount_in).unwrap()
+                );
+
+                vault_meme += swap.amount_in + swap.admin_fee_in;
+                vault_quote -= swap.amount_out;
+                pool.admin_fees_meme += swap.admin_fee_in;
+                pool.admin_fees_quote += swap.admin_fee_out;
+                pool.meme_reserve.tokens += swap.amount_in;
+                pool.quote_reserve.tokens -= swap.amount_out + swap.admin_fee_out;
+            }
+
+            // ASSERT: Vaults always hold exactly reserves plus accrued fees
+            assert_eq!(vault_quote, pool.quote_reserve.tokens + pool.admin_fees_quote);
+            assert_eq!(vault_meme, pool.meme_reserve.tokens + pool.admin_fees_meme);
+            assert!(pool.meme_reserve.tokens <= m_b + amount);
+        }
+
+        println!("✅ Fee rounding conservation test passed!");
+    }
+
+    #[test]
+    fn test_dust_sell_fee_capped() {
+        // ARRANGE: A sell so small the doubled, rounded-up fee exceeds it
+        let mut pool = create_test_pool();
+        pool.fees.fee_meme_percent = FEE;
+
+        // ACT
+        let swap = pool.swap_amounts(1, 0, false).unwrap();
+
+        // ASSERT: The fee eats the dust instead of underflowing
+        assert_eq!(swap.amount_in, 0);
+        assert_eq!(swap.admin_fee_in, 1);
+        assert_eq!(swap.amount_out, 0);
+        assert_eq!(swap.admin_fee_out, 0);
+
+        println!("✅ Dust sell fee cap test passed!");
+    }
+
+    thread_local! {
+        static RETURN_DATA: std::cell::RefCell<Option<Vec<u8>>> = const { std::cell::RefCell::new(None) };
+    }
+
+    // Keeps return data per test thread, the default stubs drop it
+    struct ReturnDataStubs;
+
+    impl solana_program::program_stubs::SyscallStubs for ReturnDataStubs {
+        fn sol_set_return_data(&self, data: &[u8]) {
+            RETURN_DATA.with(|d| *d.borrow_mut() = Some(data.to_vec()));
+        }
+
+        fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+            RETURN_DATA.with(|d| d.borrow().clone().map(|data| (crate::ID, data)))
+        }
+    }
+
+    #[test]
+    fn test_slippage_detail_returned() {
+        // ARRANGE: Capture return data and quote a buy to set the floor from
+        solana_program::program_stubs::set_syscall_stubs(Box::new(ReturnDataStubs));
+        let pool = create_test_pool();
+        let sol_amount = 1_000_000_000;
+        let expected = pool.swap_amounts(sol_amount, 0, true).unwrap().amount_out;
+
+        println!("🧪 Testing slippage failures report expected vs actual");
+
+        // ACT: Ask for one token more than the curve gives
+        let err = pool
+            .swap_amounts(sol_amount, expected + 1, true)
+            .unwrap_err();
+
+        // ASSERT: Error unchanged, detail decodes from return data
+        assert_eq!(err, error!(AmmError::SlippageExceeded));
+        let (program_id, data) = solana_program::program::get_return_data().unwrap();
+        assert_eq!(program_id, crate::ID);
+        assert_eq!(
+            SlippageDetail::try_from_slice(&data).unwrap(),
+            SlippageDetail {
+                actual: expected,
+                limit: expected + 1,
+            }
+        );
+
+        // Same on the sell side
+        let meme_amount = 1_000_000_000;
+        let expected = pool.swap_amounts(meme_amount, 0, false).unwrap().amount_out;
+        pool.swap_amounts(meme_amount, u64::MAX, false).unwrap_err();
+        let (_, data) = solana_program::program::get_return_data().unwrap();
+        assert_eq!(
+            SlippageDetail::try_from_slice(&data).unwrap(),
+            SlippageDetail {
+                actual: expected,
+                limit: u64::MAX,
+            }
+        );
+
+        println!("✅ Slippage detail test passed!");
+    }
+
+    #[test]
+    fn test_linear_fast_path_error_bound() {
+        // ARRANGE: The fixture curve and a launch-like curve, part way up
+        let mut launch_pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        launch_pool.quote_reserve.tokens = 40_000_000_000;
+
+        println!("🧪 Testing the linear fast path stays within 0.1% of the curve");
+
+        for pool in [create_test_pool(), launch_pool] {
+            let p = &pool.config;
+            let s_b = pool.quote_reserve.tokens as u128;
+            let mut hits = 0;
+
+            // Meme bought by a single lamport, the rounding granularity
+            let lamport = pool.compute_delta_m(s_b as u64 - 1, s_b as u64).unwrap();
+
+            for shift in 0..64 {
+                let delta_m = 1_u128 << shift;
+
+                // ACT
+                let Some(fast) = delta_s_linear_strategy(
+                    p.alpha_abs,
+                    p.beta,
+                    p.decimals.alpha,
+                    p.decimals.beta,
+                    s_b,
+                    delta_m,
+                ) else {
+                    continue;
+                };
+                hits += 1;
+
+                // ASSERT: Selling back `fast` SOL of supply is worth at most
+                // `delta_m`, and within 0.1% of it beyond a lamport's rounding
+                let fast = fast as u64;
+                let delta_m = delta_m as u64;
+                let worth = pool
+                    .compute_delta_m(s_b as u64 - fast, s_b as u64)
+                    .unwrap();
+                assert!(worth <= delta_m, "fast {} overpays for {}", fast, delta_m);
+                assert!(
+                    delta_m - worth <= 1 + lamport + delta_m / 1_000,
+                    "fast {} worth {} too far from {}",
+                    fast,
+                    worth,
+                    delta_m
+                );
+            }
+
+            assert!(hits > 0, "fast path never taken");
+        }
+
+        println!("✅ Linear fast path error bound test passed!");
+    }
+
+    #[test]
+    fn test_linear_fast_path_falls_back_for_large_sells() {
+        let pool = create_test_pool();
+        let p = &pool.config;
+        let s_b = pool.quote_reserve.tokens;
+
+        // Selling back everything left on the curve is far past the threshold
+        let delta_m = (p.gamma_m - pool.meme_reserve.tokens) as u128;
+        assert!(delta_s_linear_strategy(
+            p.alpha_abs,
+            p.beta,
+            p.decimals.alpha,
+            p.decimals.beta,
+            s_b as u128,
+            delta_m,
+        )
+        .is_none());
+    }
+
+    #[test]
+    fn test_delta_s_no_overflow_at_caps() {
+        // ARRANGE: Curves at the edges the slope and intercept checks accept:
+        // the flattest, default and steepest price factors for the default
+        // allocations, 6 and 9 decimal quotes, small to huge targets
+        let (gm, om) = (crate::consts::DEFAULT_MAX_M, crate::consts::DEFAULT_MAX_M_LP);
+        let factors = [(9, 4), (3, 1), (4, 1)];
+        let quotes = [1_000_000, 1_000_000_000];
+        let targets = [1, 85, 10_000, 1_000_000];
+        let mut curves = 0;
+
+        println!("🧪 Testing delta_s_positive_strategy never overflows at the caps");
+
+        for (num, denom) in factors {
+            for quote in quotes {
+                for target in targets {
+                    let gamma_s = target * quote;
+
+                    // Curves new_pool refuses never trade
+                    if compute_alpha_abs(gamma_s, quote, gm, om, num, denom).is_err() {
+                        continue;
+                    }
+                    curves += 1;
+
+                    let pool = create_curve_pool(gamma_s, quote, om, num, denom);
+                    let p = &pool.config;
+
+                    // ACT & ASSERT: Every supply up to the target, selling back
+                    // anything up to the whole trading allocation
+                    for step in 1..=32 {
+                        let s_b = gamma_s * step / 32;
+
+                        for delta_m in [1, p.gamma_m / 1_000, p.gamma_m / 2, p.gamma_m] {
+                            let delta_s = delta_s_positive_strategy(
+                                p.alpha_abs,
+                                p.beta,
+                                p.decimals.alpha,
+                                p.decimals.beta,
+                                s_b,
+                                delta_m as u128,
+                            );
+                            assert!(
+                                delta_s.is_some(),
+                                "overflow at gamma_s {} quote {} factor {}/{} s_b {} delta_m {}",
+                                gamma_s,
+                                quote,
+                                num,
+                                denom,
+                                s_b,
+                                delta_m
+                            );
+                        }
+                    }
+                }
+            }
+        }
+
+        assert!(curves > 0, "no curve accepted");
+
+        println!("✅ delta_s overflow stress test passed ({} curves)!", curves);
+    }
+
+    #[test]
+    fn test_reserve_vaults_not_recoverable() {
+        let mut pool = create_test_pool();
+        pool.meme_reserve.vault = Pubkey::new_unique();
+        pool.quote_reserve.vault = Pubkey::new_unique();
+
+        // Both reserves are off limits to recover_stuck_tokens...
+        assert!(pool.is_reserve_vault(&pool.meme_reserve.vault));
+        assert!(pool.is_reserve_vault(&pool.quote_reserve.vault));
+
+        // ...any other account of the pool signer isn't
+        assert!(!pool.is_reserve_vault(&Pubkey::new_unique()));
+        assert!(!pool.is_reserve_vault(&pool.fee_vault_quote));
+
+        println!("✅ Reserve vault recovery guard test passed!");
+    }
+
+    #[test]
+    fn test_update_fees() {
+        // ARRANGE: A live pool charging the default 1% quote fee
+        let mut pool = create_test_pool();
+        let fees = Fees {
+            fee_meme_percent: MAX_MEME_FEE,
+            fee_quote_percent: MAX_QUOTE_FEE,
+        };
+
+        println!("🧪 Testing fee updates on a live pool");
+
+        // ACT: Raise both rates to their caps
+        let previous = pool.update_fees(fees).unwrap();
+
+        // ASSERT: The new rates apply and the old ones come back for the event
+        assert_eq!(previous.fee_quote_percent, FEE);
+        assert_eq!(pool.fees, fees);
+        let swap = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        assert_eq!(swap.admin_fee_in, 50_000_000);
+
+        println!("✅ Fee update test passed!");
+    }
+
+    #[test]
+    fn test_update_fees_rejected() {
+        let mut pool = create_test_pool();
+
+        // One unit above either cap is refused and changes nothing
+        for fees in [
+            Fees {
+                fee_meme_percent: 0,
+                fee_quote_percent: MAX_QUOTE_FEE + 1,
+            },
+            Fees {
+                fee_meme_percent: MAX_MEME_FEE + 1,
+                fee_quote_percent: FEE,
+            },
+        ] {
+            assert_eq!(
+                pool.update_fees(fees).unwrap_err(),
+                error!(AmmError::FeeTooHigh)
+            );
+            assert_eq!(pool.fees.fee_quote_percent, FEE);
+        }
+
+        // Migrated pools keep their fees
+        pool.pool_migration = true;
+        assert_eq!(
+            pool.update_fees(Fees::default()).unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Fee update rejection test passed!");
+    }
+
+    #[test]
+    fn test_creator_lock_not_sold() {
+        // ARRANGE: A launch locking 50M for its creator
+        let creator_locked = crate::consts::MAX_CREATOR_LOCKED_TOKENS;
+        let mut pool = create_locked_curve_pool(
+            creator_locked,
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+
+        println!("🧪 Testing the creator lock stays off the curve");
+
+        // ACT & ASSERT: Nothing counts as sold before the first buy
+        pool.check_curve_consistency().unwrap();
+        pool.check_funded(pool.config.gamma_m).unwrap();
+        assert_eq!(pool.meme_sold().unwrap(), 0);
+        assert_eq!(pool.percent_sold().unwrap(), 0);
+
+        // Raising the whole target sells the curve's supply, not the lock
+        let sold = pool.compute_delta_m(0, pool.config.gamma_s).unwrap();
+        pool.meme_reserve.tokens -= sold;
+        assert!(sold <= crate::consts::DEFAULT_MAX_M as u64 - creator_locked);
+        assert!(pool.percent_sold().unwrap() >= BPS_DENOMINATOR - CURVE_TOLERANCE_BPS);
+
+        println!("✅ Creator lock curve test passed!");
+    }
+
+    #[test]
+    fn test_default_price_factor_builds_curve() {
+        use crate::consts::{DEFAULT_PRICE_FACTOR_DENOMINATOR, DEFAULT_PRICE_FACTOR_NUMERATOR};
+
+        println!("🧪 Testing new_pool's default price factor");
+
+        // ACT & ASSERT: With and without the largest creator lock
+        for creator_locked in [0, crate::consts::MAX_CREATOR_LOCKED_TOKENS] {
+            let pool = create_locked_curve_pool(
+                creator_locked,
+                85_000_000_000,
+                1_000_000_000,
+                crate::consts::DEFAULT_MAX_M_LP,
+                DEFAULT_PRICE_FACTOR_NUMERATOR,
+                DEFAULT_PRICE_FACTOR_DENOMINATOR,
+            );
+
+            pool.check_curve_consistency().unwrap();
+        }
+
+        println!("✅ Default price factor test passed!");
+    }
+
+    #[test]
+    fn test_mis_funded_pool_rejected() {
+        // ARRANGE: A pool set up the way new_pool leaves it
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 0;
+
+        println!("🧪 Testing the new pool funding guard");
+
+        // ACT & ASSERT: A vault holding the trading supply passes
+        pool.check_funded(pool.config.gamma_m).unwrap();
+
+        // A vault short of the trading supply is refused
+        assert_eq!(
+            pool.check_funded(pool.config.gamma_m - 1).unwrap_err(),
+            error!(AmmError::PoolNotProperlyFunded)
+        );
+
+        // So are reserves that don't match a fresh pool
+        pool.meme_reserve.tokens = pool.config.gamma_m - 1;
+        assert_eq!(
+            pool.check_funded(pool.config.gamma_m).unwrap_err(),
+            error!(AmmError::PoolNotProperlyFunded)
+        );
+
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.quote_reserve.tokens = 1;
+        assert_eq!(
+            pool.check_funded(pool.config.gamma_m).unwrap_err(),
+            error!(AmmError::PoolNotProperlyFunded)
+        );
+
+        println!("✅ New pool funding guard test passed!");
+    }
+
+    #[test]
+    fn test_meme_reserve_above_supply_rejected() {
+        // ARRANGE: A misconfigured pool holding more meme than it trades
+        let mut pool = create_test_pool();
+        pool.meme_reserve.tokens = pool.config.gamma_m + 1;
+
+        println!("🧪 Testing the meme reserve underflow guard");
+
+        // ACT & ASSERT: Creation refuses it and sells fail instead of wrapping
+        assert_eq!(
+            pool.check_meme_reserve().unwrap_err(),
+            error!(AmmError::MemeReserveExceedsSupply)
+        );
+        assert_eq!(pool.meme_sold().unwrap_err(), error!(AmmError::MathOverflow));
+        assert_eq!(
+            pool.swap_amounts(1_000_000_000, 0, false).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+        assert_eq!(
+            pool.sell_meme_exact_out_amounts(1_000_000, u64::MAX)
+                .unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        // A reserve of exactly the trading supply is a fresh pool
+        pool.meme_reserve.tokens = pool.config.gamma_m;
+        pool.check_meme_reserve().unwrap();
+        assert_eq!(pool.meme_sold().unwrap(), 0);
+
+        println!("✅ Meme reserve underflow guard test passed!");
+    }
+
+    #[test]
+    fn test_compute_delta_s_known_vectors() {
+        // ARRANGE: The default 85 SOL launch curve, where the meme sold up to
+        // supply s is m(s) = beta * s / (beta_dec * D_S)
+        // + alpha_abs * s^2 / (2 * alpha_dec * D_S^2). Expected values are
+        // the floored closed-form roots of that integral, computed offline.
+        // Small sells take the linear fast path and are checked against the
+        // range sold below s_b. Larger ones take the quadratic, which solves
+        // m(s_b + delta_s) - m(s_b) = delta_m, the range above s_b.
+        let pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        let p = &pool.config;
+        let vectors: [(u64, u64, u64); 13] = [
+            // (s_b, delta_m, delta_s)
+            (1_000_000_000, 1_000_000, 186),
+            (1_000_000_000, 1_000_000_000_000, 186_332_745),
+            (5_000_000_000, 2_500_000_000_000, 443_182_323),
+            (10_000_000_000, 100_000_000, 16_782),
+            (10_000_000_000, 50_000_000_000_000, 8_031_771_331),
+            (25_000_000_000, 10_000_000_000_000, 1_428_070_596),
+            (42_500_000_000, 1_000_000_000, 123_188),
+            (42_500_000_000, 250_000_000_000_000, 27_665_183_736),
+            (60_000_000_000, 400_000_000_000_000, 37_947_700_123),
+            (70_000_000_000, 10_000_000_000, 1_005_570),
+            (85_000_000_000, 1, 0),
+            (85_000_000_000, 5_000_000_000_000, 457_625_059),
+            (85_000_000_000, 600_000_000_000_000, 47_878_895_877),
+        ];
+        let mut fast = 0;
+
+        println!("🧪 Testing compute_delta_s against closed-form vectors");
+
+        for (s_b, delta_m, expected) in vectors {
+            // ACT
+            let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+
+            // ASSERT: The fast path may round down by its 0.1% error bound,
+            // the quadratic only by the final floor
+            let is_fast = delta_s_linear_strategy(
+                p.alpha_abs,
+                p.beta,
+                p.decimals.alpha,
+                p.decimals.beta,
+                s_b as u128,
+                delta_m as u128,
+            )
+            .is_some();
+
+            if is_fast {
+                fast += 1;
+                assert!(
+                    delta_s <= expected && expected - delta_s <= expected / 1_000,
+                    "fast path s_b {} delta_m {}: {} vs {}",
+                    s_b,
+                    delta_m,
+                    delta_s,
+                    expected
+                );
+            } else {
+                assert!(
+                    delta_s.abs_diff(expected) <= 1,
+                    "quadratic s_b {} delta_m {}: {} vs {}",
+                    s_b,
+                    delta_m,
+                    delta_s,
+                    expected
+                );
+            }
+        }
+
+        // Both variants are pinned
+        assert!(fast > 0 && fast < vectors.len());
+
+        println!("✅ compute_delta_s vector test passed ({} fast path)!", fast);
+    }
+
+    #[test]
+    fn test_price_impact_limit() {
+        // ARRANGE: 1% cap; at 250 SOL the price moves ~0.4% per SOL traded
+        let mut pool = create_test_pool();
+        pool.config.max_price_impact_bps = 100;
+        let s = pool.quote_reserve.tokens;
+
+        println!("🧪 Testing the per-swap price impact limit");
+
+        // ACT & ASSERT: Buys just under and past the cap
+        let near = pool.swap_amounts(2_000_000_000, 0, true).unwrap();
+        pool.check_price_impact(&near, true).unwrap();
+
+        let past = pool.swap_amounts(3_000_000_000, 0, true).unwrap();
+        assert_eq!(
+            pool.check_price_impact(&past, true).unwrap_err(),
+            error!(AmmError::PriceImpactTooHigh)
+        );
+
+        // Sells returning the same SOL behave alike
+        let meme_near = pool.compute_delta_m(s - 2_000_000_000, s).unwrap();
+        let near = pool.swap_amounts(meme_near, 0, false).unwrap();
+        pool.check_price_impact(&near, false).unwrap();
+
+        let meme_past = pool.compute_delta_m(s - 3_000_000_000, s).unwrap();
+        let past_sell = pool.swap_amounts(meme_past, 0, false).unwrap();
+        assert_eq!(
+            pool.check_price_impact(&past_sell, false).unwrap_err(),
+            error!(AmmError::PriceImpactTooHigh)
+        );
+
+        // Zero disables the check
+        pool.config.max_price_impact_bps = 0;
+        pool.check_price_impact(&past, true).unwrap();
+        pool.check_price_impact(&past_sell, false).unwrap();
+
+        println!("✅ Price impact limit test passed!");
+    }
+
+    #[test]
+    fn test_price_impact_guard_set_by_update() {
+        // ARRANGE: A launched pool and a 3 SOL buy, uncapped by default
+        let mut pool = create_test_pool();
+        let buy = pool.swap_amounts(3_000_000_000, 0, true).unwrap();
+        pool.check_price_impact(&buy, true).unwrap();
+
+        println!("🧪 Testing the price impact guard switched on after launch");
+
+        // ACT: The admin caps a swap's price move at 1%
+        let mut guards = pool.guards();
+        guards.max_price_impact_bps = 100;
+        pool.update_guards(guards).unwrap();
+
+        // ASSERT: The same buy is now refused
+        assert_eq!(pool.config.max_price_impact_bps, 100);
+        assert_eq!(
+            pool.check_price_impact(&buy, true).unwrap_err(),
+            error!(AmmError::PriceImpactTooHigh)
+        );
+
+        // A cap above 100% is refused and leaves the guard as it was
+        guards.max_price_impact_bps = 10_001;
+        assert_eq!(
+            pool.update_guards(guards).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(pool.config.max_price_impact_bps, 100);
+
+        println!("✅ Updated price impact guard test passed!");
     }
 
-    Ok(())
-}
+    #[test]
+    fn test_preview_rejects_untradeable_pool() {
+        // ARRANGE
+        let mut pool = create_test_pool();
+        pool.check_tradeable().unwrap();
 
-fn compute_scale(num_: u128) -> u64 {
-    let mut num = num_;
+        println!("🧪 Testing previews against locked pools");
 
-    return if num == 0 {
-        1
-    } else {
-        let mut scale = 1;
+        // ACT & ASSERT: A locked pool can't be quoted
+        pool.locked = true;
+        assert_eq!(
+            pool.check_tradeable().unwrap_err(),
+            error!(AmmError::PoolIsLocked)
+        );
 
-        while num >= 10 {
-            num = num / 10;
-            scale += 1;
-        }
+        // Migration keeps the lock but opens the residual reserves
+        pool.pool_migration = true;
+        pool.check_tradeable().unwrap();
 
-        scale
-    };
-}
+        println!("✅ Untradeable pool preview test passed!");
+    }
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_s calculation
-fn delta_s_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_b: u128,
-    delta_m: u128,
-) -> Option<u128> {
-    let alpha_abs = U256::from(alpha_abs);
-    let beta = U256::from(beta);
-    let alpha_decimals = U256::from(alpha_decimals);
-    let beta_decimals = U256::from(beta_decimals);
-    let s_b = U256::from(s_b);
-    let delta_m = U256::from(delta_m);
-    let decimals_s = U256::from(DECIMALS_S);
+    #[test]
+    fn test_reserve_invariant_catches_fee_desync() {
+        // ARRANGE: Vaults holding exactly what a buy left on the books
+        let mut pool = create_test_pool();
+        let buy = pool.swap_amounts(10_000_000_000, 0, true).unwrap();
+        let mut quote_vault = pool.quote_reserve.tokens + buy.amount_in + buy.admin_fee_in;
+        let meme_vault = pool.meme_reserve.tokens - buy.amount_out;
+        pool.accrue_quote_fees(buy.admin_fee_in).unwrap();
+        pool.admin_fees_meme += buy.admin_fee_out;
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+
+        println!("🧪 Testing the vault reserve invariant");
+
+        // ACT & ASSERT: Consistent books pass, a donation doesn't break them
+        pool.check_reserve_invariant(meme_vault, quote_vault).unwrap();
+        quote_vault += 1;
+        pool.check_reserve_invariant(meme_vault, quote_vault).unwrap();
+
+        // Fees booked twice claim more than the vault holds
+        pool.admin_fees_quote += buy.admin_fee_in + 1;
+        assert_eq!(
+            pool.check_reserve_invariant(meme_vault, quote_vault).unwrap_err(),
+            error!(AmmError::ReserveInvariantViolated)
+        );
 
-    // For positive slope: price = +alpha_abs * supply + beta
-    // The u term now ADDS beta
-    let u = U256::from(2)
-        .checked_mul(alpha_abs)
-        .checked_mul(s_b)
-        .checked_mul(beta_decimals)
-        .checked_add_(
-            U256::from(2)
-                .checked_mul(beta)
-                .checked_mul(alpha_decimals)
-                .checked_mul(decimals_s),
-        )?;
+        // Likewise a meme fee never left in the vault
+        pool.admin_fees_quote -= buy.admin_fee_in + 1;
+        pool.admin_fees_meme += 1;
+        assert_eq!(
+            pool.check_reserve_invariant(meme_vault, quote_vault).unwrap_err(),
+            error!(AmmError::ReserveInvariantViolated)
+        );
 
-    let v = alpha_decimals
-        .checked_mul(beta_decimals)
-        .checked_mul(decimals_s)?;
+        println!("✅ Reserve invariant test passed!");
+    }
 
-    let w = U256::from(8).checked_mul(delta_m).checked_mul(alpha_abs)?;
+    #[test]
+    fn test_upgrade_v1_pool_account() {
+        // ARRANGE: A pool midway through its curve, written in the v1 layout
+        let pool = create_test_pool();
+        let v1 = BoundPoolV1 {
+            meme_reserve: pool.meme_reserve,
+            quote_reserve: pool.quote_reserve,
+            admin_fees_meme: 7,
+            admin_fees_quote: 11,
+            fee_vault_quote: pool.fee_vault_quote,
+            creator_addr: pool.creator_addr,
+            fees: pool.fees,
+            config: ConfigV1 {
+                alpha_abs: pool.config.alpha_abs,
+                beta: pool.config.beta,
+                price_factor_num: pool.config.price_factor_num,
+                price_factor_denom: pool.config.price_factor_denom,
+                gamma_s: pool.config.gamma_s,
+                gamma_m: pool.config.gamma_m,
+                omega_m: pool.config.omega_m,
+                decimals: pool.config.decimals,
+            },
+            airdropped_tokens: 13,
+            locked: false,
+            pool_migration: false,
+            pool_key: Pubkey::default(),
+        };
+        let mut data = BoundPool::DISCRIMINATOR.to_vec();
+        v1.serialize(&mut data).unwrap();
+
+        println!("🧪 Testing the v1 to v2 pool account upgrade");
+
+        // ACT
+        let upgraded = BoundPool::upgrade_v1(&data).unwrap();
+
+        // ASSERT: The v1 account size, reserves, fees and curve carry over
+        assert_eq!(data.len(), BoundPool::V1_SPACE);
+        assert_eq!(BoundPool::V1_SPACE, 402);
+        assert_eq!(upgraded.version, BoundPool::VERSION);
+        assert_eq!(upgraded.meme_reserve, pool.meme_reserve);
+        assert_eq!(upgraded.quote_reserve, pool.quote_reserve);
+        assert_eq!(upgraded.admin_fees_meme, 7);
+        assert_eq!(upgraded.admin_fees_quote, 11);
+        assert_eq!(upgraded.airdropped_tokens, 13);
+        assert_eq!(upgraded.fees, pool.fees);
+        assert_eq!(upgraded.config.gamma_m, pool.config.gamma_m);
+        assert_eq!(upgraded.config.decimals, pool.config.decimals);
+
+        // The fields v1 didn't have keep a v1 pool's behaviour
+        assert_eq!(upgraded.config.creator_fee_bps, 0);
+        assert_eq!(upgraded.config.graduation_mode, GraduationMode::TokenTarget);
+        assert_eq!(upgraded.guards(), PoolGuards::default());
+        assert!(upgraded.first_buy_done);
+        assert_eq!(
+            upgraded.swap_amounts(1_000_000_000, 0, true).unwrap().amount_out,
+            pool.swap_amounts(1_000_000_000, 0, true).unwrap().amount_out
+        );
 
-    let a = compute_a_positive(u, alpha_decimals, w, v, U256::from(1))?;
+        // The old buffer doesn't load as a current pool
+        assert!(BoundPool::try_deserialize(&mut &data[..]).is_err());
 
-    let b = v
-        .checked_pow(U256::from(2))
-        .checked_mul(alpha_decimals)
-        .sqrt()?;
+        // Current accounts and foreign data aren't upgraded
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        assert_eq!(
+            BoundPool::upgrade_v1(&current).err().unwrap(),
+            error!(AmmError::UnsupportedPoolVersion)
+        );
+        let mut foreign = data.clone();
+        foreign[0] ^= 0xff;
+        assert_eq!(
+            BoundPool::upgrade_v1(&foreign).err().unwrap(),
+            error!(AmmError::UnsupportedPoolVersion)
+        );
 
-    let num_1 = vec![decimals_s, alpha_decimals, a, v];
-    let num_2 = vec![decimals_s, alpha_decimals, u, b];
-    let denom_ = vec![U256::from(2), alpha_abs, b, v];
+        println!("✅ Pool account upgrade test passed!");
+    }
 
-    let left = multiply_divide(num_1, denom_.clone());
-    let right = multiply_divide(num_2, denom_);
+    #[test]
+    fn test_is_tradeable_flag_table() {
+        // ARRANGE: Every combination of the flags gating trades
+        let cases = [
+            // (locked, pool_migration, tradeable)
+            (false, false, true),
+            // Locked awaiting migration
+            (true, false, false),
+            // Migrated pools trade what they left on the curve
+            (false, true, true),
+            (true, true, true),
+        ];
+
+        println!("🧪 Testing is_tradeable over all flag combinations");
+
+        for (locked, pool_migration, tradeable) in cases {
+            let mut pool = create_test_pool();
+            pool.locked = locked;
+            pool.pool_migration = pool_migration;
+
+            // ACT & ASSERT: The predicate and the check agree
+            assert_eq!(pool.is_tradeable(), tradeable, "{:?}", (locked, pool_migration));
+            assert_eq!(pool.check_tradeable().is_ok(), tradeable);
+        }
 
-    // We subtract here because of the quadratic formula structure
-    left.checked_sub_(right).map(|value| value.as_u128())
-}
+        println!("✅ is_tradeable flag table test passed!");
+    }
 
-fn compute_a_positive(
-    u: U256,
-    alpha_decimals: U256,
-    w: U256,
-    v: U256,
-    scale: U256,
-) -> Option<U256> {
-    let left = u
-        .checked_div(scale)
-        .checked_mul(u)
-        .checked_mul(alpha_decimals);
-
-    let right = v.checked_div(scale).checked_mul(v).checked_mul(w);
-
-    let result = left
-        .checked_add_(right)
-        .sqrt()
-        .checked_mul(scale.integer_sqrt());
-
-    match result {
-        Some(value) => Some(value),
-        None => compute_a_positive(
-            u,
-            alpha_decimals,
-            w,
-            v,
-            scale.checked_mul(U256::from(100)).unwrap(),
-        ),
+    #[test]
+    fn test_only_creator_passes_creator_gate() {
+        // ARRANGE: A pool created by a creator distinct from the payer
+        let mut pool = create_test_pool();
+        let (creator, payer) = (Pubkey::new_unique(), Pubkey::new_unique());
+        pool.creator_addr = creator;
+
+        println!("🧪 Testing the creator gate");
+
+        // ACT & ASSERT: Only the recorded creator gets through
+        assert!(pool.is_creator(&creator));
+        assert!(!pool.is_creator(&payer));
+        assert!(!pool.is_creator(&Pubkey::default()));
+
+        println!("✅ Creator gate test passed!");
     }
-}
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 1)
-fn delta_m1_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_a: u128,
-    s_b: u128,
-) -> Option<u128> {
-    // For positive intercept, we ADD the beta term
-    let left_num = s_b.checked_sub(s_a)?.checked_mul(beta)?;
-    let left_denom = beta_decimals.checked_mul(DECIMALS_S)?;
-    let left = Some(left_num).checked_div_(Some(left_denom))?;
+    #[test]
+    fn test_creator_handoff() {
+        // ARRANGE
+        let mut pool = create_test_pool();
+        let (creator, next_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        pool.creator_addr = creator;
 
-    let s_b_squared = s_b.checked_pow(2)?;
-    let s_a_squared = s_a.checked_pow(2)?;
-    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
-    let decimals_s_squared = DECIMALS_S.checked_pow(2)?;
+        println!("🧪 Testing the two-step creator handoff");
 
-    let right = power_diff
-        .checked_mul(alpha_abs)
-        .checked_div(decimals_s_squared)?
-        .checked_div(2 * alpha_decimals)?;
+        // ACT: Propose, then accept from the proposed wallet
+        pool.propose_creator(next_creator);
 
-    // For positive slope with positive intercept, we ADD beta term
-    Some(left).checked_add_(Some(right))
-}
+        // Proposing alone doesn't move the creator gate
+        assert!(pool.is_creator(&creator));
+        assert!(!pool.is_creator(&next_creator));
 
-/// CHANGED: Updated for positive slope with POSITIVE intercept delta_m calculation (method 2)
-fn delta_m2_positive_strategy(
-    alpha_abs: u128,
-    beta: u128,
-    alpha_decimals: u128,
-    beta_decimals: u128,
-    s_a: u128,
-    s_b: u128,
-) -> Option<u128> {
-    // For positive slope: price = +alpha_abs * supply + beta
-    // We ADD beta term
-    let left = (beta * 2)
-        .checked_mul(DECIMALS_S)
-        .checked_mul(alpha_decimals)
-        .checked_mul(s_b - s_a)?;
+        pool.accept_creator(&next_creator).unwrap();
 
-    let s_b_squared = s_b.checked_pow(2)?;
-    let s_a_squared = s_a.checked_pow(2)?;
-    let power_diff = s_b_squared.checked_sub(s_a_squared)?;
+        // ASSERT
+        assert!(pool.is_creator(&next_creator));
+        assert!(!pool.is_creator(&creator));
+        assert_eq!(pool.pending_creator, Pubkey::default());
 
-    let right = alpha_abs
-        .checked_mul(beta_decimals)
-        .checked_mul(power_diff)?;
+        println!("✅ Creator handoff test passed!");
+    }
 
-    let denom = (2 * alpha_decimals)
-        .checked_mul(beta_decimals)
-        .checked_mul(DECIMALS_S.checked_pow(2)?)?;
+    #[test]
+    fn test_creator_handoff_rejects_non_pending_signer() {
+        // ARRANGE
+        let mut pool = create_test_pool();
+        let (creator, next_creator) = (Pubkey::new_unique(), Pubkey::new_unique());
+        pool.creator_addr = creator;
 
-    // For positive slope with positive intercept, we ADD both terms
-    left.checked_add(right)?.checked_div(denom)
-}
+        // ACT & ASSERT: Nothing to accept before a proposal
+        assert_eq!(
+            pool.accept_creator(&next_creator).unwrap_err(),
+            error!(AmmError::NotPendingCreator)
+        );
 
-#[cfg(test)]
-mod tests {
-    use super::Reserve;
-    use super::*;
-    use crate::models::fees::FEE;
+        // Neither the current creator nor a stranger can accept for the pending one
+        pool.propose_creator(next_creator);
+        for signer in [creator, Pubkey::new_unique()] {
+            assert_eq!(
+                pool.accept_creator(&signer).unwrap_err(),
+                error!(AmmError::NotPendingCreator)
+            );
+        }
+        assert!(pool.is_creator(&creator));
 
-    // Helper function to create a test pool configuration
-    fn create_test_config() -> Config {
-        Config {
-            alpha_abs: 1_000_000,       // Smaller alpha for gentler slope
-            beta: 1_000_000_000,        // 1.0 with 9 decimals (positive intercept)
-            price_factor_num: 1,        // Simple 1:1 ratio
-            price_factor_denom: 10, // This gives omega_m * 1/10 = 300, clearly satisfying 2*gamma_m > omega_m*price_factor (6000 > 300)
-            gamma_s: 1_000_000_000_000, // 1000 SOL
-            gamma_m: 3_000_000_000_000, // 3000 tokens (increased to satisfy constraint better)
-            omega_m: 3_000_000_000_000, // 3000 tokens (same as gamma_m)
-            decimals: Decimals {
-                alpha: 1_000_000,     // 6 decimals for alpha
-                beta: 1_000_000_000,  // 9 decimals for beta
-                quote: 1_000_000_000, // 9 decimals (SOL)
-            },
+        // A withdrawn proposal can't be accepted
+        pool.propose_creator(Pubkey::default());
+        assert_eq!(
+            pool.accept_creator(&next_creator).unwrap_err(),
+            error!(AmmError::NotPendingCreator)
+        );
+        assert!(pool.is_creator(&creator));
+
+        println!("✅ Non-pending creator rejection test passed!");
+    }
+
+    #[test]
+    fn test_price_decimals_follow_quote_decimals() {
+        let om = crate::consts::DEFAULT_MAX_M_LP;
+
+        // SOL: 9 decimals of DECIMALS_S scaling plus 9 quote decimals
+        let sol_pool = create_curve_pool(85_000_000_000, 1_000_000_000, om, 3, 1);
+        assert_eq!(sol_pool.price_decimals(), 18);
+
+        // USDC-like quote with 6 decimals
+        let usdc_pool = create_curve_pool(85_000_000_000, 1_000_000, om, 3, 1);
+        assert_eq!(usdc_pool.price_decimals(), 15);
+
+        // Rescaling by it gives the same whole-token price on both pools
+        for pool in [&sol_pool, &usdc_pool] {
+            let scale = 10u128.pow(pool.price_decimals() as u32);
+            let quote_unit = pool.config.decimals.quote as u128;
+            assert_eq!(scale, DECIMALS_S * quote_unit);
         }
     }
 
-    // Helper function to create a test pool
-    fn create_test_pool() -> BoundPool {
-        BoundPool {
-            meme_reserve: Reserve {
-                tokens: 500_000_000_000, // 500 tokens
-                mint: Pubkey::default(),
-                vault: Pubkey::default(),
-            },
-            quote_reserve: Reserve {
-                tokens: 250_000_000_000, // 250 SOL
-                mint: Pubkey::default(),
-                vault: Pubkey::default(),
-            },
-            admin_fees_meme: 0,
-            admin_fees_quote: 0,
-            fee_vault_quote: Pubkey::default(),
-            creator_addr: Pubkey::default(),
-            fees: Fees {
-                fee_meme_percent: 0,
-                fee_quote_percent: FEE, // 1%
-            },
-            config: create_test_config(),
-            airdropped_tokens: 0,
-            locked: false,
-            pool_migration: false,
-            pool_key: Pubkey::default(),
+    #[test]
+    fn test_decimals_validation() {
+        let valid = Decimals {
+            alpha: 1_000_000,
+            beta: 1_000_000,
+            quote: 1_000_000_000,
+        };
+        valid.validate().unwrap();
+
+        // Non powers of ten
+        for decimals in [
+            Decimals { alpha: 2_000_000, ..valid },
+            Decimals { beta: 999_999, ..valid },
+            Decimals { quote: 1_000_000_001, ..valid },
+            Decimals { alpha: 0, ..valid },
+        ] {
+            assert_eq!(
+                decimals.validate().unwrap_err(),
+                error!(AmmError::InvalidDecimalsConfig)
+            );
+        }
+
+        // Powers of ten beyond what the curve math supports
+        for decimals in [
+            Decimals { alpha: MAX_CURVE_DECIMALS * 10, ..valid },
+            Decimals { beta: MAX_CURVE_DECIMALS * 10, ..valid },
+            Decimals { quote: 10u64.pow(MAX_QUOTE_DECIMALS as u32 + 1), ..valid },
+        ] {
+            assert_eq!(
+                decimals.validate().unwrap_err(),
+                error!(AmmError::InvalidDecimalsConfig)
+            );
         }
+
+        // Bounds themselves are fine
+        Decimals {
+            alpha: MAX_CURVE_DECIMALS,
+            beta: 1,
+            quote: 10u64.pow(MAX_QUOTE_DECIMALS as u32),
+        }
+        .validate()
+        .unwrap();
+
+        println!("✅ Decimals validation test passed!");
     }
 
     #[test]
-    fn test_compute_delta_m_basic() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let s_a = 100_000_000_000; // 100 SOL
-        let s_b = 200_000_000_000; // 200 SOL
+    fn test_curve_consistency_check() {
+        // ARRANGE: Curves derived the way new_pool does
+        let om = crate::consts::DEFAULT_MAX_M_LP;
+        let sol_pool = create_curve_pool(85_000_000_000, 1_000_000_000, om, 3, 1);
+        let small_pool = create_curve_pool(5_000_000_000, 1_000_000_000, om, 3, 1);
 
-        println!("🧪 Testing compute_delta_m with:");
-        println!("   s_a (start): {} SOL", s_a / 1_000_000_000);
-        println!("   s_b (end): {} SOL", s_b / 1_000_000_000);
+        println!("🧪 Testing the curve parameter self-check");
 
-        // ACT: Calculate delta_m
-        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+        // ACT & ASSERT: Consistent parameters pass
+        sol_pool.check_curve_consistency().unwrap();
+        small_pool.check_curve_consistency().unwrap();
 
-        // ASSERT: Check result is reasonable
-        assert!(delta_m > 0, "Delta_m should be positive");
-        assert!(
-            delta_m < pool.config.gamma_m,
-            "Delta_m should be less than max meme tokens"
+        // A slope that doesn't belong to the target is caught
+        let mut pool = sol_pool.clone();
+        pool.config.alpha_abs *= 2;
+        assert_eq!(
+            pool.check_curve_consistency().unwrap_err(),
+            error!(AmmError::CurveParameterMismatch)
+        );
+
+        // So is a target swapped after the curve was derived
+        let mut pool = sol_pool.clone();
+        pool.config.gamma_s /= 2;
+        assert_eq!(
+            pool.check_curve_consistency().unwrap_err(),
+            error!(AmmError::CurveParameterMismatch)
+        );
+
+        println!("✅ Curve consistency check test passed!");
+    }
+
+    #[test]
+    fn test_assert_signer_pda() {
+        // ARRANGE
+        let pool_key = Pubkey::new_unique();
+        let (signer, _) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, pool_key.as_ref()],
+            &crate::ID,
+        );
+
+        // ACT & ASSERT: The real PDA passes
+        BoundPool::assert_signer_pda(&crate::ID, &pool_key, &signer).unwrap();
+
+        // The signer of another pool is rejected
+        let (other, _) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, Pubkey::new_unique().as_ref()],
+            &crate::ID,
+        );
+        assert_eq!(
+            BoundPool::assert_signer_pda(&crate::ID, &pool_key, &other).unwrap_err(),
+            error!(AmmError::InvalidPoolSigner)
+        );
+
+        // So is the same seed set derived under another program
+        let (lookalike, _) = Pubkey::find_program_address(
+            &[BoundPool::SIGNER_PDA_PREFIX, pool_key.as_ref()],
+            &Pubkey::new_unique(),
+        );
+        assert_eq!(
+            BoundPool::assert_signer_pda(&crate::ID, &pool_key, &lookalike).unwrap_err(),
+            error!(AmmError::InvalidPoolSigner)
         );
 
-        println!(
-            "✅ Test passed! Delta_m = {} tokens",
-            delta_m / 1_000_000_000
-        );
+        println!("✅ Signer PDA assertion test passed!");
     }
 
     #[test]
-    fn test_compute_delta_s_basic() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let s_b = 100_000_000_000; // 100 SOL
-        let delta_m = 1_000_000_000; // 1 token to sell (much smaller amount)
+    fn test_two_percent_meme_fee() {
+        // ARRANGE: A 2% meme fee on top of the 1% quote fee
+        let mut pool = create_curve_pool(
+            85_000_000_000,
+            1_000_000_000,
+            crate::consts::DEFAULT_MAX_M_LP,
+            3,
+            1,
+        );
+        pool.fees.fee_meme_percent = 20_000_000;
+        pool.fees.check_meme_fee().unwrap();
+        let fee_of = |amount: u64| (amount * 2).div_ceil(100);
+
+        println!("🧪 Testing a 2% meme fee on buys and sells");
+
+        // ACT: Buy, then settle the way swap_y does
+        let (_, s_b) = pool.balances();
+        let buy = pool.swap_amounts(10_000_000_000, 0, true).unwrap();
+        let delta_m = pool.compute_delta_m(s_b, s_b + buy.amount_in).unwrap();
+        pool.admin_fees_quote += buy.admin_fee_in;
+        pool.admin_fees_meme += buy.admin_fee_out;
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+
+        // ASSERT: 2% of the tokens bought is kept as a meme fee
+        assert_eq!(buy.admin_fee_out, fee_of(delta_m));
+        assert_eq!(buy.amount_out + buy.admin_fee_out, delta_m);
+        assert_eq!(pool.admin_fees_meme, buy.admin_fee_out);
+
+        // ACT: Sell half the tokens back, the meme fee is doubled on sells
+        let sell_amount = buy.amount_out / 2;
+        let sell = pool.swap_amounts(sell_amount, 0, false).unwrap();
+        pool.admin_fees_meme += sell.admin_fee_in;
+        pool.admin_fees_quote += sell.admin_fee_out;
+        pool.meme_reserve.tokens += sell.amount_in;
+        pool.quote_reserve.tokens -= sell.amount_out + sell.admin_fee_out;
+
+        // ASSERT: 4% of the tokens sold is kept, the rest returns to the curve
+        assert_eq!(sell.admin_fee_in, fee_of(sell_amount) * 2);
+        assert_eq!(sell.amount_in + sell.admin_fee_in, sell_amount);
+        assert_eq!(pool.admin_fees_meme, buy.admin_fee_out + sell.admin_fee_in);
+        assert!(sell.amount_out > 0);
+
+        println!("✅ 2% meme fee test passed!");
+    }
 
-        println!("🧪 Testing compute_delta_s with:");
-        println!("   s_b (current): {} SOL", s_b / 1_000_000_000);
-        println!("   delta_m: {} tokens", delta_m / 1_000_000_000);
+    #[test]
+    fn test_meme_fee_cap() {
+        // ARRANGE
+        let mut pool = create_test_pool();
+        pool.fees.fee_meme_percent = MAX_MEME_FEE;
 
-        // ACT: Calculate delta_s
-        let delta_s = pool.compute_delta_s(s_b, delta_m).unwrap();
+        // ACT & ASSERT: 5% is the ceiling
+        pool.fees.check_meme_fee().unwrap();
+        assert_eq!(MAX_MEME_FEE * 20, FEE_PRECISION);
 
-        // ASSERT: Check result is reasonable
-        assert!(delta_s > 0, "Delta_s should be positive");
-        // Use a more reasonable upper bound check
-        assert!(
-            delta_s < 100_000_000_000_000, // 100k SOL as reasonable upper bound
-            "Delta_s should be reasonable: got {} SOL",
-            delta_s / 1_000_000_000
+        pool.fees.fee_meme_percent = MAX_MEME_FEE + 1;
+        assert_eq!(
+            pool.fees.check_meme_fee().unwrap_err(),
+            error!(AmmError::FeeTooHigh)
         );
 
-        println!("✅ Test passed! Delta_s = {} SOL", delta_s / 1_000_000_000);
+        println!("✅ Meme fee cap test passed!");
     }
 
     #[test]
-    fn test_positive_intercept_at_zero_supply() {
-        // ARRANGE: Test that price = beta when supply = 0
-        let pool = create_test_pool();
-        let s_a = 0; // Start from 0 supply
-        let s_b = 1_000_000_000; // Small amount of SOL
+    fn test_virtual_reserves_shift_spot_price() {
+        // ARRANGE: The same curve, once plain and once opening at 20 virtual SOL
+        let om = crate::consts::DEFAULT_MAX_M_LP;
+        let plain = create_curve_pool(85_000_000_000, 1_000_000_000, om, 3, 1);
+        let virtual_sol = 20_000_000_000;
+
+        let mut pool = plain.clone();
+        pool.config.virtual_sol_reserves = virtual_sol;
+        pool.config.virtual_meme_reserves = plain.compute_delta_m(0, virtual_sol).unwrap();
+
+        println!("🧪 Testing virtual reserves on the opening price");
+
+        // ACT
+        let price = pool.spot_price().unwrap();
+
+        // ASSERT: The pool opens where the plain curve is after 20 SOL
+        pool.check_virtual_reserves().unwrap();
+        assert_eq!(price, plain.spot_price_at(virtual_sol).unwrap());
+        assert_ne!(price, plain.spot_price().unwrap());
+
+        // A buy gets what the plain curve gives at that supply
+        let mut moved = plain.clone();
+        moved.quote_reserve.tokens = virtual_sol;
+        moved.meme_reserve.tokens -= pool.config.virtual_meme_reserves;
+        let buy = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        let moved_buy = moved.swap_amounts(1_000_000_000, 0, true).unwrap();
+        assert_eq!(buy.amount_out, moved_buy.amount_out);
+        assert_eq!(buy.amount_in, moved_buy.amount_in);
+
+        // Selling it all back can't reach into the virtual SOL
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        let sell = pool.swap_amounts(u64::MAX / 2, 0, false).unwrap();
+        assert!(sell.amount_out + sell.admin_fee_out <= pool.quote_reserve.tokens);
+
+        println!("✅ Virtual reserves spot price test passed!");
+    }
 
-        println!("🧪 Testing positive intercept (beta) at zero supply");
+    #[test]
+    fn test_dust_buy_rejected_cleanly() {
+        // ARRANGE: 1 lamport, which a 1% fee rounded up swallows whole
+        let pool = create_test_pool();
 
-        // ACT: Calculate delta_m
-        let delta_m = pool.compute_delta_m(s_a, s_b).unwrap();
+        println!("🧪 Testing a buy of a single lamport");
 
-        // The price at zero should be beta, so initial tokens should be higher
-        // because we're starting from a positive price
-        assert!(
-            delta_m > 0,
-            "Should get tokens even at zero supply due to positive intercept"
+        // ACT & ASSERT: An error instead of a buy of nothing
+        assert_eq!(get_fee_amount(1, pool.fees.fee_quote_percent).unwrap(), 1);
+        assert_eq!(
+            pool.swap_amounts(1, 0, true).unwrap_err(),
+            error!(AmmError::NoZeroTokens)
         );
 
-        println!(
-            "✅ Positive intercept confirmed! Got {} tokens for {} SOL at zero supply",
-            delta_m / 1_000_000_000,
-            s_b / 1_000_000_000
-        );
+        // The smallest amount the fee doesn't swallow still trades
+        assert!(pool.swap_amounts(2, 0, true).is_ok());
+
+        println!("✅ Dust buy test passed!");
     }
 
     #[test]
-    fn test_buy_meme_swap_amounts() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let sol_amount = 10_000_000_000; // 10 SOL to buy with
-        let min_meme_out = 0; // No slippage protection for test
-
-        println!(
-            "🧪 Testing buy_meme_swap_amounts with {} SOL",
-            sol_amount / 1_000_000_000
-        );
+    fn test_minimum_trade_size() {
+        // ARRANGE: 0.01 SOL minimum buy, 1000 token minimum sell
+        let mut pool = create_test_pool();
+        pool.config.min_quote_in = 10_000_000;
+        pool.config.min_meme_in = 1_000_000_000;
 
-        // ACT: Calculate swap amounts
-        let swap = pool
-            .buy_meme_swap_amounts(sol_amount, min_meme_out)
-            .unwrap();
+        println!("🧪 Testing the minimum trade sizes");
 
-        // ASSERT: Check all values are correct
-        assert!(swap.amount_out > 0, "Should receive meme tokens");
+        // ACT & ASSERT: One below the minimum is dust
         assert_eq!(
-            swap.admin_fee_in,
-            sol_amount / 100, // 1% fee
-            "Admin fee should be 1% of input"
+            pool.check_min_trade(9_999_999, true).unwrap_err(),
+            error!(AmmError::BelowMinimumTrade)
         );
-        assert!(
-            swap.amount_in <= sol_amount - swap.admin_fee_in,
-            "Net amount in should be less than input minus fees"
+        assert_eq!(
+            pool.check_min_trade(999_999_999, false).unwrap_err(),
+            error!(AmmError::BelowMinimumTrade)
         );
 
-        println!("✅ Buy swap test passed!");
-        println!("   Input: {} SOL", sol_amount / 1_000_000_000);
-        println!("   Output: {} MEME", swap.amount_out / 1_000_000_000);
-        println!("   Fee: {} SOL", swap.admin_fee_in / 1_000_000_000);
+        // Exactly the minimum goes through
+        pool.check_min_trade(10_000_000, true).unwrap();
+        pool.check_min_trade(1_000_000_000, false).unwrap();
+
+        // Zero disables both
+        pool.config.min_quote_in = 0;
+        pool.config.min_meme_in = 0;
+        pool.check_min_trade(1, true).unwrap();
+        pool.check_min_trade(1, false).unwrap();
+
+        println!("✅ Minimum trade size test passed!");
     }
 
     #[test]
-    fn test_sell_meme_swap_amounts() {
-        // ARRANGE: Set up test data
-        let pool = create_test_pool();
-        let meme_amount = 10_000_000_000; // 10 MEME to sell
-        let min_sol_out = 0; // No slippage protection for test
+    fn test_minimum_trade_size_set_by_update() {
+        // ARRANGE: A launched pool takes any trade size by default
+        let mut pool = create_test_pool();
+        pool.check_min_trade(1, true).unwrap();
+        pool.check_min_trade(1, false).unwrap();
 
-        println!(
-            "🧪 Testing sell_meme_swap_amounts with {} MEME",
-            meme_amount / 1_000_000_000
-        );
+        println!("🧪 Testing the minimum trade sizes switched on after launch");
 
-        // ACT: Calculate swap amounts
-        let swap = pool
-            .sell_meme_swap_amounts(meme_amount, min_sol_out)
-            .unwrap();
+        // ACT: The admin sets a 0.01 SOL buy and 1000 token sell floor
+        let mut guards = pool.guards();
+        guards.min_quote_in = 10_000_000;
+        guards.min_meme_in = 1_000_000_000;
+        let previous = pool.update_guards(guards).unwrap();
 
-        // ASSERT: Check all values are correct
-        assert!(swap.amount_out > 0, "Should receive SOL");
+        // ASSERT: Dust is now refused both ways, the floors themselves trade
+        assert_eq!(previous.min_quote_in, 0);
+        assert_eq!(previous.min_meme_in, 0);
         assert_eq!(
-            swap.admin_fee_in,
-            0, // 0% fee on meme as per MEME_FEE constant
-            "Admin fee on meme should be 0%"
+            pool.check_min_trade(9_999_999, true).unwrap_err(),
+            error!(AmmError::BelowMinimumTrade)
+        );
+        assert_eq!(
+            pool.check_min_trade(999_999_999, false).unwrap_err(),
+            error!(AmmError::BelowMinimumTrade)
         );
+        pool.check_min_trade(10_000_000, true).unwrap();
+        pool.check_min_trade(1_000_000_000, false).unwrap();
 
-        println!("✅ Sell swap test passed!");
-        println!("   Input: {} MEME", meme_amount / 1_000_000_000);
-        println!("   Output: {} SOL", swap.amount_out / 1_000_000_000);
-        println!("   Fee: {} SOL", swap.admin_fee_out / 1_000_000_000);
+        println!("✅ Updated minimum trade size test passed!");
     }
 
     #[test]
-    fn test_round_trip_swap() {
-        // ARRANGE: Buy then sell to test round trip
-        let mut pool = create_test_pool();
-        let initial_sol = 100_000_000_000; // 100 SOL
-
-        println!("🧪 Testing round trip swap (buy then sell)");
+    fn test_fee_exempt_buy() {
+        // ARRANGE: Same pool, same 10 SOL buy
+        let pool = create_test_pool();
+        let amount = 10_000_000_000;
 
-        // ACT: First buy meme with SOL
-        let buy_swap = pool.buy_meme_swap_amounts(initial_sol, 0).unwrap();
+        println!("🧪 Testing an exempt against a regular buy");
 
-        // Update pool reserves (simulate the buy)
-        pool.quote_reserve.tokens += buy_swap.amount_in;
-        pool.meme_reserve.tokens -= buy_swap.amount_out;
+        // ACT
+        let regular = pool.swap_amounts(amount, 0, true).unwrap();
+        let exempt = pool.without_fees().swap_amounts(amount, 0, true).unwrap();
 
-        // Now sell the meme back
-        let sell_swap = pool.sell_meme_swap_amounts(buy_swap.amount_out, 0).unwrap();
+        // ASSERT: The exempt buy pays no fees and gets more for the same SOL
+        assert!(regular.admin_fee_in > 0);
+        assert_eq!(exempt.admin_fee_in, 0);
+        assert_eq!(exempt.admin_fee_out, 0);
+        assert_eq!(exempt.amount_in, amount);
+        assert!(exempt.amount_out > regular.amount_out);
+        assert_eq!(regular.amount_in + regular.admin_fee_in, amount);
 
-        // ASSERT: We should get less SOL back due to fees
-        assert!(
-            sell_swap.amount_out < initial_sol,
-            "Should get less SOL back due to fees"
-        );
+        // The stored pool keeps charging everybody else
+        assert_eq!(pool.fees.fee_quote_percent, FEE);
 
-        let total_fees = initial_sol - sell_swap.amount_out;
-        println!("✅ Round trip test passed!");
-        println!("   Started with: {} SOL", initial_sol / 1_000_000_000);
-        println!(
-            "   Ended with: {} SOL",
-            sell_swap.amount_out / 1_000_000_000
-        );
-        println!("   Total fees: {} SOL", total_fees / 1_000_000_000);
+        println!("✅ Fee exempt buy test passed!");
     }
 
     #[test]
-    fn test_price_increases_with_supply() {
-        // ARRANGE: Test that price increases as supply increases (positive slope)
-        let pool = create_test_pool();
+    fn test_twap_accumulates_across_slots() {
+        // ARRANGE: Launched at slot 100
+        let mut pool = create_test_pool();
+        pool.last_twap_slot = 100;
+        let start_cumulative = pool.price_cumulative;
+        let first_price = pool.spot_price().unwrap() as u128;
 
-        // Use more dramatic supply differences to see the effect
-        let supply1 = 10_000_000_000; // 10 SOL (low supply)
-        let supply2 = 500_000_000_000; // 500 SOL (high supply)
-        let delta = 1_000_000_000; // 1 SOL increment (smaller to be more precise)
+        println!("🧪 Testing the TWAP accumulator");
 
-        println!("🧪 Testing that price increases with supply (positive slope)");
+        // ACT: Hold the first price for 10 slots, then buy and hold for 30
+        pool.update_twap(110).unwrap();
+        let buy = pool.swap_amounts(10_000_000_000, 0, true).unwrap();
+        pool.quote_reserve.tokens += buy.amount_in;
+        pool.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        let second_price = pool.spot_price().unwrap() as u128;
+        pool.update_twap(140).unwrap();
 
-        // ACT: Get tokens at lower supply
-        let tokens_at_low_supply = pool.compute_delta_m(supply1, supply1 + delta).unwrap();
+        // ASSERT: Each price is weighted by the slots it held
+        assert_eq!(
+            pool.price_cumulative,
+            start_cumulative + first_price * 10 + second_price * 30
+        );
+        let twap = pool.read_twap(start_cumulative, 100, 140).unwrap() as u128;
+        assert_eq!(twap, (first_price * 10 + second_price * 30) / 40);
 
-        // Get tokens at higher supply
-        let tokens_at_high_supply = pool.compute_delta_m(supply2, supply2 + delta).unwrap();
+        // Reads between trades extrapolate the current price
+        let twap = pool.read_twap(pool.price_cumulative, 140, 150).unwrap() as u128;
+        assert_eq!(twap, second_price);
 
-        println!(
-            "   At supply {}: {} tokens for {} SOL",
-            supply1 / 1_000_000_000,
-            tokens_at_low_supply / 1_000_000_000,
-            delta / 1_000_000_000
-        );
-        println!(
-            "   At supply {}: {} tokens for {} SOL",
-            supply2 / 1_000_000_000,
-            tokens_at_high_supply / 1_000_000_000,
-            delta / 1_000_000_000
+        // A repeated update in the same slot adds nothing
+        let cumulative = pool.price_cumulative;
+        pool.update_twap(140).unwrap();
+        assert_eq!(pool.price_cumulative, cumulative);
+
+        // An empty window has no average
+        assert_eq!(
+            pool.read_twap(cumulative, 140, 140).unwrap_err(),
+            error!(AmmError::InvalidArg)
         );
 
-        // ASSERT: At higher supply, same SOL amount should buy fewer tokens (higher price)
-        // If this still fails, the bonding curve math might need adjustment
-        if tokens_at_low_supply <= tokens_at_high_supply {
-            println!("⚠️  Bonding curve behavior: getting more tokens at higher supply");
-            println!("   This suggests the math implementation may need review");
-            // For now, just check that both values are positive and different
-            assert!(
-                tokens_at_low_supply > 0 && tokens_at_high_supply > 0,
-                "Both token amounts should be positive"
-            );
-        } else {
-            println!("✅ Positive slope confirmed!");
-        }
+        println!("✅ TWAP accumulator test passed!");
     }
 
     #[test]
-    fn test_max_supply_limits() {
-        // ARRANGE: Test behavior at max supply limits
-        let pool = create_test_pool();
-        let near_max_sol = pool.config.gamma_s - 1_000_000_000; // 1 SOL from max
-
-        println!("🧪 Testing behavior near max supply limits");
+    fn test_twap_survives_accumulator_wrap() {
+        // ARRANGE: An accumulator about to overflow
+        let mut pool = create_test_pool();
+        pool.price_cumulative = u128::MAX - 5;
+        pool.last_twap_slot = 10;
+        let price = pool.spot_price().unwrap();
 
-        // ACT: Try to buy with amount that would exceed max
-        let large_amount = 100_000_000_000; // 100 SOL
-        let swap = pool.buy_meme_swap_amounts(large_amount, 0).unwrap();
+        // ACT
+        pool.update_twap(20).unwrap();
 
-        // ASSERT: Should cap at remaining amount
-        assert!(
-            swap.amount_in <= pool.config.gamma_s - pool.quote_reserve.tokens,
-            "Should not exceed max SOL supply"
-        );
+        // ASSERT: The wrapped difference still gives the held price
+        assert!(pool.price_cumulative < u128::MAX - 5);
+        assert_eq!(pool.read_twap(u128::MAX - 5, 10, 20).unwrap(), price);
 
-        println!("✅ Max supply limit test passed!");
-        println!("   Attempted: {} SOL", large_amount / 1_000_000_000);
-        println!("   Actually used: {} SOL", swap.amount_in / 1_000_000_000);
+        println!("✅ TWAP wrap test passed!");
     }
 
     #[test]
-    #[should_panic(expected = "SlippageExceeded")]
-    fn test_slippage_protection() {
-        // ARRANGE: Test slippage protection
-        let pool = create_test_pool();
-        let sol_amount = 10_000_000_000; // 10 SOL
-        let unrealistic_min_out = 1_000_000_000_000; // Expect 1000 MEME (unrealistic)
+    fn test_abort_only_before_trading() {
+        // ARRANGE: A fresh launch nobody traded on yet
+        let mut pool = create_test_pool();
+        pool.quote_reserve.tokens = 0;
+        pool.total_quote_volume = 0;
+        pool.total_meme_volume = 0;
 
-        println!("🧪 Testing slippage protection (should fail)");
+        println!("🧪 Testing when a launch can be aborted");
 
-        // ACT & ASSERT: This should panic with SlippageExceeded
-        pool.buy_meme_swap_amounts(sol_amount, unrealistic_min_out)
-            .unwrap();
+        // ACT & ASSERT: No trades, the creator may abort
+        pool.check_abortable().unwrap();
+
+        // A buy puts quote in the pool
+        let buy = pool.swap_amounts(1_000_000_000, 0, true).unwrap();
+        let mut traded = pool.clone();
+        traded.quote_reserve.tokens += buy.amount_in;
+        traded.meme_reserve.tokens -= buy.amount_out + buy.admin_fee_out;
+        traded.record_volume(&buy, true).unwrap();
+        assert_eq!(
+            traded.check_abortable().unwrap_err(),
+            error!(AmmError::LaunchAlreadyTraded)
+        );
+
+        // Selling it all back empties the reserve but still counts as trading
+        traded.quote_reserve.tokens = 0;
+        assert_eq!(
+            traded.check_abortable().unwrap_err(),
+            error!(AmmError::LaunchAlreadyTraded)
+        );
+
+        // Migrated pools can't be aborted either
+        pool.pool_migration = true;
+        assert_eq!(
+            pool.check_abortable().unwrap_err(),
+            error!(AmmError::AlreadyMigrated)
+        );
+
+        println!("✅ Abort launch guard test passed!");
     }
 
     #[test]
-    fn test_alpha_and_beta_calculation() {
-        // ARRANGE: Test the compute_alpha_abs and compute_beta functions
-        let gamma_s = 1_000_000_000_000_u128; // 1000 SOL
-        let gamma_s_denom = 1_000_000_000_u128; // SOL decimals
-        let gamma_m = 3_000_000_000_000_u128; // 3000 tokens
-        let omega_m = 3_000_000_000_000_u128; // 3000 tokens
-        let price_factor_num = 3; // Adjusted to ensure positive slope
-        let price_factor_denom = 2; // Adjusted to ensure positive slope and intercept
-        let beta_decimals = 1_000_000_000_u128;
-
-        println!("🧪 Testing alpha and beta calculations");
+    fn test_buy_clamped_to_meme_reserve() {
+        // ARRANGE: 1 SOL before the target, with the reserve 5% behind
+        // what the curve prices as left, as rounding leaves it near saturation
+        let om = crate::consts::DEFAULT_MAX_M_LP;
+        let mut pool = create_curve_pool(85_000_000_000, 1_000_000_000, om, 3, 1);
+        let s = pool.config.gamma_s - 1_000_000_000;
+        let curve_left = pool.compute_delta_m(s, pool.config.gamma_s).unwrap();
+        pool.quote_reserve.tokens = s;
+        pool.meme_reserve.tokens = curve_left - curve_left / 20;
+
+        println!("🧪 Testing a buy pricing more meme than is left");
+
+        // ACT: Just short of filling the curve, so not the max path
+        let swap = pool.swap_amounts(990_000_000, 0, true).unwrap();
+
+        // ASSERT: The payout fits the reserve instead of underflowing it
+        assert!(pool.compute_delta_m(s, s + swap.amount_in).unwrap() > pool.meme_reserve.tokens);
+        assert!(swap.amount_in < pool.config.gamma_s - s);
+        assert_eq!(swap.amount_out + swap.admin_fee_out, pool.meme_reserve.tokens);
+
+        println!("✅ Buy clamp test passed!");
+    }
 
-        // ACT: Calculate alpha
-        let (alpha, alpha_decimals_result) = compute_alpha_abs(
-            gamma_s,
-            gamma_s_denom,
-            gamma_m,
-            omega_m,
-            price_factor_num,
-            price_factor_denom,
-        )
-        .unwrap();
+    #[test]
+    fn test_virtual_reserves_validation() {
+        // ARRANGE
+        let om = crate::consts::DEFAULT_MAX_M_LP;
+        let pool = create_curve_pool(85_000_000_000, 1_000_000_000, om, 3, 1);
+
+        // ACT & ASSERT: Virtual SOL at the target leaves no max_delta_s
+        let mut at_target = pool.clone();
+        at_target.config.virtual_sol_reserves = pool.config.gamma_s;
+        assert_eq!(
+            at_target.check_virtual_reserves().unwrap_err(),
+            error!(AmmError::InvalidVirtualReserves)
+        );
 
-        // Calculate beta
-        let beta = compute_beta(
-            gamma_s,
-            gamma_s_denom,
-            gamma_m,
-            omega_m,
-            price_factor_num,
-            price_factor_denom,
-            beta_decimals,
-        )
-        .unwrap();
+        // Holding back more meme than the pool has
+        let mut over_meme = pool.clone();
+        over_meme.config.virtual_meme_reserves = pool.meme_reserve.tokens + 1;
+        assert_eq!(
+            over_meme.check_virtual_reserves().unwrap_err(),
+            error!(AmmError::InvalidVirtualReserves)
+        );
 
-        // ASSERT: Both should be positive
-        assert!(alpha > 0, "Alpha should be positive for positive slope");
-        assert!(beta > 0, "Beta should be positive for positive intercept");
+        // No offsets is the plain curve
+        pool.check_virtual_reserves().unwrap();
 
-        println!("✅ Alpha and beta calculation passed!");
-        println!("   Alpha: {} (decimals: {})", alpha, alpha_decimals_result);
-        println!("   Beta: {} (decimals: {})", beta, beta_decimals);
+        println!("✅ Virtual reserves validation test passed!");
     }
 }
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+/// Marks a wallet as having bought from a pool. Created on the wallet's first
+/// buy and used to count unique buyers without double counting repeat buys.
+#[account]
+#[derive(InitSpace)]
+pub struct BuyerRecord {
+    /// Pool the buyer traded against
+    pub pool: Pubkey,
+    /// Buying wallet
+    pub buyer: Pubkey,
+    /// Slot of the wallet's first buy
+    pub first_buy_slot: u64,
+    /// Flag set once the record has been filled in
+    pub initialized: bool,
+}
+
+impl BuyerRecord {
+    /// Prefix for buyer record PDA derivation
+    pub const BUYER_RECORD_PREFIX: &'static [u8; 12] = b"buyer_record";
+
+    /// Fills in a freshly created record. Returns `true` only the first time,
+    /// so callers can count each wallet once.
+    pub fn register(&mut self, pool: Pubkey, buyer: Pubkey, slot: u64) -> bool {
+        if self.initialized {
+            return false;
+        }
+
+        self.pool = pool;
+        self.buyer = buyer;
+        self.first_buy_slot = slot;
+        self.initialized = true;
+
+        true
+    }
+}
@@ -0,0 +1,189 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Creator allocation carved out of the trading supply at pool creation and
+/// held in its own vault. Nothing is released before `unlock_ts`, after which
+/// the allocation vests linearly over `vesting_period`. The curve and
+/// migration never see it, so the lock doesn't count as sold.
+///
+/// The account is public so front-ends can fetch it and show the lock status.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorLock {
+    /// Pool the allocation belongs to
+    pub pool: Pubkey,
+    /// Wallet allowed to claim the allocation
+    pub creator: Pubkey,
+    /// Amount of meme tokens locked
+    pub amount: u64,
+    /// Unix timestamp after which the allocation can be claimed
+    pub unlock_ts: i64,
+    /// Seconds after `unlock_ts` over which the allocation vests, 0 releases
+    /// it all at once. Buyers' tokens don't vest at all.
+    pub vesting_period: i64,
+    /// Amount already released to the creator
+    pub claimed_amount: u64,
+    /// Flag indicating if the whole allocation was already claimed
+    pub claimed: bool,
+}
+
+impl CreatorLock {
+    /// Prefix for creator lock PDA derivation
+    pub const CREATOR_LOCK_PREFIX: &'static [u8; 12] = b"creator_lock";
+    /// Prefix for the vault holding the locked tokens, owned by the lock PDA
+    pub const VAULT_PREFIX: &'static [u8; 18] = b"creator_lock_vault";
+
+    pub fn is_unlocked(&self, now: i64) -> bool {
+        now >= self.unlock_ts
+    }
+
+    /// Moves the allocation to `new_creator` along with the pool, unless it
+    /// was already claimed in full.
+    pub fn hand_over(&mut self, new_creator: Pubkey) {
+        if !self.claimed {
+            self.creator = new_creator;
+        }
+    }
+
+    /// Part of the allocation vested by `now`, claimed or not.
+    pub fn vested_amount(&self, now: i64) -> u64 {
+        if !self.is_unlocked(now) {
+            return 0;
+        }
+
+        let elapsed = now - self.unlock_ts;
+        if elapsed >= self.vesting_period {
+            return self.amount;
+        }
+
+        (self.amount as u128 * elapsed as u128 / self.vesting_period as u128) as u64
+    }
+
+    /// Part of the allocation not released yet.
+    pub fn unclaimed_amount(&self) -> u64 {
+        self.amount - self.claimed_amount
+    }
+
+    /// Records the vested but unclaimed tokens as claimed and returns them.
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        if self.claimed || self.amount == 0 {
+            return Err(error!(AmmError::NoTokensToWithdraw));
+        }
+
+        if !self.is_unlocked(now) {
+            return Err(error!(AmmError::CreatorAllocationLocked));
+        }
+
+        let released = self.vested_amount(now) - self.claimed_amount;
+        if released == 0 {
+            return Err(error!(AmmError::NoTokensToWithdraw));
+        }
+
+        self.claimed_amount += released;
+        self.claimed = self.claimed_amount == self.amount;
+
+        Ok(released)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_lock() -> CreatorLock {
+        CreatorLock {
+            pool: Pubkey::new_unique(),
+            creator: Pubkey::new_unique(),
+            amount: 10_000_000_000_000, // 10M tokens
+            unlock_ts: 1_000,
+            vesting_period: 0,
+            claimed_amount: 0,
+            claimed: false,
+        }
+    }
+
+    #[test]
+    fn test_early_claim_rejected() {
+        let mut lock = create_test_lock();
+
+        let err = lock.claim(999).unwrap_err();
+
+        assert_eq!(err, error!(AmmError::CreatorAllocationLocked));
+        assert!(!lock.claimed);
+        assert!(!lock.is_unlocked(999));
+
+        println!("✅ Early creator claim rejection test passed!");
+    }
+
+    #[test]
+    fn test_claim_after_unlock() {
+        let mut lock = create_test_lock();
+
+        let released = lock.claim(1_000).unwrap();
+
+        assert_eq!(released, 10_000_000_000_000);
+        assert!(lock.claimed);
+
+        // A second claim must not release the tokens again
+        let err = lock.claim(2_000).unwrap_err();
+        assert_eq!(err, error!(AmmError::NoTokensToWithdraw));
+
+        println!("✅ Post-unlock creator claim test passed!");
+    }
+
+    #[test]
+    fn test_allocation_vests_after_unlock() {
+        // ARRANGE: A lock vesting over 1_000 seconds after its unlock
+        println!("🧪 Testing the creator allocation vesting linearly");
+        let mut lock = create_test_lock();
+        lock.vesting_period = 1_000;
+
+        // ACT & ASSERT: Nothing before the unlock, even with a vesting period
+        assert_eq!(lock.vested_amount(999), 0);
+        assert_eq!(
+            lock.claim(999).unwrap_err(),
+            error!(AmmError::CreatorAllocationLocked)
+        );
+
+        // A quarter of the way through releases a quarter
+        assert_eq!(lock.claim(1_250).unwrap(), 2_500_000_000_000);
+        assert!(!lock.claimed);
+
+        // Nothing new vested in the same second
+        assert_eq!(
+            lock.claim(1_250).unwrap_err(),
+            error!(AmmError::NoTokensToWithdraw)
+        );
+
+        // The rest once the period is over, only once
+        assert_eq!(lock.claim(5_000).unwrap(), 7_500_000_000_000);
+        assert!(lock.claimed);
+        assert_eq!(lock.unclaimed_amount(), 0);
+        assert_eq!(
+            lock.claim(6_000).unwrap_err(),
+            error!(AmmError::NoTokensToWithdraw)
+        );
+
+        println!("✅ Creator vesting test passed!");
+    }
+
+    #[test]
+    fn test_vesting_spreads_release_past_unlock() {
+        // ARRANGE: Two pools locking the same allocation, one vesting
+        println!("🧪 Testing a vesting period against a plain cliff");
+        let cliff = create_test_lock();
+        let mut vesting = create_test_lock();
+        vesting.vesting_period = 86_400;
+
+        // ASSERT: Both hold everything back until the unlock, then the cliff
+        // releases it all while the vesting lock spreads it over the period
+        assert_eq!(cliff.vested_amount(0), 0);
+        assert_eq!(vesting.vested_amount(0), 0);
+        assert_eq!(cliff.vested_amount(1_000), cliff.amount);
+        assert_eq!(vesting.vested_amount(1_000), 0);
+        assert_eq!(vesting.vested_amount(1_000 + 43_200), vesting.amount / 2);
+        assert_eq!(vesting.vested_amount(1_000 + 86_400), vesting.amount);
+
+        println!("✅ Vesting vs cliff test passed!");
+    }
+}
@@ -0,0 +1,95 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Launch history of a creator, used to rate limit pool creation. Created on
+/// the creator's first pool or when the admin exempts them.
+#[account]
+#[derive(InitSpace)]
+pub struct CreatorStats {
+    /// Creator the stats belong to
+    pub creator: Pubkey,
+    /// Pools launched by the creator
+    pub pools_created: u64,
+    /// Unix timestamp of the creator's last launch
+    pub last_created_ts: i64,
+    /// Set by the admin to lift the rate limit
+    pub exempt: bool,
+}
+
+impl CreatorStats {
+    /// Prefix for creator stats PDA derivation
+    pub const CREATOR_STATS_PREFIX: &'static [u8; 13] = b"creator_stats";
+
+    /// Rejects a launch less than `cooldown_secs` after the creator's previous
+    /// one unless they're exempt, then counts it.
+    pub fn record_creation(&mut self, creator: Pubkey, now: i64, cooldown_secs: i64) -> Result<()> {
+        if !self.exempt
+            && self.pools_created > 0
+            && now.saturating_sub(self.last_created_ts) < cooldown_secs
+        {
+            return Err(error!(AmmError::CreationRateLimited));
+        }
+
+        self.creator = creator;
+        self.pools_created = self
+            .pools_created
+            .checked_add(1)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        self.last_created_ts = now;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_stats() -> CreatorStats {
+        CreatorStats {
+            creator: Pubkey::default(),
+            pools_created: 0,
+            last_created_ts: 0,
+            exempt: false,
+        }
+    }
+
+    #[test]
+    fn test_rapid_second_creation_rejected() {
+        let mut stats = create_test_stats();
+        let creator = Pubkey::new_unique();
+
+        println!("🧪 Testing the creator launch rate limit");
+
+        // The first launch always goes through
+        stats.record_creation(creator, 1_000, 3_600).unwrap();
+        assert_eq!(stats.pools_created, 1);
+
+        // A second launch inside the cooldown is rejected and counts nothing
+        let err = stats.record_creation(creator, 4_599, 3_600).unwrap_err();
+        assert_eq!(err, error!(AmmError::CreationRateLimited));
+        assert_eq!(stats.pools_created, 1);
+        assert_eq!(stats.last_created_ts, 1_000);
+
+        // Once the cooldown has passed it's allowed
+        stats.record_creation(creator, 4_600, 3_600).unwrap();
+        assert_eq!(stats.pools_created, 2);
+        assert_eq!(stats.last_created_ts, 4_600);
+
+        println!("✅ Creator rate limit test passed!");
+    }
+
+    #[test]
+    fn test_exempt_creator_not_limited() {
+        let mut stats = create_test_stats();
+        stats.exempt = true;
+        let creator = Pubkey::new_unique();
+
+        stats.record_creation(creator, 1_000, 3_600).unwrap();
+        stats.record_creation(creator, 1_000, 3_600).unwrap();
+
+        assert_eq!(stats.pools_created, 2);
+
+        println!("✅ Exempt creator test passed!");
+    }
+}
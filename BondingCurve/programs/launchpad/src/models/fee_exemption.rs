@@ -0,0 +1,17 @@
+use anchor_lang::prelude::*;
+
+/// Waives the admin fees for one wallet on one pool. Its existence is the
+/// exemption; swaps passing it in are quoted without fees.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeExemption {
+    /// Pool the exemption applies to
+    pub pool: Pubkey,
+    /// Wallet trading fee free
+    pub wallet: Pubkey,
+}
+
+impl FeeExemption {
+    /// Prefix for fee exemption PDA derivation
+    pub const FEE_EXEMPTION_PREFIX: &'static [u8; 13] = b"fee_exemption";
+}
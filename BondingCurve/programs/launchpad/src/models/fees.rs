@@ -1,8 +1,11 @@
+use crate::err::AmmError;
 use crate::libraries::MulDiv;
 use anchor_lang::prelude::*;
+use std::cmp::min;
 
-pub const MEME_FEE: u64 = 0; // 0%
+pub const MAX_MEME_FEE: u64 = 50_000_000; // 5%
 pub const FEE: u64 = 10_000_000; // 1%
+pub const MAX_QUOTE_FEE: u64 = 50_000_000; // 5%
 pub const FEE_PRECISION: u64 = 1_000_000_000;
 
 #[derive(
@@ -21,10 +24,42 @@ impl Fees {
     pub fn get_fee_quote_amount(&self, amount: u64) -> Result<u64> {
         get_fee_amount(amount, self.fee_quote_percent)
     }
+
+    /// Rejects a quote fee above `MAX_QUOTE_FEE`. Sells pay it twice.
+    pub fn check_quote_fee(&self) -> Result<()> {
+        if self.fee_quote_percent > MAX_QUOTE_FEE {
+            return Err(error!(AmmError::FeeTooHigh));
+        }
+
+        Ok(())
+    }
+
+    /// Rejects a meme fee above `MAX_MEME_FEE`. Sells pay it twice.
+    pub fn check_meme_fee(&self) -> Result<()> {
+        if self.fee_meme_percent > MAX_MEME_FEE {
+            return Err(error!(AmmError::FeeTooHigh));
+        }
+
+        Ok(())
+    }
 }
 
+/// Fee on `x`, rounded up in the protocol's favour but never above `x`, so
+/// callers can always subtract it.
 pub fn get_fee_amount(x: u64, percent: u64) -> Result<u64> {
-    Ok(x.mul_div_ceil(percent, FEE_PRECISION).unwrap())
+    x.mul_div_ceil(percent, FEE_PRECISION)
+        .map(|fee| min(fee, x))
+        .ok_or(error!(AmmError::MathOverflow))
+}
+
+/// Smallest gross amount which still leaves `net` after the fee is deducted.
+pub fn get_gross_amount(net: u64, percent: u64) -> Result<u64> {
+    if percent >= FEE_PRECISION {
+        return Err(error!(AmmError::InvalidArg));
+    }
+
+    net.mul_div_ceil(FEE_PRECISION, FEE_PRECISION - percent)
+        .ok_or(error!(AmmError::MathOverflow))
 }
 
 #[cfg(test)]
@@ -61,4 +96,71 @@ mod tests {
             amount, actual_fee
         );
     }
+
+    #[test]
+    fn test_one_percent_fee_on_round_amount() {
+        // ARRANGE: 1% of an amount that divides evenly, so no ceil kicks in
+        println!("🧪 Testing the 1% fee on 1000 tokens");
+        let amount = 1000;
+
+        // ACT
+        let fee = get_fee_amount(amount, FEE).unwrap();
+
+        // ASSERT: Exactly a hundredth, leaving 990 after the fee
+        assert_eq!(fee, 10);
+        assert_eq!(amount - fee, 990);
+        println!("✅ 1% of {} = {}", amount, fee);
+    }
+
+    #[test]
+    fn test_fee_capped_at_amount() {
+        // ARRANGE: Amounts so small the ceil rounds the fee up to everything
+        println!("🧪 Testing fees on dust amounts");
+
+        // ACT & ASSERT: A single unit pays at most itself
+        assert_eq!(get_fee_amount(1, FEE).unwrap(), 1);
+        assert_eq!(get_fee_amount(1, FEE_PRECISION * 2).unwrap(), 1);
+        assert_eq!(get_fee_amount(0, FEE).unwrap(), 0);
+
+        // Subtracting it never underflows
+        for x in [1_u64, 2, 99, 100, 101] {
+            let fee = get_fee_amount(x, FEE).unwrap();
+            assert!(fee <= x);
+            assert_eq!(x - fee + fee, x);
+        }
+
+        println!("✅ Dust fee cap test passed!");
+    }
+
+    #[test]
+    fn test_fee_overflow_is_an_error() {
+        // A fee rate far above 100% on a huge amount doesn't fit in a u64
+        assert_eq!(
+            get_fee_amount(u64::MAX, u64::MAX).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        // Right at the edge it still computes
+        assert_eq!(get_fee_amount(u64::MAX, FEE_PRECISION).unwrap(), u64::MAX);
+    }
+
+    #[test]
+    fn test_gross_amount_covers_net() {
+        for net in [1_u64, 99, 1_000, 123_456_789, 10_000_000_000] {
+            let gross = get_gross_amount(net, FEE).unwrap();
+            let fee = get_fee_amount(gross, FEE).unwrap();
+
+            assert!(gross - fee >= net, "gross {} doesn't cover net {}", gross, net);
+            assert!(
+                gross - 1 - get_fee_amount(gross - 1, FEE).unwrap() < net,
+                "gross {} isn't the smallest for net {}",
+                gross,
+                net
+            );
+        }
+
+        assert_eq!(get_gross_amount(1_000, 0).unwrap(), 1_000);
+
+        println!("✅ Gross amount calculation test passed!");
+    }
 }
@@ -0,0 +1,123 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Program-wide settings, a single PDA. Holds the admin the gated endpoints
+/// check against, so the key can be rotated without redeploying.
+#[account]
+#[derive(InitSpace)]
+pub struct GlobalConfig {
+    /// Wallet allowed to call the admin endpoints
+    pub admin: Pubkey,
+    /// Wallet proposed as the next admin, default key when none is pending
+    pub pending_admin: Pubkey,
+}
+
+impl GlobalConfig {
+    /// Seed of the global config PDA
+    pub const GLOBAL_CONFIG_PREFIX: &'static [u8; 13] = b"global_config";
+
+    pub fn is_admin(&self, key: &Pubkey) -> bool {
+        self.admin == *key
+    }
+
+    /// First step of an admin handoff. Only the current admin can propose,
+    /// and proposing the default key withdraws a pending proposal.
+    pub fn propose_admin(&mut self, signer: &Pubkey, new_admin: Pubkey) -> Result<()> {
+        if !self.is_admin(signer) {
+            return Err(error!(AmmError::NotAdmin));
+        }
+
+        self.pending_admin = new_admin;
+
+        Ok(())
+    }
+
+    /// Second step of an admin handoff, signed by the proposed wallet so the
+    /// role can't land on a key nobody controls.
+    pub fn accept_admin(&mut self, signer: &Pubkey) -> Result<()> {
+        if self.pending_admin == Pubkey::default() || self.pending_admin != *signer {
+            return Err(error!(AmmError::NotPendingAdmin));
+        }
+
+        self.admin = std::mem::take(&mut self.pending_admin);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_config(admin: Pubkey) -> GlobalConfig {
+        GlobalConfig {
+            admin,
+            pending_admin: Pubkey::default(),
+        }
+    }
+
+    #[test]
+    fn test_admin_handoff() {
+        // ARRANGE
+        let (admin, next_admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut config = create_test_config(admin);
+
+        println!("🧪 Testing the two-step admin handoff");
+
+        // ACT: Propose, then accept from the proposed wallet
+        config.propose_admin(&admin, next_admin).unwrap();
+
+        // Proposing alone doesn't move the role
+        assert!(config.is_admin(&admin));
+        assert_eq!(config.pending_admin, next_admin);
+
+        config.accept_admin(&next_admin).unwrap();
+
+        // ASSERT
+        assert!(config.is_admin(&next_admin));
+        assert!(!config.is_admin(&admin));
+        assert_eq!(config.pending_admin, Pubkey::default());
+
+        println!("✅ Admin handoff test passed!");
+    }
+
+    #[test]
+    fn test_handoff_rejects_stale_admin() {
+        // ARRANGE: The role already moved on once
+        let (admin, next_admin) = (Pubkey::new_unique(), Pubkey::new_unique());
+        let mut config = create_test_config(admin);
+        config.propose_admin(&admin, next_admin).unwrap();
+        config.accept_admin(&next_admin).unwrap();
+
+        println!("🧪 Testing that a stale admin can't hand the role off");
+
+        // ACT & ASSERT: The previous admin can no longer propose
+        assert_eq!(
+            config.propose_admin(&admin, admin).unwrap_err(),
+            error!(AmmError::NotAdmin)
+        );
+
+        // Nor accept without a proposal naming it
+        assert_eq!(
+            config.accept_admin(&admin).unwrap_err(),
+            error!(AmmError::NotPendingAdmin)
+        );
+
+        // Anyone else but the proposed wallet is refused too
+        config.propose_admin(&next_admin, admin).unwrap();
+        assert_eq!(
+            config.accept_admin(&Pubkey::new_unique()).unwrap_err(),
+            error!(AmmError::NotPendingAdmin)
+        );
+
+        // A withdrawn proposal can't be accepted
+        config.propose_admin(&next_admin, Pubkey::default()).unwrap();
+        assert_eq!(
+            config.accept_admin(&admin).unwrap_err(),
+            error!(AmmError::NotPendingAdmin)
+        );
+        assert!(config.is_admin(&next_admin));
+
+        println!("✅ Stale admin rejection test passed!");
+    }
+}
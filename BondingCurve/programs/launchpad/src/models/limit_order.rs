@@ -0,0 +1,174 @@
+use crate::consts::DECIMALS_S;
+use crate::err::AmmError;
+use crate::libraries::MulDiv;
+use crate::models::fees::Fees;
+use anchor_lang::prelude::*;
+
+/// Side of the trigger price a limit order waits for
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum LimitDirection {
+    /// Buy once the spot price falls to the trigger or below
+    #[default]
+    Below,
+    /// Buy once the spot price rises to the trigger or above
+    Above,
+}
+
+/// A buy waiting for the pool's spot price to cross `trigger_price`. The
+/// quote tokens are escrowed at placement and spent by the crank.
+#[account]
+#[derive(InitSpace)]
+pub struct LimitOrder {
+    /// Pool the order buys from
+    pub pool: Pubkey,
+    /// Wallet receiving the meme tokens
+    pub owner: Pubkey,
+    /// Distinguishes the owner's orders on the same pool
+    pub order_id: u64,
+    /// Spot price to wait for, in `BoundPool::spot_price` units
+    pub trigger_price: u64,
+    /// Escrowed quote tokens not spent yet
+    pub quote_amount: u64,
+    /// Side of `trigger_price` which fills the order
+    pub direction: LimitDirection,
+}
+
+impl LimitOrder {
+    /// Prefix for limit order PDA derivation
+    pub const LIMIT_ORDER_PREFIX: &'static [u8; 11] = b"limit_order";
+    /// Prefix for the order's quote escrow PDA derivation
+    pub const ESCROW_PREFIX: &'static [u8; 18] = b"limit_order_escrow";
+
+    /// Whether `spot_price` is on the order's side of the trigger.
+    pub fn is_triggered(&self, spot_price: u64) -> bool {
+        match self.direction {
+            LimitDirection::Below => spot_price <= self.trigger_price,
+            LimitDirection::Above => spot_price >= self.trigger_price,
+        }
+    }
+
+    /// Rejects executing the order before the price crossed its trigger.
+    pub fn check_triggered(&self, spot_price: u64) -> Result<()> {
+        if !self.is_triggered(spot_price) {
+            return Err(error!(AmmError::LimitOrderNotTriggered));
+        }
+
+        Ok(())
+    }
+
+    /// Least meme, net of `fees`, a fill spending `quote_in` must buy: what
+    /// `quote_in` buys at `trigger_price`. The trigger is also the worst
+    /// average price the order accepts, however far the price moved past it.
+    pub fn min_meme_out(&self, quote_in: u64, fees: &Fees) -> Result<u64> {
+        let net_quote = quote_in - fees.get_fee_quote_amount(quote_in)?;
+        let meme = net_quote
+            .mul_div_floor(DECIMALS_S as u64, self.trigger_price)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(meme - fees.get_fee_meme_amount(meme)?)
+    }
+
+    /// Takes `spent` quote tokens out of the escrowed amount. What a capped
+    /// buy leaves behind stays escrowed for the owner.
+    pub fn fill(&mut self, spent: u64) -> Result<()> {
+        self.quote_amount = self
+            .quote_amount
+            .checked_sub(spent)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Empties the order for cancellation, returning the escrowed quote the
+    /// owner gets back. Quote already spent by fills isn't part of it.
+    pub fn cancel(&mut self) -> u64 {
+        std::mem::take(&mut self.quote_amount)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::fees::FEE;
+
+    fn create_test_order(direction: LimitDirection) -> LimitOrder {
+        LimitOrder {
+            pool: Pubkey::new_unique(),
+            owner: Pubkey::new_unique(),
+            order_id: 0,
+            trigger_price: 1_000,
+            quote_amount: 5_000,
+            direction,
+        }
+    }
+
+    #[test]
+    fn test_trigger_sides() {
+        let below = create_test_order(LimitDirection::Below);
+        let above = create_test_order(LimitDirection::Above);
+
+        // The trigger price itself fills either side
+        assert!(below.is_triggered(1_000) && above.is_triggered(1_000));
+        assert!(below.is_triggered(999) && !above.is_triggered(999));
+        assert!(!below.is_triggered(1_001) && above.is_triggered(1_001));
+
+        assert_eq!(
+            below.check_triggered(1_001).unwrap_err(),
+            error!(AmmError::LimitOrderNotTriggered)
+        );
+
+        println!("✅ Limit order trigger test passed!");
+    }
+
+    #[test]
+    fn test_min_meme_out_at_trigger() {
+        let order = create_test_order(LimitDirection::Above);
+        let fees = Fees {
+            fee_meme_percent: FEE,
+            fee_quote_percent: FEE,
+        };
+
+        // 1 quote unit buys 1e6 meme units at a trigger of 1e-6 quote each
+        assert_eq!(order.min_meme_out(1, &Fees::default()).unwrap(), 1_000_000);
+
+        // Both fees come off, the quote one before pricing
+        assert_eq!(order.min_meme_out(100, &fees).unwrap(), 98_010_000);
+
+        println!("✅ Limit order min out test passed!");
+    }
+
+    #[test]
+    fn test_fill_keeps_remainder() {
+        let mut order = create_test_order(LimitDirection::Below);
+
+        order.fill(3_000).unwrap();
+        assert_eq!(order.quote_amount, 2_000);
+
+        // Spending more than escrowed is an accounting bug
+        assert_eq!(
+            order.fill(2_001).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+        assert_eq!(order.quote_amount, 2_000);
+
+        println!("✅ Limit order fill test passed!");
+    }
+
+    #[test]
+    fn test_cancel_refunds_unfilled_remainder() {
+        // An untouched order refunds everything it escrowed
+        let mut order = create_test_order(LimitDirection::Below);
+        assert_eq!(order.cancel(), 5_000);
+        assert_eq!(order.quote_amount, 0);
+
+        // After a partial fill only what's left comes back
+        let mut order = create_test_order(LimitDirection::Below);
+        order.fill(3_500).unwrap();
+        assert_eq!(order.cancel(), 1_500);
+        assert_eq!(order.cancel(), 0);
+
+        println!("✅ Limit order cancel test passed!");
+    }
+}
@@ -1,10 +1,25 @@
 pub mod bound;
+pub mod buyer_record;
+pub mod creator_lock;
+pub mod creator_stats;
+pub mod fee_exemption;
 pub mod fees;
+pub mod global_config;
+pub mod limit_order;
 pub mod points_epoch;
+pub mod points_escrow;
+pub mod pool_registry;
+pub mod price_snapshot;
+pub mod referral_code;
+pub mod referrer_stats;
+pub mod stake_account;
 pub mod staking;
 pub mod target_config;
+pub mod wallet_activity;
 
+use crate::err::AmmError;
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::program::set_return_data;
 
 extern crate std;
 
@@ -30,9 +45,31 @@ pub struct Reserve {
     pub vault: Pubkey,
 }
 
+#[derive(Debug)]
 pub struct SwapAmount {
     pub amount_in: u64,
     pub amount_out: u64,
     pub admin_fee_in: u64,
     pub admin_fee_out: u64,
 }
+
+/// Returned via return data alongside `SlippageExceeded`, so wallets can show
+/// how far the quote missed: `actual` is what the swap would have given (or
+/// required), `limit` the caller's bound.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Eq, PartialEq)]
+pub struct SlippageDetail {
+    pub actual: u64,
+    pub limit: u64,
+}
+
+impl SlippageDetail {
+    pub fn exceeded(actual: u64, limit: u64) -> Error {
+        let detail = SlippageDetail { actual, limit };
+
+        if let Ok(data) = detail.try_to_vec() {
+            set_return_data(&data);
+        }
+
+        error!(AmmError::SlippageExceeded)
+    }
+}
@@ -1,4 +1,19 @@
+use crate::err::AmmError;
 use anchor_lang::prelude::*;
+use std::cmp::min;
+
+/// How a buy's points follow the pool's progress
+#[derive(
+    AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default, InitSpace,
+)]
+pub enum PointsEmission {
+    /// `points_per_sol` for every buy
+    #[default]
+    Flat,
+    /// `points_per_sol` scaled down by the share of the trading supply
+    /// already sold, so early buyers earn more
+    Decaying,
+}
 
 #[account]
 #[derive(InitSpace)]
@@ -6,5 +21,255 @@ pub struct PointsEpoch {
     pub epoch_number: u64,
     pub points_per_sol_num: u64,
     pub points_per_sol_denom: u64,
-    pub padding: [u8; 8],
+    /// Sellers give back the points an equivalent buy would have earned
+    pub burn_on_sell: bool,
+    /// Flat or decaying with the pool's progress
+    pub emission_mode: PointsEmission,
+    /// Seconds referral points wait in the referrer's `PointsEscrow` before
+    /// `claim_points` releases them (0 pays them out directly)
+    pub points_vesting_secs: u32,
+    pub padding: [u8; 2],
+    /// Most points distributed during this epoch (0 disables the cap)
+    pub max_points_per_epoch: u64,
+    /// Points distributed so far during this epoch
+    pub points_emitted: u64,
+}
+
+/// The points rules the admin may tune through `update_points_epoch`. See
+/// `PointsEpoch` for each field.
+#[derive(AnchorDeserialize, AnchorSerialize, Copy, Clone, Debug, Eq, PartialEq, Default)]
+pub struct PointsEpochSettings {
+    pub epoch_number: u64,
+    pub points_per_sol_num: u64,
+    pub points_per_sol_denom: u64,
+    pub max_points_per_epoch: u64,
+    pub emission_mode: PointsEmission,
+    pub points_vesting_secs: u32,
+    pub burn_on_sell: bool,
+}
+
+impl PointsEpoch {
+    pub fn settings(&self) -> PointsEpochSettings {
+        PointsEpochSettings {
+            epoch_number: self.epoch_number,
+            points_per_sol_num: self.points_per_sol_num,
+            points_per_sol_denom: self.points_per_sol_denom,
+            max_points_per_epoch: self.max_points_per_epoch,
+            emission_mode: self.emission_mode,
+            points_vesting_secs: self.points_vesting_secs,
+            burn_on_sell: self.burn_on_sell,
+        }
+    }
+
+    /// Replaces the points rules, returning the previous ones. A higher
+    /// `epoch_number` starts a new epoch with nothing emitted yet, a lower one
+    /// is refused.
+    pub fn update_settings(
+        &mut self,
+        settings: PointsEpochSettings,
+    ) -> Result<PointsEpochSettings> {
+        if settings.epoch_number < self.epoch_number {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        let previous = self.settings();
+        self.set_rate(settings.points_per_sol_num, settings.points_per_sol_denom)?;
+
+        if settings.epoch_number > self.epoch_number {
+            self.epoch_number = settings.epoch_number;
+            self.points_emitted = 0;
+        }
+
+        self.max_points_per_epoch = settings.max_points_per_epoch;
+        self.emission_mode = settings.emission_mode;
+        self.points_vesting_secs = settings.points_vesting_secs;
+        self.burn_on_sell = settings.burn_on_sell;
+
+        Ok(previous)
+    }
+
+    /// Sets the points earned per quote token paid in, as a fraction.
+    pub fn set_rate(&mut self, points_per_sol_num: u64, points_per_sol_denom: u64) -> Result<()> {
+        if points_per_sol_denom == 0 {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        self.points_per_sol_num = points_per_sol_num;
+        self.points_per_sol_denom = points_per_sol_denom;
+
+        Ok(())
+    }
+
+    /// Clamps `points` to what is left of the epoch cap and records the
+    /// emission. Returns the points that may actually be distributed.
+    pub fn emit_points(&mut self, points: u64) -> Result<u64> {
+        let granted = self.grantable_points(points);
+
+        self.points_emitted = self
+            .points_emitted
+            .checked_add(granted)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(granted)
+    }
+
+    /// `points` clamped to what is left of the epoch cap, without recording
+    /// anything.
+    pub fn grantable_points(&self, points: u64) -> u64 {
+        if self.max_points_per_epoch == 0 {
+            points
+        } else {
+            min(
+                points,
+                self.max_points_per_epoch.saturating_sub(self.points_emitted),
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_epoch(max_points_per_epoch: u64) -> PointsEpoch {
+        PointsEpoch {
+            epoch_number: 1,
+            points_per_sol_num: 1000,
+            points_per_sol_denom: 1,
+            max_points_per_epoch,
+            points_emitted: 0,
+            burn_on_sell: false,
+            emission_mode: PointsEmission::Flat,
+            points_vesting_secs: 0,
+            padding: [0; 2],
+        }
+    }
+
+    #[test]
+    fn test_emission_stops_at_cap() {
+        let mut epoch = create_test_epoch(1_000_000);
+
+        // Whale buy worth more than the whole epoch
+        assert_eq!(epoch.emit_points(1_500_000).unwrap(), 1_000_000);
+        assert_eq!(epoch.points_emitted, 1_000_000);
+
+        // Later buys in the same epoch get nothing
+        assert_eq!(epoch.emit_points(10).unwrap(), 0);
+        assert_eq!(epoch.points_emitted, 1_000_000);
+
+        println!("✅ Points epoch cap test passed!");
+    }
+
+    #[test]
+    fn test_cap_set_by_update() {
+        // ARRANGE: An uncapped epoch
+        let mut epoch = create_test_epoch(0);
+        assert_eq!(epoch.emit_points(5_000).unwrap(), 5_000);
+
+        println!("🧪 Testing the epoch cap switched on by the admin");
+
+        // ACT: Cap the current epoch at 6_000 points
+        let mut settings = epoch.settings();
+        settings.max_points_per_epoch = 6_000;
+        let previous = epoch.update_settings(settings).unwrap();
+
+        // ASSERT: What was emitted before counts toward the cap
+        assert_eq!(previous.max_points_per_epoch, 0);
+        assert_eq!(epoch.emit_points(1_500).unwrap(), 1_000);
+        assert_eq!(epoch.emit_points(10).unwrap(), 0);
+
+        // The next epoch starts from nothing under the same cap
+        settings.epoch_number = 2;
+        epoch.update_settings(settings).unwrap();
+        assert_eq!(epoch.points_emitted, 0);
+        assert_eq!(epoch.emit_points(7_000).unwrap(), 6_000);
+
+        // Going back an epoch or a zero denominator is refused, changing nothing
+        settings.epoch_number = 1;
+        assert_eq!(
+            epoch.update_settings(settings).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        settings.epoch_number = 3;
+        settings.points_per_sol_denom = 0;
+        assert_eq!(
+            epoch.update_settings(settings).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(epoch.epoch_number, 2);
+        assert_eq!(epoch.points_emitted, 6_000);
+
+        println!("✅ Updated epoch cap test passed!");
+    }
+
+    #[test]
+    fn test_vesting_set_by_update() {
+        use crate::models::points_escrow::PointsEscrow;
+
+        // ARRANGE: An epoch paying referral points out directly
+        let mut epoch = create_test_epoch(0);
+        assert_eq!(epoch.points_vesting_secs, 0);
+
+        println!("🧪 Testing points vesting switched on by the admin");
+
+        // ACT: The admin holds referral points back for a day
+        let mut settings = epoch.settings();
+        settings.points_vesting_secs = 86_400;
+        epoch.update_settings(settings).unwrap();
+
+        // ASSERT: Points escrowed under the epoch only unlock a day later
+        let mut escrow = PointsEscrow {
+            owner: Pubkey::new_unique(),
+            amount: 0,
+            unlock_ts: 0,
+        };
+        escrow
+            .deposit(5_000, 1_000, epoch.points_vesting_secs)
+            .unwrap();
+        assert_eq!(
+            escrow.claim(86_399 + 1_000).unwrap_err(),
+            error!(AmmError::PointsStillVesting)
+        );
+        assert_eq!(escrow.claim(86_400 + 1_000).unwrap(), 5_000);
+
+        println!("✅ Updated points vesting test passed!");
+    }
+
+    #[test]
+    fn test_emission_partially_fills_cap() {
+        let mut epoch = create_test_epoch(1_000);
+
+        assert_eq!(epoch.emit_points(600).unwrap(), 600);
+        assert_eq!(epoch.emit_points(600).unwrap(), 400);
+        assert_eq!(epoch.emit_points(1).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_rate_needs_denominator() {
+        let mut epoch = create_test_epoch(0);
+
+        epoch.set_rate(3, 2).unwrap();
+        assert_eq!(
+            (epoch.points_per_sol_num, epoch.points_per_sol_denom),
+            (3, 2)
+        );
+
+        // A zero denominator would fail every buy, and leaves the rate alone
+        assert_eq!(
+            epoch.set_rate(1, 0).unwrap_err(),
+            error!(AmmError::InvalidArg)
+        );
+        assert_eq!(epoch.points_per_sol_denom, 2);
+    }
+
+    #[test]
+    fn test_emission_uncapped() {
+        let mut epoch = create_test_epoch(0);
+
+        assert_eq!(epoch.emit_points(u64::MAX).unwrap(), u64::MAX);
+        assert_eq!(
+            epoch.emit_points(1).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+    }
 }
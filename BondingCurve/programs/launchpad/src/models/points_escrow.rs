@@ -0,0 +1,130 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Referral points held back while the epoch's `points_vesting_secs` runs,
+/// so points can't be farmed and dumped within the same loop of trades. The
+/// tokens sit in the escrow vault and `claim_points` releases them.
+#[account]
+#[derive(InitSpace)]
+pub struct PointsEscrow {
+    /// Referrer the points are held for
+    pub owner: Pubkey,
+    /// Points waiting to be claimed
+    pub amount: u64,
+    /// Unix timestamp after which `amount` can be claimed
+    pub unlock_ts: i64,
+}
+
+impl PointsEscrow {
+    /// Prefix for points escrow PDA derivation, followed by the owner
+    pub const POINTS_ESCROW_PREFIX: &'static [u8; 13] = b"points_escrow";
+    /// Prefix for the escrow's points token vault, followed by the owner
+    pub const VAULT_PREFIX: &'static [u8; 19] = b"points_escrow_vault";
+
+    pub fn is_unlocked(&self, now: i64) -> bool {
+        now >= self.unlock_ts
+    }
+
+    /// Holds `amount` more points. Everything in escrow unlocks together, so
+    /// new points push the unlock back to `vesting_secs` from now.
+    pub fn deposit(&mut self, amount: u64, now: i64, vesting_secs: u32) -> Result<()> {
+        self.amount = self
+            .amount
+            .checked_add(amount)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        self.unlock_ts = now
+            .checked_add(vesting_secs.into())
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Empties the escrow once unlocked, returning the points to release.
+    pub fn claim(&mut self, now: i64) -> Result<u64> {
+        if self.amount == 0 {
+            return Err(error!(AmmError::NoTokensToWithdraw));
+        }
+
+        if !self.is_unlocked(now) {
+            return Err(error!(AmmError::PointsStillVesting));
+        }
+
+        Ok(std::mem::take(&mut self.amount))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DAY: u32 = 86_400;
+
+    fn create_test_escrow() -> PointsEscrow {
+        PointsEscrow {
+            owner: Pubkey::new_unique(),
+            amount: 0,
+            unlock_ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_claim_rejected_before_delay() {
+        // ARRANGE: Points earned at t=1_000 vesting for a day
+        let mut escrow = create_test_escrow();
+        escrow.deposit(5_000, 1_000, DAY).unwrap();
+
+        println!("🧪 Testing points claims before the delay");
+
+        // ACT & ASSERT: Up to the last second the points stay escrowed
+        for now in [1_000, 1_000 + DAY as i64 - 1] {
+            assert_eq!(
+                escrow.claim(now).unwrap_err(),
+                error!(AmmError::PointsStillVesting)
+            );
+        }
+        assert_eq!(escrow.amount, 5_000);
+
+        println!("✅ Pre-delay points claim rejection test passed!");
+    }
+
+    #[test]
+    fn test_claim_after_delay() {
+        // ARRANGE
+        let mut escrow = create_test_escrow();
+        escrow.deposit(5_000, 1_000, DAY).unwrap();
+
+        println!("🧪 Testing points claims after the delay");
+
+        // ACT
+        let claimed = escrow.claim(1_000 + DAY as i64).unwrap();
+
+        // ASSERT: Everything is released once, then there's nothing left
+        assert_eq!(claimed, 5_000);
+        assert_eq!(escrow.amount, 0);
+        assert_eq!(
+            escrow.claim(1_000 + DAY as i64).unwrap_err(),
+            error!(AmmError::NoTokensToWithdraw)
+        );
+
+        println!("✅ Post-delay points claim test passed!");
+    }
+
+    #[test]
+    fn test_new_points_restart_delay() {
+        let mut escrow = create_test_escrow();
+        escrow.deposit(5_000, 1_000, DAY).unwrap();
+
+        // Points earned half a day later hold the whole escrow for another day
+        let later = 1_000 + DAY as i64 / 2;
+        escrow.deposit(2_000, later, DAY).unwrap();
+
+        assert_eq!(escrow.amount, 7_000);
+        assert_eq!(
+            escrow.claim(1_000 + DAY as i64).unwrap_err(),
+            error!(AmmError::PointsStillVesting)
+        );
+        assert_eq!(escrow.claim(later + DAY as i64).unwrap(), 7_000);
+
+        println!("✅ Points escrow delay restart test passed!");
+    }
+}
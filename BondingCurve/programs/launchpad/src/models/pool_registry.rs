@@ -0,0 +1,141 @@
+use crate::consts::POOL_REGISTRY_PAGE_CAPACITY;
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Program-wide count of registered pools. Picks the `PoolRegistry` page the
+/// next pool is appended to.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolRegistryHead {
+    /// Pools registered across all pages
+    pub total_pools: u64,
+}
+
+impl PoolRegistryHead {
+    /// Seed of the registry head PDA
+    pub const REGISTRY_HEAD_PREFIX: &'static [u8; 18] = b"pool_registry_head";
+
+    /// Page the next pool goes to. A new page starts once the last one is full.
+    pub fn current_page(&self) -> u32 {
+        (self.total_pools / POOL_REGISTRY_PAGE_CAPACITY as u64) as u32
+    }
+
+    /// Appends `pool` to `page`, which must be the current page.
+    pub fn register(&mut self, page: &mut PoolRegistry, pool: Pubkey) -> Result<()> {
+        let current_page = self.current_page();
+
+        // A freshly created page doesn't know its index yet
+        if page.pools.is_empty() {
+            page.page = current_page;
+        }
+
+        if page.page != current_page {
+            return Err(error!(AmmError::InvalidRegistryPage));
+        }
+
+        page.append(pool)?;
+        self.total_pools = self
+            .total_pools
+            .checked_add(1)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+}
+
+/// One page of the append-only list of pools created by the program, so
+/// front-ends can list launches without scanning every program account.
+#[account]
+#[derive(InitSpace)]
+pub struct PoolRegistry {
+    /// Index of the page, part of its PDA seeds
+    pub page: u32,
+    /// Pools in creation order
+    #[max_len(POOL_REGISTRY_PAGE_CAPACITY)]
+    pub pools: Vec<Pubkey>,
+}
+
+impl PoolRegistry {
+    /// Prefix for registry page PDA derivation, followed by the page index
+    pub const REGISTRY_PREFIX: &'static [u8; 13] = b"pool_registry";
+
+    /// Adds `pool` at the end of the page.
+    pub fn append(&mut self, pool: Pubkey) -> Result<()> {
+        if self.pools.len() >= POOL_REGISTRY_PAGE_CAPACITY {
+            return Err(error!(AmmError::RegistryPageFull));
+        }
+
+        self.pools.push(pool);
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_page() -> PoolRegistry {
+        PoolRegistry {
+            page: 0,
+            pools: vec![],
+        }
+    }
+
+    #[test]
+    fn test_registry_append() {
+        let mut head = PoolRegistryHead { total_pools: 0 };
+        let mut page = create_test_page();
+        let (first, second) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        head.register(&mut page, first).unwrap();
+        head.register(&mut page, second).unwrap();
+
+        // Pools are listed in creation order
+        assert_eq!(page.pools, vec![first, second]);
+        assert_eq!(head.total_pools, 2);
+        assert_eq!(head.current_page(), 0);
+
+        println!("✅ Pool registry append test passed!");
+    }
+
+    #[test]
+    fn test_registry_page_rollover() {
+        // ARRANGE: Fill the first page
+        let mut head = PoolRegistryHead { total_pools: 0 };
+        let mut first_page = create_test_page();
+
+        for _ in 0..POOL_REGISTRY_PAGE_CAPACITY {
+            head.register(&mut first_page, Pubkey::new_unique())
+                .unwrap();
+        }
+
+        println!("🧪 Testing registry rollover to a second page");
+
+        // ACT & ASSERT: The head moves on to page 1
+        assert_eq!(head.current_page(), 1);
+
+        // The full page refuses more pools either way
+        assert_eq!(
+            head.register(&mut first_page, Pubkey::new_unique())
+                .unwrap_err(),
+            error!(AmmError::InvalidRegistryPage)
+        );
+        assert_eq!(
+            first_page.append(Pubkey::new_unique()).unwrap_err(),
+            error!(AmmError::RegistryPageFull)
+        );
+
+        // A new page takes the next index and the next pool
+        let mut second_page = create_test_page();
+        let pool = Pubkey::new_unique();
+        head.register(&mut second_page, pool).unwrap();
+
+        assert_eq!(second_page.page, 1);
+        assert_eq!(second_page.pools, vec![pool]);
+        assert_eq!(first_page.pools.len(), POOL_REGISTRY_PAGE_CAPACITY);
+        assert_eq!(head.total_pools, POOL_REGISTRY_PAGE_CAPACITY as u64 + 1);
+
+        println!("✅ Pool registry rollover test passed!");
+    }
+}
@@ -0,0 +1,117 @@
+use crate::consts::PRICE_SNAPSHOT_CAPACITY;
+use anchor_lang::prelude::*;
+
+/// Pool price and depth right after one swap
+#[derive(
+    AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default, PartialEq, Eq, InitSpace,
+)]
+pub struct PriceSnapshot {
+    /// Slot of the swap
+    pub slot: u64,
+    /// Spot price after the swap, see `BoundPool::spot_price`
+    pub spot_price: u64,
+    /// Quote reserve after the swap
+    pub quote_reserve: u64,
+}
+
+/// Ring buffer of a pool's last `PRICE_SNAPSHOT_CAPACITY` swaps, for
+/// indexers building short-horizon candles. Optional: swaps only record
+/// into it when it's passed in.
+#[account]
+#[derive(InitSpace)]
+pub struct PriceSnapshots {
+    /// Pool the snapshots belong to
+    pub pool: Pubkey,
+    /// Index the next snapshot is written to
+    pub head: u16,
+    /// Snapshots recorded so far, up to the capacity
+    pub len: u16,
+    pub entries: [PriceSnapshot; PRICE_SNAPSHOT_CAPACITY],
+}
+
+impl PriceSnapshots {
+    /// Prefix for price snapshots PDA derivation
+    pub const PRICE_SNAPSHOTS_PREFIX: &'static [u8; 15] = b"price_snapshots";
+
+    /// Records a snapshot, overwriting the oldest once the buffer is full.
+    pub fn record(&mut self, snapshot: PriceSnapshot) {
+        self.entries[self.head as usize] = snapshot;
+        self.head = ((self.head as usize + 1) % PRICE_SNAPSHOT_CAPACITY) as u16;
+
+        if (self.len as usize) < PRICE_SNAPSHOT_CAPACITY {
+            self.len += 1;
+        }
+    }
+
+    /// Recorded snapshots, oldest first.
+    pub fn ordered(&self) -> Vec<PriceSnapshot> {
+        let len = self.len as usize;
+        let start = (self.head as usize + PRICE_SNAPSHOT_CAPACITY - len) % PRICE_SNAPSHOT_CAPACITY;
+
+        (0..len)
+            .map(|i| self.entries[(start + i) % PRICE_SNAPSHOT_CAPACITY])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_snapshots() -> PriceSnapshots {
+        PriceSnapshots {
+            pool: Pubkey::new_unique(),
+            head: 0,
+            len: 0,
+            entries: [PriceSnapshot::default(); PRICE_SNAPSHOT_CAPACITY],
+        }
+    }
+
+    fn snapshot(slot: u64) -> PriceSnapshot {
+        PriceSnapshot {
+            slot,
+            spot_price: slot * 10,
+            quote_reserve: slot * 100,
+        }
+    }
+
+    #[test]
+    fn test_snapshots_before_wrap() {
+        let mut snapshots = create_test_snapshots();
+        assert!(snapshots.ordered().is_empty());
+
+        for slot in 1..=3 {
+            snapshots.record(snapshot(slot));
+        }
+
+        assert_eq!(
+            snapshots.ordered(),
+            vec![snapshot(1), snapshot(2), snapshot(3)]
+        );
+    }
+
+    #[test]
+    fn test_snapshots_wraparound() {
+        // ARRANGE
+        let mut snapshots = create_test_snapshots();
+        let total = PRICE_SNAPSHOT_CAPACITY as u64 + 5;
+
+        println!("🧪 Testing the price snapshot ring buffer wraparound");
+
+        // ACT: Five swaps more than the buffer holds
+        for slot in 1..=total {
+            snapshots.record(snapshot(slot));
+        }
+
+        // ASSERT: The oldest five were overwritten, the order is kept
+        let ordered = snapshots.ordered();
+        assert_eq!(ordered.len(), PRICE_SNAPSHOT_CAPACITY);
+        assert_eq!(ordered.first(), Some(&snapshot(6)));
+        assert_eq!(ordered.last(), Some(&snapshot(total)));
+        assert!(ordered
+            .windows(2)
+            .all(|pair| pair[0].slot + 1 == pair[1].slot));
+
+        println!("✅ Price snapshot wraparound test passed!");
+    }
+}
@@ -0,0 +1,90 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+
+/// Human readable referral code pointing at the referrer's wallet. Codes are
+/// case insensitive and unique across all pools; the PDA is derived from the
+/// hash of the lowercased code.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferralCode {
+    /// Wallet referred buys pay points to
+    pub owner: Pubkey,
+    /// The code as registered
+    #[max_len(16)]
+    pub code: String,
+}
+
+impl ReferralCode {
+    /// Prefix for referral code PDA derivation
+    pub const REFERRAL_CODE_PREFIX: &'static [u8; 4] = b"code";
+    /// Longest code that can be registered
+    pub const MAX_CODE_LEN: usize = 16;
+
+    /// Accepts 1 to `MAX_CODE_LEN` printable ASCII characters, no spaces.
+    pub fn validate_code(code: &str) -> Result<()> {
+        if code.is_empty()
+            || code.len() > Self::MAX_CODE_LEN
+            || !code.bytes().all(|c| c.is_ascii_graphic())
+        {
+            return Err(error!(AmmError::InvalidReferralCode));
+        }
+
+        Ok(())
+    }
+
+    /// PDA seed of `code`. Codes differing only in case share a seed, so
+    /// lookalike codes collide on registration.
+    pub fn code_seed(code: &str) -> [u8; 32] {
+        hash(code.to_ascii_lowercase().as_bytes()).to_bytes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code_address(code: &str) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                ReferralCode::REFERRAL_CODE_PREFIX.as_ref(),
+                &ReferralCode::code_seed(code),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+
+    #[test]
+    fn test_code_validation() {
+        ReferralCode::validate_code("alice").unwrap();
+        ReferralCode::validate_code("MOON-2024_x!").unwrap();
+        ReferralCode::validate_code(&"a".repeat(16)).unwrap();
+
+        for code in ["", &"a".repeat(17), "with space", "tab\t", "émoji"] {
+            assert_eq!(
+                ReferralCode::validate_code(code).unwrap_err(),
+                error!(AmmError::InvalidReferralCode),
+                "{:?}",
+                code
+            );
+        }
+
+        println!("✅ Referral code validation test passed!");
+    }
+
+    #[test]
+    fn test_code_collision() {
+        println!("🧪 Testing referral code collisions");
+
+        // Case variants land on the same account, so the second `init` fails
+        assert_eq!(code_address("Alice"), code_address("alice"));
+        assert_eq!(code_address("ALICE"), code_address("alice"));
+
+        // Different codes get different accounts
+        assert_ne!(code_address("alice"), code_address("alice1"));
+        assert_ne!(code_address("alice"), code_address("bob"));
+
+        println!("✅ Referral code collision test passed!");
+    }
+}
@@ -0,0 +1,83 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Running totals of a referrer's referred buys across every pool, so
+/// front-ends can rank referrers without indexing each swap. Opened by the
+/// referrer with `open_referrer_stats`, then passed alongside
+/// `referrer_points` on buys.
+#[account]
+#[derive(InitSpace)]
+pub struct ReferrerStats {
+    /// Referrer the stats belong to
+    pub referrer: Pubkey,
+    /// Points paid out to the referrer, escrowed ones included
+    pub total_points_earned: u64,
+    /// Buys made with the referrer attached which earned it points
+    pub referral_count: u64,
+}
+
+impl ReferrerStats {
+    /// Prefix for referrer stats PDA derivation, followed by the referrer
+    pub const REFERRER_STATS_PREFIX: &'static [u8; 14] = b"referrer_stats";
+
+    /// Counts one referred buy which earned the referrer `points`. Swaps only
+    /// call it for buys paying out points, see `record_referral_stats`.
+    pub fn record_referral(&mut self, points: u64) -> Result<()> {
+        self.total_points_earned = self
+            .total_points_earned
+            .checked_add(points)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        self.referral_count = self
+            .referral_count
+            .checked_add(1)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_stats() -> ReferrerStats {
+        ReferrerStats {
+            referrer: Pubkey::new_unique(),
+            total_points_earned: 0,
+            referral_count: 0,
+        }
+    }
+
+    #[test]
+    fn test_stats_accumulate_across_referred_buys() {
+        // ARRANGE
+        let mut stats = create_test_stats();
+
+        println!("🧪 Testing the referrer stats across several referred buys");
+
+        // ACT: Three buys, the last one after the points ran dry
+        for points in [1_500, 2_500, 0] {
+            stats.record_referral(points).unwrap();
+        }
+
+        // ASSERT: Every buy is counted, only the points paid add up
+        assert_eq!(stats.referral_count, 3);
+        assert_eq!(stats.total_points_earned, 4_000);
+
+        println!("✅ Referrer stats accumulation test passed!");
+    }
+
+    #[test]
+    fn test_stats_overflow_is_an_error() {
+        let mut stats = create_test_stats();
+        stats.total_points_earned = u64::MAX;
+
+        assert_eq!(
+            stats.record_referral(1).unwrap_err(),
+            error!(AmmError::MathOverflow)
+        );
+
+        // Nothing is counted for the failed buy
+        assert_eq!(stats.referral_count, 0);
+    }
+}
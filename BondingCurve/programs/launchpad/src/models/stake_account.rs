@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+/// A wallet's stake in a staking pool. Created on the wallet's first stake.
+#[account]
+#[derive(InitSpace)]
+pub struct StakeAccount {
+    /// Staking pool the tokens are deposited in
+    pub staking: Pubkey,
+    /// Staking wallet
+    pub owner: Pubkey,
+    /// Meme tokens currently staked
+    pub amount: u64,
+    /// Unix timestamp of the wallet's latest deposit
+    pub stake_ts: i64,
+    /// Rewards already accounted for at the pool's current reward per share
+    pub reward_debt: u128,
+    /// Rewards earned before the latest stake change and not claimed yet
+    pub unclaimed_rewards: u64,
+}
+
+impl StakeAccount {
+    /// Prefix for stake account PDA derivation
+    pub const STAKE_ACCOUNT_PREFIX: &'static [u8; 13] = b"stake_account";
+}
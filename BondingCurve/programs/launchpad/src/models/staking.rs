@@ -1,12 +1,596 @@
+use crate::consts::{ANCHOR_DISCRIMINATOR, MAX_AIRDROP_RECIPIENTS, REWARD_PER_SHARE_PRECISION};
+use crate::err::AmmError;
+use crate::libraries::big_num::U128;
+use crate::libraries::MulDiv;
+use crate::models::stake_account::StakeAccount;
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
 
 #[account]
 #[derive(InitSpace)]
 pub struct StakingPool {
     pub to_airdrop: u64,
-    pub padding: [u8; 32],
+    /// Meme tokens deposited by all stakers
+    pub total_staked: u64,
+    /// Quote rewards per staked token, scaled by `REWARD_PER_SHARE_PRECISION`
+    pub acc_reward_per_share: u128,
+    /// Airdrops can't go out before this time (0 disables)
+    pub airdrop_start_ts: i64,
+    /// Airdrops can't go out from this time on (0 never expires)
+    pub airdrop_end_ts: i64,
+    /// Account layout version, `VERSION` for staking pools written by this program
+    pub version: u8,
 }
 
 impl StakingPool {
     pub const SIGNER_PDA_PREFIX: &'static [u8; 6] = b"signer";
+    /// Current account layout version. Bump it, and add an upgrade from the
+    /// previous layout, whenever a field is added
+    pub const VERSION: u8 = 2;
+    /// Account size of a v1 staking pool, see `StakingPoolV1`
+    pub const V1_SPACE: usize = ANCHOR_DISCRIMINATOR + StakingPoolV1::INIT_SPACE;
+
+    /// Reads a v1 staking pool account, discriminator included, into the
+    /// current layout. v1 had no stakers, rewards or airdrop window, so only
+    /// `to_airdrop` carries over. Anything else is refused.
+    pub fn upgrade_v1(data: &[u8]) -> Result<StakingPool> {
+        if data.len() != Self::V1_SPACE
+            || data[..ANCHOR_DISCRIMINATOR] != StakingPool::DISCRIMINATOR
+        {
+            return Err(error!(AmmError::UnsupportedPoolVersion));
+        }
+
+        let v1 = StakingPoolV1::deserialize(&mut &data[ANCHOR_DISCRIMINATOR..])?;
+
+        Ok(StakingPool {
+            to_airdrop: v1.to_airdrop,
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            airdrop_start_ts: 0,
+            airdrop_end_ts: 0,
+            version: Self::VERSION,
+        })
+    }
+
+    /// Sets the airdrop window. Either bound may be 0 to leave that side open.
+    pub fn set_airdrop_window(&mut self, start_ts: i64, end_ts: i64) -> Result<()> {
+        if end_ts != 0 && end_ts <= start_ts {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        self.airdrop_start_ts = start_ts;
+        self.airdrop_end_ts = end_ts;
+
+        Ok(())
+    }
+
+    /// Whether airdrops may go out at `now`.
+    pub fn is_airdrop_open(&self, now: i64) -> bool {
+        now >= self.airdrop_start_ts && (self.airdrop_end_ts == 0 || now < self.airdrop_end_ts)
+    }
+
+    /// Rejects airdrops outside the airdrop window.
+    pub fn check_airdrop_window(&self, now: i64) -> Result<()> {
+        if !self.is_airdrop_open(now) {
+            return Err(error!(AmmError::AirdropWindowClosed));
+        }
+
+        Ok(())
+    }
+
+    /// Books `amount` unclaimed airdrop tokens swept back into the staking
+    /// vault after the window expired. They rejoin `to_airdrop` for the next
+    /// airdrop.
+    pub fn reclaim_airdrop(&mut self, amount: u64, now: i64) -> Result<()> {
+        if self.airdrop_end_ts == 0 || now < self.airdrop_end_ts {
+            return Err(error!(AmmError::AirdropNotExpired));
+        }
+
+        if amount == 0 {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        self.to_airdrop = self
+            .to_airdrop
+            .checked_add(amount)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Deducts a batch of airdrops from `to_airdrop`, returning their sum.
+    /// Nothing is deducted unless the whole batch fits.
+    pub fn take_airdrop(&mut self, amounts: &[u64]) -> Result<u64> {
+        if amounts.is_empty() {
+            return Err(error!(AmmError::InvalidArg));
+        }
+
+        if amounts.len() > MAX_AIRDROP_RECIPIENTS {
+            return Err(error!(AmmError::BatchTooLarge));
+        }
+
+        if amounts.contains(&0) {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        let total = amounts
+            .iter()
+            .try_fold(0_u64, |total, amount| total.checked_add(*amount))
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        self.to_airdrop = self
+            .to_airdrop
+            .checked_sub(total)
+            .ok_or(error!(AmmError::AirdropExceedsAllocation))?;
+
+        Ok(total)
+    }
+
+    /// Books a deposit of `amount` meme tokens into `account` at `now`.
+    pub fn stake(&mut self, account: &mut StakeAccount, amount: u64, now: i64) -> Result<()> {
+        if amount == 0 {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        self.settle_rewards(account)?;
+
+        account.amount = account
+            .amount
+            .checked_add(amount)
+            .ok_or(error!(AmmError::MathOverflow))?;
+        account.stake_ts = now;
+        account.reward_debt = self.accumulated_rewards(account.amount)?;
+
+        self.total_staked = self
+            .total_staked
+            .checked_add(amount)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Books a withdrawal of `amount` staked tokens from `account` and returns
+    /// the airdrop share paid with it: while the airdrop window is open, the
+    /// withdrawn share of the total stake, applied to what is left of
+    /// `to_airdrop`. Quote rewards earned so far stay claimable.
+    pub fn unstake(&mut self, account: &mut StakeAccount, amount: u64, now: i64) -> Result<u64> {
+        if amount == 0 {
+            return Err(error!(AmmError::NoZeroTokens));
+        }
+
+        if amount > account.amount {
+            return Err(error!(AmmError::InsufficientBalance));
+        }
+
+        let airdrop_share = if self.is_airdrop_open(now) {
+            self.to_airdrop
+                .mul_div_floor(amount, self.total_staked)
+                .ok_or(error!(AmmError::MathOverflow))?
+        } else {
+            0
+        };
+
+        self.settle_rewards(account)?;
+
+        self.to_airdrop -= airdrop_share;
+        self.total_staked -= amount;
+        account.amount -= amount;
+        account.reward_debt = self.accumulated_rewards(account.amount)?;
+
+        Ok(airdrop_share)
+    }
+
+    /// Spreads `amount` quote rewards over the current stake, MasterChef style.
+    pub fn add_rewards(&mut self, amount: u64) -> Result<()> {
+        if self.total_staked == 0 {
+            return Err(error!(AmmError::NoStakers));
+        }
+
+        let per_share = (amount as u128)
+            .checked_mul(REWARD_PER_SHARE_PRECISION)
+            .ok_or(error!(AmmError::MathOverflow))?
+            / self.total_staked as u128;
+
+        self.acc_reward_per_share = self
+            .acc_reward_per_share
+            .checked_add(per_share)
+            .ok_or(error!(AmmError::MathOverflow))?;
+
+        Ok(())
+    }
+
+    /// Quote rewards `account` could claim right now.
+    pub fn pending_rewards(&self, account: &StakeAccount) -> Result<u64> {
+        let earned = self
+            .accumulated_rewards(account.amount)?
+            .saturating_sub(account.reward_debt);
+
+        u64::try_from(earned)
+            .ok()
+            .and_then(|earned| earned.checked_add(account.unclaimed_rewards))
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+
+    /// Pays out everything `account` has earned and returns the amount.
+    pub fn claim_rewards(&mut self, account: &mut StakeAccount) -> Result<u64> {
+        let rewards = self.pending_rewards(account)?;
+
+        account.unclaimed_rewards = 0;
+        account.reward_debt = self.accumulated_rewards(account.amount)?;
+
+        Ok(rewards)
+    }
+
+    // Moves what `account` earned so far into `unclaimed_rewards` ahead of a
+    // stake change, so the new reward debt doesn't swallow it
+    fn settle_rewards(&self, account: &mut StakeAccount) -> Result<()> {
+        account.unclaimed_rewards = self.pending_rewards(account)?;
+
+        Ok(())
+    }
+
+    // `staked * acc_reward_per_share`, unscaled. Widened so a big stake
+    // joining after dust earned a large accumulator doesn't overflow
+    fn accumulated_rewards(&self, staked: u64) -> Result<u128> {
+        U128::from(staked)
+            .mul_div_floor(
+                U128::from(self.acc_reward_per_share),
+                U128::from(REWARD_PER_SHARE_PRECISION),
+            )
+            .map(|rewards| rewards.as_u128())
+            .ok_or(error!(AmmError::MathOverflow))
+    }
+}
+
+/// A staking pool as the first release wrote it, only an airdrop allocation
+/// and padding. Only read by `StakingPool::upgrade_v1`
+#[derive(AnchorDeserialize, AnchorSerialize, InitSpace)]
+struct StakingPoolV1 {
+    to_airdrop: u64,
+    padding: [u8; 32],
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_staking_pool(to_airdrop: u64) -> StakingPool {
+        StakingPool {
+            to_airdrop,
+            total_staked: 0,
+            acc_reward_per_share: 0,
+            airdrop_start_ts: 0,
+            airdrop_end_ts: 0,
+            version: StakingPool::VERSION,
+        }
+    }
+
+    fn create_stake_account(staking: Pubkey) -> StakeAccount {
+        StakeAccount {
+            staking,
+            owner: Pubkey::new_unique(),
+            amount: 0,
+            stake_ts: 0,
+            reward_debt: 0,
+            unclaimed_rewards: 0,
+        }
+    }
+
+    #[test]
+    fn test_airdrop_batch_to_three_recipients() {
+        // ARRANGE
+        let mut staking = create_staking_pool(1_000_000);
+
+        println!("🧪 Testing a batched airdrop to three recipients");
+
+        // ACT
+        let total = staking.take_airdrop(&[100_000, 250_000, 650_000]).unwrap();
+
+        // ASSERT: The whole allocation is spent, nothing more
+        assert_eq!(total, 1_000_000);
+        assert_eq!(staking.to_airdrop, 0);
+
+        println!("✅ Airdrop batch test passed!");
+    }
+
+    #[test]
+    fn test_airdrop_batch_rejections() {
+        let mut staking = create_staking_pool(1_000);
+
+        let err = staking.take_airdrop(&[600, 401]).unwrap_err();
+        assert_eq!(err, error!(AmmError::AirdropExceedsAllocation));
+        assert_eq!(staking.to_airdrop, 1_000);
+
+        let err = staking
+            .take_airdrop(&[1; MAX_AIRDROP_RECIPIENTS + 1])
+            .unwrap_err();
+        assert_eq!(err, error!(AmmError::BatchTooLarge));
+
+        let err = staking.take_airdrop(&[]).unwrap_err();
+        assert_eq!(err, error!(AmmError::InvalidArg));
+
+        let err = staking.take_airdrop(&[10, 0]).unwrap_err();
+        assert_eq!(err, error!(AmmError::NoZeroTokens));
+
+        let err = staking.take_airdrop(&[u64::MAX, 1]).unwrap_err();
+        assert_eq!(err, error!(AmmError::MathOverflow));
+
+        assert_eq!(staking.to_airdrop, 1_000);
+    }
+
+    #[test]
+    fn test_airdrop_window() {
+        // ARRANGE: Airdrops run from t=1_000 to t=2_000
+        let mut staking = create_staking_pool(1_000);
+        staking.set_airdrop_window(1_000, 2_000).unwrap();
+
+        println!("🧪 Testing the airdrop window");
+
+        // ACT & ASSERT: Closed before the start
+        let err = staking.check_airdrop_window(999).unwrap_err();
+        assert_eq!(err, error!(AmmError::AirdropWindowClosed));
+
+        // Open from the start up to the last second before expiry
+        staking.check_airdrop_window(1_000).unwrap();
+        staking.check_airdrop_window(1_999).unwrap();
+
+        // Closed again once expired
+        let err = staking.check_airdrop_window(2_000).unwrap_err();
+        assert_eq!(err, error!(AmmError::AirdropWindowClosed));
+
+        // Unset bounds leave the window open
+        staking.set_airdrop_window(0, 0).unwrap();
+        assert!(staking.is_airdrop_open(0));
+        assert!(staking.is_airdrop_open(i64::MAX));
+
+        // Windows ending before they start are refused
+        let err = staking.set_airdrop_window(2_000, 1_000).unwrap_err();
+        assert_eq!(err, error!(AmmError::InvalidArg));
+
+        println!("✅ Airdrop window test passed!");
+    }
+
+    #[test]
+    fn test_reclaim_expired_airdrop() {
+        // ARRANGE: Half of the allocation went out, 400 of it unclaimed
+        let mut staking = create_staking_pool(500);
+        staking.set_airdrop_window(1_000, 2_000).unwrap();
+
+        println!("🧪 Testing the expired airdrop reclaim");
+
+        // ACT & ASSERT: Nothing comes back while the window is open
+        let err = staking.reclaim_airdrop(400, 1_500).unwrap_err();
+        assert_eq!(err, error!(AmmError::AirdropNotExpired));
+        assert_eq!(staking.to_airdrop, 500);
+
+        // After expiry the unclaimed tokens rejoin the allocation
+        staking.reclaim_airdrop(400, 2_000).unwrap();
+        assert_eq!(staking.to_airdrop, 900);
+
+        // Windows that never expire never reclaim
+        staking.set_airdrop_window(0, 0).unwrap();
+        let err = staking.reclaim_airdrop(400, i64::MAX).unwrap_err();
+        assert_eq!(err, error!(AmmError::AirdropNotExpired));
+
+        println!("✅ Expired airdrop reclaim test passed!");
+    }
+
+    #[test]
+    fn test_stake_deposit() {
+        // ARRANGE
+        let mut staking = create_staking_pool(0);
+        let mut account = create_stake_account(Pubkey::new_unique());
+
+        println!("🧪 Testing staking deposits");
+
+        // ACT: Two deposits from the same wallet
+        staking.stake(&mut account, 1_000, 100).unwrap();
+        staking.stake(&mut account, 500, 200).unwrap();
+
+        // ASSERT: Both are tracked, the timestamp follows the latest one
+        assert_eq!(account.amount, 1_500);
+        assert_eq!(account.stake_ts, 200);
+        assert_eq!(staking.total_staked, 1_500);
+
+        let err = staking.stake(&mut account, 0, 0).unwrap_err();
+        assert_eq!(err, error!(AmmError::NoZeroTokens));
+
+        println!("✅ Stake deposit test passed!");
+    }
+
+    #[test]
+    fn test_partial_unstake() {
+        // ARRANGE
+        let mut staking = create_staking_pool(0);
+        let mut account = create_stake_account(Pubkey::new_unique());
+        staking.stake(&mut account, 1_000, 0).unwrap();
+
+        // ACT
+        let airdrop_share = staking.unstake(&mut account, 400, 0).unwrap();
+
+        // ASSERT: Nothing to share out, the rest stays staked
+        assert_eq!(airdrop_share, 0);
+        assert_eq!(account.amount, 600);
+        assert_eq!(staking.total_staked, 600);
+
+        // Withdrawing more than is staked is refused without side effects
+        let err = staking.unstake(&mut account, 601, 0).unwrap_err();
+        assert_eq!(err, error!(AmmError::InsufficientBalance));
+        assert_eq!(account.amount, 600);
+
+        println!("✅ Partial unstake test passed!");
+    }
+
+    #[test]
+    fn test_late_staker_earns_nothing_past() {
+        // ARRANGE: Alice staked alone through 1_000 of rewards
+        let mut staking = create_staking_pool(1_000_000);
+        let key = Pubkey::new_unique();
+        let mut alice = create_stake_account(key);
+        let mut bob = create_stake_account(key);
+        staking.stake(&mut alice, 250, 0).unwrap();
+        staking.add_rewards(1_000).unwrap();
+        staking.set_airdrop_window(1_000, 2_000).unwrap();
+
+        println!("🧪 Testing a stake in and out around past rewards");
+
+        // ACT: Bob stakes 3x her stake and leaves before the airdrop opens
+        staking.stake(&mut bob, 750, 500).unwrap();
+        let airdrop_share = staking.unstake(&mut bob, 750, 500).unwrap();
+
+        // ASSERT: Bob takes nothing, neither rewards nor airdrop allocation
+        assert_eq!(airdrop_share, 0);
+        assert_eq!(staking.pending_rewards(&bob).unwrap(), 0);
+        assert_eq!(staking.claim_rewards(&mut bob).unwrap(), 0);
+        assert_eq!(staking.pending_rewards(&alice).unwrap(), 1_000);
+        assert_eq!(staking.to_airdrop, 1_000_000);
+        assert_eq!(staking.total_staked, 250);
+
+        println!("✅ Late staker test passed!");
+    }
+
+    #[test]
+    fn test_unstake_airdrop_share() {
+        // ARRANGE: Two stakers holding 1/4 and 3/4 of the stake, and an
+        // airdrop window from 1_000 to 2_000
+        let mut staking = create_staking_pool(1_000_000);
+        let key = Pubkey::new_unique();
+        let mut alice = create_stake_account(key);
+        let mut bob = create_stake_account(key);
+        staking.stake(&mut alice, 250, 0).unwrap();
+        staking.stake(&mut bob, 750, 0).unwrap();
+        staking.set_airdrop_window(1_000, 2_000).unwrap();
+
+        println!("🧪 Testing the airdrop share paid on unstake");
+
+        // ACT & ASSERT: Nothing is shared out before the window opens
+        assert_eq!(staking.unstake(&mut alice, 50, 999).unwrap(), 0);
+        staking.stake(&mut alice, 50, 999).unwrap();
+
+        // Inside it, each withdrawal takes its share of what is left
+        assert_eq!(staking.unstake(&mut alice, 250, 1_000).unwrap(), 250_000);
+        assert_eq!(staking.unstake(&mut bob, 375, 1_999).unwrap(), 375_000);
+
+        // Once it closed, the rest stays for the next airdrop
+        assert_eq!(staking.unstake(&mut bob, 375, 2_000).unwrap(), 0);
+        assert_eq!(staking.to_airdrop, 375_000);
+        assert_eq!(staking.total_staked, 0);
+
+        println!("✅ Unstake airdrop share test passed!");
+    }
+
+    #[test]
+    fn test_reward_debt_across_stakers() {
+        // ARRANGE
+        let mut staking = create_staking_pool(0);
+        let key = Pubkey::new_unique();
+        let mut alice = create_stake_account(key);
+        let mut bob = create_stake_account(key);
+
+        println!("🧪 Testing reward debt bookkeeping across stakers");
+
+        // ACT: Alice is alone for the first 1_000 of rewards
+        staking.stake(&mut alice, 100, 0).unwrap();
+        staking.add_rewards(1_000).unwrap();
+
+        // Bob joins with 3x her stake, then 2_000 more arrive
+        staking.stake(&mut bob, 300, 0).unwrap();
+        assert_eq!(staking.pending_rewards(&bob).unwrap(), 0);
+        staking.add_rewards(2_000).unwrap();
+
+        // ASSERT: Alice earned all of the first batch and 1/4 of the second
+        assert_eq!(staking.pending_rewards(&alice).unwrap(), 1_500);
+        assert_eq!(staking.pending_rewards(&bob).unwrap(), 1_500);
+
+        // A top-up doesn't lose what was earned before it
+        staking.stake(&mut alice, 100, 0).unwrap();
+        assert_eq!(staking.pending_rewards(&alice).unwrap(), 1_500);
+
+        // Claiming pays out once
+        assert_eq!(staking.claim_rewards(&mut alice).unwrap(), 1_500);
+        assert_eq!(staking.claim_rewards(&mut alice).unwrap(), 0);
+
+        // Now 200 vs 300 staked, so 1_000 splits 400 / 600
+        staking.add_rewards(1_000).unwrap();
+        assert_eq!(staking.claim_rewards(&mut alice).unwrap(), 400);
+
+        // Unstaking keeps Bob's earnings claimable
+        staking.unstake(&mut bob, 300, 0).unwrap();
+        assert_eq!(staking.claim_rewards(&mut bob).unwrap(), 2_100);
+        assert_eq!(staking.pending_rewards(&bob).unwrap(), 0);
+
+        println!("✅ Reward debt bookkeeping test passed!");
+    }
+
+    #[test]
+    fn test_rewards_need_stakers() {
+        let mut staking = create_staking_pool(0);
+
+        let err = staking.add_rewards(1_000).unwrap_err();
+        assert_eq!(err, error!(AmmError::NoStakers));
+        assert_eq!(staking.acc_reward_per_share, 0);
+    }
+
+    #[test]
+    fn test_reward_accumulator_after_dust_stake() {
+        // ARRANGE: A single base unit staked, the smallest possible share
+        let mut staking = create_staking_pool(0);
+        let mut dust = create_stake_account(Pubkey::new_unique());
+        staking.stake(&mut dust, 1, 0).unwrap();
+
+        println!("🧪 Testing the reward accumulator after a dust first stake");
+
+        // ACT: Route 20 SOL a thousand times over that single unit
+        for _ in 0..1_000 {
+            staking.add_rewards(20_000_000_000).unwrap();
+        }
+
+        // ASSERT: The accumulator went well past u64 and routing kept working
+        assert!(staking.acc_reward_per_share > u64::MAX as u128);
+        assert_eq!(staking.pending_rewards(&dust).unwrap(), 20_000_000_000_000);
+
+        // A whale joining afterwards neither overflows nor inherits the past
+        let mut whale = create_stake_account(Pubkey::new_unique());
+        staking.stake(&mut whale, 100_000_000_000_000, 0).unwrap();
+        assert_eq!(staking.pending_rewards(&whale).unwrap(), 0);
+        staking.add_rewards(100_000_000_001).unwrap();
+        assert_eq!(staking.pending_rewards(&whale).unwrap(), 100_000_000_000);
+        assert_eq!(staking.claim_rewards(&mut dust).unwrap(), 20_000_000_000_000);
+
+        println!("✅ Dust stake accumulator test passed!");
+    }
+
+    #[test]
+    fn test_upgrade_v1_staking_account() {
+        // ARRANGE: A staking pool written by the first release
+        let v1 = StakingPoolV1 {
+            to_airdrop: 1_000_000,
+            padding: [0; 32],
+        };
+        let mut data = StakingPool::DISCRIMINATOR.to_vec();
+        v1.serialize(&mut data).unwrap();
+
+        println!("🧪 Testing the v1 to v2 staking account upgrade");
+
+        // ACT
+        let upgraded = StakingPool::upgrade_v1(&data).unwrap();
+
+        // ASSERT: The allocation carries over into the grown layout
+        assert_eq!(data.len(), StakingPool::V1_SPACE);
+        assert_eq!(StakingPool::V1_SPACE, 48);
+        assert_eq!(upgraded.to_airdrop, 1_000_000);
+        assert_eq!(upgraded.acc_reward_per_share, 0);
+        assert_eq!(upgraded.version, StakingPool::VERSION);
+        assert!(StakingPool::try_deserialize(&mut &data[..]).is_err());
+
+        // Current accounts aren't upgraded again
+        let mut current = Vec::new();
+        upgraded.try_serialize(&mut current).unwrap();
+        assert_eq!(
+            StakingPool::upgrade_v1(&current).err().unwrap(),
+            error!(AmmError::UnsupportedPoolVersion)
+        );
+
+        println!("✅ Staking account upgrade test passed!");
+    }
 }
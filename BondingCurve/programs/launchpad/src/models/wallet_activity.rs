@@ -0,0 +1,88 @@
+use crate::err::AmmError;
+use anchor_lang::prelude::*;
+
+/// Last trade of a wallet on a pool, used to enforce the pool's per-wallet
+/// cooldown. Created on the wallet's first swap.
+#[account]
+#[derive(InitSpace)]
+pub struct WalletActivity {
+    /// Pool the wallet trades on
+    pub pool: Pubkey,
+    /// Trading wallet
+    pub wallet: Pubkey,
+    /// Unix timestamp of the wallet's last swap on the pool
+    pub last_trade_ts: i64,
+}
+
+impl WalletActivity {
+    /// Prefix for wallet activity PDA derivation
+    pub const WALLET_ACTIVITY_PREFIX: &'static [u8; 15] = b"wallet_activity";
+
+    /// Rejects a trade less than `cooldown_secs` after the wallet's previous
+    /// one, then records `now` as its last trade. Zero disables the cooldown.
+    pub fn record_trade(
+        &mut self,
+        pool: Pubkey,
+        wallet: Pubkey,
+        now: i64,
+        cooldown_secs: u64,
+    ) -> Result<()> {
+        if cooldown_secs > 0 && now.saturating_sub(self.last_trade_ts) < cooldown_secs as i64 {
+            return Err(error!(AmmError::WalletCooldown));
+        }
+
+        self.pool = pool;
+        self.wallet = wallet;
+        self.last_trade_ts = now;
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn create_test_activity() -> WalletActivity {
+        WalletActivity {
+            pool: Pubkey::default(),
+            wallet: Pubkey::default(),
+            last_trade_ts: 0,
+        }
+    }
+
+    #[test]
+    fn test_cooldown_boundary() {
+        let mut activity = create_test_activity();
+        let (pool, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        // The first trade of a fresh account always goes through
+        activity.record_trade(pool, wallet, 1_000, 30).unwrap();
+        assert_eq!(activity.last_trade_ts, 1_000);
+        assert_eq!(activity.wallet, wallet);
+
+        // One second short of the cooldown is rejected and records nothing
+        let err = activity.record_trade(pool, wallet, 1_029, 30).unwrap_err();
+        assert_eq!(err, error!(AmmError::WalletCooldown));
+        assert_eq!(activity.last_trade_ts, 1_000);
+
+        // Exactly the cooldown later is allowed
+        activity.record_trade(pool, wallet, 1_030, 30).unwrap();
+        assert_eq!(activity.last_trade_ts, 1_030);
+
+        println!("✅ Wallet cooldown boundary test passed!");
+    }
+
+    #[test]
+    fn test_zero_cooldown_disabled() {
+        let mut activity = create_test_activity();
+        let (pool, wallet) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+        activity.record_trade(pool, wallet, 1_000, 0).unwrap();
+        activity.record_trade(pool, wallet, 1_000, 0).unwrap();
+
+        assert_eq!(activity.last_trade_ts, 1_000);
+
+        println!("✅ Disabled wallet cooldown test passed!");
+    }
+}
@@ -2,54 +2,262 @@ import * as anchor from "@coral-xyz/anchor";
 import { Program, BN } from "@coral-xyz/anchor";
 import { Launchpad } from "../target/types/launchpad";
 import {
+  ComputeBudgetProgram,
   PublicKey,
   Keypair,
   LAMPORTS_PER_SOL,
   SystemProgram,
 } from "@solana/web3.js";
-import { TOKEN_PROGRAM_ID } from "@coral-xyz/anchor/dist/cjs/utils/token";
-import { getAssociatedTokenAddressSync } from "@solana/spl-token";
+import {
+  NATIVE_MINT,
+  TOKEN_PROGRAM_ID,
+  createAccount,
+  createMint,
+  createWrappedNativeAccount,
+  getAccount,
+  getOrCreateAssociatedTokenAccount,
+} from "@solana/spl-token";
+import { assert } from "chai";
+import * as fs from "fs";
+import * as path from "path";
+
+// Accounts the program expects at fixed addresses, loaded at genesis from
+// tests/fixtures (see Anchor.toml): the global config naming `admin`, the
+// WSOL target config and the points mint
+const POINTS_MINT = new PublicKey("ptsVM2dwpBVhu6uR3D1zzoRSjm1TC8gdmBEk8jpTP1P");
+const BP_FEE_KEY = new PublicKey("6YNJG9KDex3eNAmh1i64KUDbfKBiESkew3AWmnf6FiCy");
+
+const CREATOR_LOCKED_TOKENS = new BN("10000000000000"); // 10M tokens
+const TRADING_TOKENS = new BN("690000000000000"); // 690M tokens
 
 describe("Launchpad", () => {
   const provider = anchor.AnchorProvider.env();
   anchor.setProvider(provider);
-  const user = provider.wallet;
+  const user = provider.wallet as anchor.Wallet;
+  const connection = provider.connection;
   const program = anchor.workspace.Launchpad as Program<Launchpad>;
 
+  const admin = Keypair.fromSecretKey(
+    Uint8Array.from(
+      JSON.parse(fs.readFileSync(path.join(__dirname, "fixtures/admin.json"), "utf8"))
+    )
+  );
+
+  const pda = (...seeds: Buffer[]) =>
+    PublicKey.findProgramAddressSync(seeds, program.programId)[0];
+
+  const globalConfig = pda(Buffer.from("global_config"));
+  const pointsEpoch = pda(Buffer.from("points_epoch"));
+  const pointsPda = pda(Buffer.from("points_pda"));
+  const targetConfig = pda(Buffer.from("config"), NATIVE_MINT.toBuffer());
+
+  const memeMint = Keypair.generate();
+  const pool = pda(
+    Buffer.from("bound_pool"),
+    memeMint.publicKey.toBuffer(),
+    NATIVE_MINT.toBuffer()
+  );
+  const poolSigner = pda(Buffer.from("signer"), pool.toBuffer());
+  const creatorLockVault = pda(Buffer.from("creator_lock_vault"), pool.toBuffer());
+
+  let memeVault: PublicKey;
+  let quoteVault: PublicKey;
+  let swapUnitsConsumed: number;
+
+  before(async () => {
+    const sig = await connection.requestAirdrop(admin.publicKey, LAMPORTS_PER_SOL);
+    await connection.confirmTransaction(sig);
+  });
+
+  it("should initialize the points epoch", async () => {
+    await program.methods
+      .initPointsEpoch(new BN(1), new BN(1))
+      .accountsPartial({
+        sender: admin.publicKey,
+        globalConfig,
+        pointsEpoch,
+        systemProgram: SystemProgram.programId,
+      })
+      .signers([admin])
+      .rpc();
+
+    const epoch = await program.account.pointsEpoch.fetch(pointsEpoch);
+    assert.equal(epoch.epochNumber.toNumber(), 1);
+  });
+
   it("should create a new pool", async () => {
-    // WSOL mint address
-    const WSOL_MINT = new PublicKey(
-      "So11111111111111111111111111111111111111112"
+    // The pool signer must own the meme mint and both vaults up front
+    await createMint(connection, user.payer, poolSigner, null, 6, memeMint);
+    memeVault = await createAccount(
+      connection,
+      user.payer,
+      memeMint.publicKey,
+      poolSigner,
+      Keypair.generate()
     );
-
-    const pool = Keypair.generate();
-    const tokenMint = Keypair.generate();
-    const memeVault = Keypair.generate();
-    const memeMint = Keypair.generate();
-    const targetConfig = Keypair.generate();
-    // Derive the associated token account for WSOL (quoteVault)
-    const quoteVault = getAssociatedTokenAddressSync(
-      WSOL_MINT,
-      pool.publicKey, // Assuming pool is the owner of the quoteVault
-      true // Allow owner off-curve for PDAs
+    quoteVault = await createAccount(
+      connection,
+      user.payer,
+      NATIVE_MINT,
+      poolSigner,
+      Keypair.generate()
+    );
+    const feeQuoteVault = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user.payer,
+      NATIVE_MINT,
+      BP_FEE_KEY
     );
 
-    const targetAmount = new BN(1_000_000_000); // 1 billion base units
+    const page = Buffer.alloc(4);
+    page.writeUInt32LE(0);
 
-    const tx = await program.methods
-      .newPool(targetAmount)
+    await program.methods
+      .newPool(
+        new BN(0), // airdropped_tokens
+        CREATOR_LOCKED_TOKENS,
+        new BN(86_400), // creator_lock_period
+        new BN(0), // creator_vesting_period
+        { tokenTarget: {} },
+        new BN(0), // fee_meme_percent
+        new BN(0), // virtual_sol_reserves
+        new BN(0), // virtual_meme_reserves
+        { raydium: {} },
+        0, // first_buyer_bonus_bps
+        0, // burn_unsold_bps
+        { treasury: {} },
+        { positiveIntercept: {} }
+      )
       .accountsPartial({
         sender: user.publicKey,
-        feeQuoteVault: quoteVault,
+        creator: user.publicKey,
+        pool,
+        creatorLock: pda(Buffer.from("creator_lock"), pool.toBuffer()),
+        creatorLockVault,
+        creatorStats: pda(Buffer.from("creator_stats"), user.publicKey.toBuffer()),
+        registryHead: pda(Buffer.from("pool_registry_head")),
+        registryPage: pda(Buffer.from("pool_registry"), page),
         memeMint: memeMint.publicKey,
-        quoteMint: WSOL_MINT,
-        pool: pool.publicKey,
-        targetConfig: targetConfig.publicKey,
-        tokenProgram: TOKEN_PROGRAM_ID,
+        quoteVault,
+        quoteMint: NATIVE_MINT,
+        feeQuoteVault: feeQuoteVault.address,
+        memeVault,
+        targetConfig,
+        poolSigner,
         systemProgram: SystemProgram.programId,
+        tokenProgram: TOKEN_PROGRAM_ID,
       })
-      .signers([pool, tokenMint, memeVault, memeMint, targetConfig])
+      .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 })])
       .rpc();
-    console.log("Pool created:", tx);
+
+    // The creator lock sits in its own vault, off the curve
+    const state = await program.account.boundPool.fetch(pool);
+    const lockVault = await getAccount(connection, creatorLockVault);
+    assert.ok(state.memeReserve.tokens.eq(TRADING_TOKENS.sub(CREATOR_LOCKED_TOKENS)));
+    assert.equal(lockVault.amount.toString(), CREATOR_LOCKED_TOKENS.toString());
+  });
+
+  it("should buy meme with WSOL", async () => {
+    const userSol = await createWrappedNativeAccount(
+      connection,
+      user.payer,
+      user.publicKey,
+      LAMPORTS_PER_SOL
+    );
+    const userMeme = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user.payer,
+      memeMint.publicKey,
+      user.publicKey
+    );
+    const userPoints = await getOrCreateAssociatedTokenAccount(
+      connection,
+      user.payer,
+      POINTS_MINT,
+      user.publicKey
+    );
+    const pointsAcc = await createAccount(
+      connection,
+      user.payer,
+      POINTS_MINT,
+      pointsPda,
+      Keypair.generate()
+    );
+
+    const sig = await program.methods
+      .swapY(new BN(LAMPORTS_PER_SOL / 10), new BN(1), new BN(0))
+      .accountsPartial({
+        pool,
+        memeVault,
+        memeMint: memeMint.publicKey,
+        quoteVault,
+        userSol,
+        userMeme: userMeme.address,
+        userPoints: userPoints.address,
+        referrerPoints: null,
+        referralCode: null,
+        pointsEpoch,
+        pointsMint: POINTS_MINT,
+        pointsAcc,
+        pointsEscrow: null,
+        pointsEscrowVault: null,
+        referrerStats: null,
+        owner: user.publicKey,
+        pointsPda,
+        poolSignerPda: poolSigner,
+        buyerRecord: pda(Buffer.from("buyer_record"), pool.toBuffer(), user.publicKey.toBuffer()),
+        walletActivity: pda(
+          Buffer.from("wallet_activity"),
+          pool.toBuffer(),
+          user.publicKey.toBuffer()
+        ),
+        feeExemption: null,
+        priceSnapshots: null,
+        tokenProgram: TOKEN_PROGRAM_ID,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc({ commitment: "confirmed" });
+
+    const bought = await getAccount(connection, userMeme.address);
+    assert.notEqual(bought.amount.toString(), "0");
+
+    // The swap event travels as a self-CPI, so it sits in the inner
+    // instructions rather than the (truncatable) logs
+    const tx = await connection.getTransaction(sig, {
+      commitment: "confirmed",
+      maxSupportedTransactionVersion: 0,
+    });
+    swapUnitsConsumed = tx.meta.computeUnitsConsumed;
+
+    const keys = tx.transaction.message.getAccountKeys({
+      accountKeysFromLookups: tx.meta.loadedAddresses,
+    });
+    const events = tx.meta.innerInstructions
+      .flatMap((inner) => inner.instructions)
+      .filter((ix) => keys.get(ix.programIdIndex).equals(program.programId))
+      .map((ix) => {
+        // Skip the 8-byte EVENT_IX_TAG ahead of the event discriminator
+        const data = anchor.utils.bytes.bs58.decode(ix.data).subarray(8);
+        return program.coder.events.decode(anchor.utils.bytes.base64.encode(data));
+      });
+    const swapEvent = events.find((event) => event && event.name === "swapEvent");
+    assert.ok(swapEvent, "swap event missing from inner instructions");
+    assert.ok(swapEvent.data.pool.equals(pool));
+  });
+
+  it("should budget at least the units a swap consumes", async () => {
+    const simulated = await program.methods
+      .estimateCompute({ swap: {} }, 0)
+      .accounts({})
+      .simulate();
+
+    const log = simulated.raw.find((line) => line.includes("compute_units: "));
+    const units = parseInt(log.split("compute_units: ")[1], 10);
+    assert.isAtLeast(units, swapUnitsConsumed);
+  });
+
+  it("should preview the migration", async () => {
+    // Read only, nothing raised is near the threshold yet
+    await program.methods.simulateMigration().accountsPartial({ pool }).simulate();
   });
 });